@@ -0,0 +1,76 @@
+use std::{collections::HashSet, error::Error, fs, path::Path};
+
+use toto_tosca::csar::Csar;
+
+/// Expands the given files, directories and glob patterns into urls of the
+/// files to check. Files found in directories or by patterns are only
+/// checked if they look like TOSCA files, i.e. declare
+/// `tosca_definitions_version`; files given explicitly are always checked.
+/// Archives (`.csar`) are checked starting from their entry definitions.
+pub fn discover(
+    base: &url::Url,
+    paths: &[String],
+) -> Result<Vec<url::Url>, Box<dyn Error + Send + Sync>> {
+    let mut found = vec![];
+    for path in paths {
+        if Path::new(path).is_dir() {
+            let pattern = Path::new(path).join("**").join("*");
+            found.extend(
+                glob(pattern.to_str().unwrap())?
+                    .into_iter()
+                    .filter(|p| has_yaml_extension(p) && is_tosca_file(p)),
+            );
+        } else if path.contains(['*', '?', '[']) {
+            found.extend(glob(path)?.into_iter().filter(|p| is_tosca_file(p)));
+        } else {
+            found.push(path.clone());
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let urls = found
+        .iter()
+        .map(|path| resolve(base, path))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .flatten()
+        .filter(|url| seen.insert(url.clone()))
+        .collect::<Vec<_>>();
+
+    if urls.is_empty() {
+        return Err("no TOSCA files found".into());
+    }
+    Ok(urls)
+}
+
+/// Url of a file to check, or urls of the definitions of an archive.
+fn resolve(base: &url::Url, path: &str) -> Result<Vec<url::Url>, Box<dyn Error + Send + Sync>> {
+    if Path::new(path).extension().is_some_and(|ext| ext == "csar") {
+        return Ok(Csar::open(Path::new(path))?.definitions()?);
+    }
+    Ok(vec![base.join(path).or(url::Url::parse(path))?])
+}
+
+fn glob(pattern: &str) -> Result<Vec<String>, Box<dyn Error + Send + Sync>> {
+    let mut paths = glob::glob(pattern)?
+        .filter_map(|p| p.ok())
+        .filter(|p| p.is_file())
+        .map(|p| p.to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    paths.sort();
+    Ok(paths)
+}
+
+fn has_yaml_extension(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .is_some_and(|ext| ext == "yaml" || ext == "yml")
+}
+
+fn is_tosca_file(path: &str) -> bool {
+    fs::read_to_string(path).is_ok_and(|content| {
+        content
+            .lines()
+            .any(|line| line.starts_with("tosca_definitions_version:"))
+    })
+}
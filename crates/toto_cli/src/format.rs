@@ -0,0 +1,206 @@
+use serde_json::{json, Value};
+use toto_parser::{DiagnosticCode, ErrorSpan, ParseError, Severity};
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default)]
+pub enum Format {
+    /// annotated source snippets
+    #[default]
+    Human,
+    /// a list of diagnostics
+    Json,
+    /// SARIF 2.1.0 log
+    Sarif,
+}
+
+pub type Diagnostic<'a> = (&'a ParseError, ErrorSpan);
+
+/// One object per diagnostic. Lines and columns are one-based, the end of
+/// the range is exclusive.
+pub fn json(diagnostics: &[Diagnostic]) -> Value {
+    Value::Array(
+        diagnostics
+            .iter()
+            .map(|(err, span)| {
+                let code = err.code();
+                json!({
+                    "file": span.url.as_str(),
+                    "offset": span.offset,
+                    "length": span.len,
+                    "range": {
+                        "start": { "line": span.start.0 + 1, "column": span.start.1 + 1 },
+                        "end": { "line": span.end.0 + 1, "column": span.end.1 + 1 },
+                    },
+                    "severity": code.severity.to_string(),
+                    "code": code.code,
+                    "name": code.name,
+                    "message": err.to_string(),
+                })
+            })
+            .collect(),
+    )
+}
+
+pub fn sarif(diagnostics: &[Diagnostic]) -> Value {
+    let rules = toto_parser::codes::ALL
+        .iter()
+        .chain(toto_tosca::codes::ALL.iter())
+        .copied()
+        .collect::<Vec<_>>();
+
+    let results = diagnostics
+        .iter()
+        .map(|(err, span)| {
+            let code = err.code();
+            json!({
+                "ruleId": code.code,
+                "ruleIndex": rules.iter().position(|r| *r == code),
+                "level": sarif_level(code.severity),
+                "message": { "text": err.to_string() },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": span.url.as_str() },
+                        "region": {
+                            "startLine": span.start.0 + 1,
+                            "startColumn": span.start.1 + 1,
+                            "endLine": span.end.0 + 1,
+                            "endColumn": span.end.1 + 1,
+                            "byteOffset": span.offset,
+                            "byteLength": span.len,
+                        },
+                    },
+                }],
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "toto",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/Shishqa/totosca",
+                    "rules": rules.iter().map(|r| sarif_rule(r)).collect::<Vec<_>>(),
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+fn sarif_rule(code: &DiagnosticCode) -> Value {
+    let summary = code
+        .explanation
+        .split("\n\n")
+        .next()
+        .unwrap_or_default()
+        .replace('\n', " ");
+
+    json!({
+        "id": code.code,
+        "name": code.name,
+        "shortDescription": { "text": summary },
+        "fullDescription": { "text": code.explanation },
+        "help": { "text": code.explanation },
+        "defaultConfiguration": { "level": sarif_level(code.severity) },
+    })
+}
+
+fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info | Severity::Hint => "note",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use toto_parser::{ErrorSpan, ParseError, Severity};
+
+    // Brings in both the `json!` macro and the `json` formatter.
+    use super::{json, sarif, sarif_level};
+
+    fn span(start: (u32, u32), end: (u32, u32), offset: usize, len: usize) -> ErrorSpan {
+        ErrorSpan {
+            url: url::Url::parse("file:///service.yaml").unwrap(),
+            offset,
+            len,
+            start,
+            end,
+        }
+    }
+
+    #[test]
+    fn json_diagnostics() {
+        let err = toto_tosca::codes::DEPRECATED_TYPE.with("type Old is deprecated");
+        let diagnostics = [(&err, span((2, 4), (2, 7), 30, 3))];
+
+        assert_eq!(
+            json(&diagnostics),
+            json!([{
+                "file": "file:///service.yaml",
+                "offset": 30,
+                "length": 3,
+                "range": {
+                    "start": { "line": 3, "column": 5 },
+                    "end": { "line": 3, "column": 8 },
+                },
+                "severity": "warning",
+                "code": "T0106",
+                "name": "deprecated-type",
+                "message": "type Old is deprecated",
+            }])
+        );
+    }
+
+    #[test]
+    fn sarif_results() {
+        let unknown = ParseError::UnknownField("foo".to_string());
+        let lossy = toto_tosca::codes::LOSSY_VERSION_MAPPING.with("version 1.2 was mapped");
+        let diagnostics = [
+            (&unknown, span((0, 0), (0, 3), 0, 3)),
+            (&lossy, span((4, 2), (5, 0), 50, 10)),
+        ];
+
+        let log = sarif(&diagnostics);
+        let run = &log["runs"][0];
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+
+        for (result, code) in results.iter().zip(["T0002", "T0216"]) {
+            assert_eq!(result["ruleId"], code);
+            let index = result["ruleIndex"].as_u64().unwrap() as usize;
+            assert_eq!(rules[index]["id"], code);
+        }
+
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(results[0]["message"]["text"], "unsupported field");
+        assert_eq!(results[1]["level"], "note");
+        assert_eq!(
+            results[1]["locations"][0]["physicalLocation"],
+            json!({
+                "artifactLocation": { "uri": "file:///service.yaml" },
+                "region": {
+                    "startLine": 5,
+                    "startColumn": 3,
+                    "endLine": 6,
+                    "endColumn": 1,
+                    "byteOffset": 50,
+                    "byteLength": 10,
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn sarif_levels() {
+        assert_eq!(sarif_level(Severity::Error), "error");
+        assert_eq!(sarif_level(Severity::Warning), "warning");
+        assert_eq!(sarif_level(Severity::Info), "note");
+        assert_eq!(sarif_level(Severity::Hint), "note");
+    }
+}
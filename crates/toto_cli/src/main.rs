@@ -1,10 +1,21 @@
-use std::{env, error::Error};
+use std::{collections::HashSet, env, error::Error, path::PathBuf};
 
 use clap::{Parser, Subcommand};
 
+mod discover;
+mod format;
 mod models;
-use toto_parser::{get_errors, report_error};
-use toto_tosca::ToscaParser;
+mod watch;
+mod workflow;
+use format::Format;
+use toto_parser::{get_error_span, get_errors, report_error, AsParseError, Severity};
+use toto_tosca::{
+    csar::Csar,
+    fetch::{DefaultFetcher, HttpFetcher},
+    profiles::ProfileRegistry,
+    semantic::DeclarativeWorkflow,
+    ToscaParser,
+};
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
@@ -19,8 +30,70 @@ enum Command {
     /// lint TOSCA file
     ///
     /// This command will report any grammar issues it can find
-    /// within provided file and imported files
-    Check { path: String },
+    /// within provided files and imported files. Directories are
+    /// searched for TOSCA files recursively, glob patterns are expanded.
+    /// Only errors make the check fail, unless warnings are denied
+    Check {
+        #[arg(required = true)]
+        paths: Vec<String>,
+
+        /// fail on warnings as well as on errors
+        #[arg(long)]
+        deny_warnings: bool,
+
+        /// format of the reported diagnostics
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+
+        /// check again whenever one of the checked or imported files changes
+        #[arg(long)]
+        watch: bool,
+
+        /// do not download remote imports, only use the cached ones
+        #[arg(long)]
+        offline: bool,
+
+        /// load imports under URL from a local directory or another url,
+        /// may be given several times
+        #[arg(long, value_name = "URL=MIRROR")]
+        mirror: Vec<String>,
+
+        /// also look for imported profiles in the directory,
+        /// may be given several times
+        #[arg(long, value_name = "DIR")]
+        profile_path: Vec<PathBuf>,
+    },
+
+    /// manage installed profiles
+    ///
+    /// Profiles imported with `profile` are looked up by name in
+    /// the profile directory, then in the directories listed in
+    /// TOTO_PROFILE_PATH
+    Profile {
+        #[clap(subcommand)]
+        command: ProfileCommand,
+    },
+
+    /// work with TOSCA Cloud Service Archives
+    ///
+    /// Archives can be checked with `toto check service.csar`
+    Csar {
+        #[clap(subcommand)]
+        command: CsarCommand,
+    },
+
+    /// inspect the workflows of a service template
+    Workflow {
+        #[clap(subcommand)]
+        command: WorkflowCommand,
+    },
+
+    /// explain a diagnostic
+    ///
+    /// This command will print a description of the diagnostic
+    /// with the given code (e.g. T0101) or name (e.g. unknown-type)
+    /// and an example of how to fix it
+    Explain { code: String },
 
     /// start language server
     ///
@@ -32,37 +105,357 @@ enum Command {
     LS,
 }
 
+#[derive(Subcommand, Debug)]
+enum ProfileCommand {
+    /// list installed and bundled profiles
+    List,
+
+    /// install a profile file, or a directory with the profile files
+    Add { path: PathBuf },
+
+    /// uninstall a profile
+    Remove { name: String },
+}
+
+#[derive(Subcommand, Debug)]
+enum WorkflowCommand {
+    /// print a declarative workflow of a service template
+    ///
+    /// The `deploy` and `undeploy` workflows are synthesised from the
+    /// node templates and the relationships between them, and printed
+    /// as workflow steps in the order they may run
+    Show {
+        template: String,
+
+        /// deploy or undeploy
+        name: String,
+
+        /// do not download remote imports, only use the cached ones
+        #[arg(long)]
+        offline: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum CsarCommand {
+    /// archive a directory and check the archive
+    ///
+    /// The directory has to contain either TOSCA-Metadata/TOSCA.meta
+    /// declaring the Entry-Definitions, or a single YAML file in its root.
+    /// The archive is removed if the check fails
+    Pack {
+        dir: PathBuf,
+
+        /// path of the archive, <dir>.csar by default
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// fail on warnings as well as on errors
+        #[arg(long)]
+        deny_warnings: bool,
+    },
+}
+
 fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
     let args = Args::parse();
 
     match args.command {
-        Command::Check { path } => check(&path),
+        Command::Check {
+            paths,
+            deny_warnings,
+            format,
+            watch,
+            offline,
+            mirror,
+            profile_path,
+        } => check(
+            &paths,
+            deny_warnings,
+            format,
+            watch,
+            parser(offline, &mirror, &profile_path)?,
+        ),
+        Command::Profile { command } => profile(command),
+        Command::Csar { command } => csar(command),
+        Command::Workflow { command } => workflow(command),
+        Command::Explain { code } => explain(&code),
         Command::LS => run_ls(),
     }
 }
 
-fn check(path: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+fn check(
+    paths: &[String],
+    deny_warnings: bool,
+    format: Format,
+    watch: bool,
+    mut parser: ToscaParser,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if !watch {
+        return match check_once(&mut parser, paths, deny_warnings, format)? {
+            true => Err("validation failed".into()),
+            false => Ok(()),
+        };
+    }
+
+    let base = base_url();
+    loop {
+        // Escape codes would corrupt the JSON and SARIF output.
+        if let Format::Human = format {
+            watch::clear_terminal();
+        }
+        if let Err(err) = check_once(&mut parser, paths, deny_warnings, format) {
+            eprintln!("Error: {}", err);
+        }
+
+        // Imports are taken from the last parse, so newly added ones are
+        // watched as well. Paths are discovered again on every poll to pick
+        // up new files matching a directory or a pattern.
+        let imports = parser
+            .get_files()
+            .filter_map(|uri| uri.to_file_path().ok())
+            .collect::<Vec<_>>();
+        watch::wait_for_change(|| {
+            discover::discover(&base, paths)
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|uri| uri.to_file_path().ok())
+                .chain(paths.iter().map(PathBuf::from))
+                .chain(imports.iter().cloned())
+                .collect()
+        });
+    }
+}
+
+fn parser(
+    offline: bool,
+    mirrors: &[String],
+    profile_path: &[PathBuf],
+) -> Result<ToscaParser, Box<dyn Error + Send + Sync>> {
+    let mut parser = ToscaParser::with_fetcher(DefaultFetcher::new(
+        HttpFetcher::new(HttpFetcher::default_cache_dir()).offline(offline),
+    ));
+    for mirror in mirrors {
+        let (prefix, mirror) = parse_mirror(mirror)?;
+        parser = parser.mirror(prefix, mirror);
+    }
+    let profiles = profile_path
+        .iter()
+        .fold(ProfileRegistry::default(), |profiles, dir| {
+            profiles.search(dir)
+        });
+    Ok(parser.profiles(profiles))
+}
+
+/// Parses `URL=MIRROR`, where the mirror is either a url or a local
+/// directory. Both are treated as directories.
+fn parse_mirror(arg: &str) -> Result<(url::Url, url::Url), Box<dyn Error + Send + Sync>> {
+    let Some((prefix, mirror)) = arg.split_once('=') else {
+        return Err(format!("expected URL=MIRROR, got {}", arg).into());
+    };
+    let as_dir = |mut url: url::Url| {
+        if !url.path().ends_with('/') {
+            url.set_path(&format!("{}/", url.path()));
+        }
+        url
+    };
+
+    let prefix = as_dir(url::Url::parse(prefix)?);
+    // Single letter schemes are Windows drives rather than urls.
+    let mirror = match url::Url::parse(mirror) {
+        Ok(url) if url.scheme().len() > 1 => url,
+        _ => url::Url::from_directory_path(env::current_dir()?.join(mirror))
+            .map_err(|_| format!("invalid mirror directory: {}", mirror))?,
+    };
+    Ok((prefix, as_dir(mirror)))
+}
+
+/// Checks the files once and reports the diagnostics, returns whether the
+/// check failed.
+fn check_once(
+    parser: &mut ToscaParser,
+    paths: &[String],
+    deny_warnings: bool,
+    format: Format,
+) -> Result<bool, Box<dyn Error + Send + Sync>> {
     let mut ast = toto_ast::AST::<models::Entity, models::Relation>::new();
 
-    let doc_path = "file://".to_string() + env::current_dir().unwrap().to_str().unwrap() + "/";
-    let doc_path = url::Url::parse(&doc_path).unwrap();
-    let doc_path = doc_path.join(path).or(url::Url::parse(path)).unwrap();
+    let uris = discover::discover(&base_url(), paths)?;
+
+    parser.parse_all(&uris, &mut ast)?;
+
+    // Files shared by several checked files are loaded once, but identical
+    // diagnostics may still be reported from different places.
+    let mut seen = HashSet::new();
+    let errors = get_errors(&ast)
+        .map(|(what, loc)| {
+            let span = get_error_span(what, loc, &ast);
+            (what, loc, ast[what].as_parse().unwrap(), span)
+        })
+        .filter(|(_, _, err, span)| {
+            seen.insert((
+                span.url.clone(),
+                span.offset,
+                span.len,
+                err.code().code,
+                err.to_string(),
+            ))
+        })
+        .collect::<Vec<_>>();
+
+    let count = |severity: Severity| {
+        errors
+            .iter()
+            .filter(|(_, _, err, _)| err.severity() == severity)
+            .count()
+    };
+    let (n_errors, n_warnings) = (count(Severity::Error), count(Severity::Warning));
+
+    match format {
+        Format::Human => errors
+            .iter()
+            .for_each(|(what, loc, _, _)| report_error(*what, *loc, &ast)),
+        Format::Json | Format::Sarif => {
+            let diagnostics = errors
+                .into_iter()
+                .map(|(_, _, err, span)| (err, span))
+                .collect::<Vec<_>>();
+            let output = match format {
+                Format::Json => format::json(&diagnostics),
+                _ => format::sarif(&diagnostics),
+            };
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+    }
+
+    eprintln!(
+        "checked {}: {}, {}",
+        plural(uris.len(), "file"),
+        plural(n_errors, "error"),
+        plural(n_warnings, "warning")
+    );
 
-    let mut parser = ToscaParser::new();
-    parser.parse(&doc_path, &mut ast).unwrap();
+    Ok(n_errors > 0 || (deny_warnings && n_warnings > 0))
+}
+
+fn base_url() -> url::Url {
+    let base = "file://".to_string() + env::current_dir().unwrap().to_str().unwrap() + "/";
+    url::Url::parse(&base).unwrap()
+}
+
+fn plural(n: usize, what: &str) -> String {
+    format!("{} {}{}", n, what, if n == 1 { "" } else { "s" })
+}
+
+fn profile(command: ProfileCommand) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let profiles = ProfileRegistry::default();
+    match command {
+        ProfileCommand::List => {
+            let mut listed = profiles
+                .list()
+                .into_iter()
+                .map(|p| (p.name, p.root.display().to_string()))
+                .collect::<Vec<_>>();
+            // Installed profiles take precedence over the bundled ones.
+            for (name, url) in toto_tosca::grammar::BUILTIN_PROFILES {
+                if !listed.iter().any(|(installed, _)| installed == name) {
+                    listed.push((name.to_string(), url.to_string()));
+                }
+            }
+            listed.sort();
+
+            let width = listed.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+            for (name, location) in listed {
+                println!("{:width$}  {}", name, location);
+            }
+        }
+        ProfileCommand::Add { path } => {
+            let profile = profiles.add(&path)?;
+            println!("installed {} to {}", profile.name, profile.root.display());
+        }
+        ProfileCommand::Remove { name } => {
+            let profile = profiles.remove(&name)?;
+            println!("removed {}", profile.name);
+        }
+    }
+    Ok(())
+}
+
+fn csar(command: CsarCommand) -> Result<(), Box<dyn Error + Send + Sync>> {
+    match command {
+        CsarCommand::Pack {
+            dir,
+            output,
+            deny_warnings,
+        } => {
+            let output = match output {
+                Some(output) => output,
+                None => {
+                    let name = std::path::absolute(&dir)?
+                        .file_name()
+                        .ok_or("can't name the archive, use --output")?
+                        .to_owned();
+                    PathBuf::from(name).with_extension("csar")
+                }
+            };
+            Csar::pack(&dir, &output)?;
+
+            let checked = output.to_string_lossy().into_owned();
+            let failed = check_once(
+                &mut parser(false, &[], &[])?,
+                &[checked],
+                deny_warnings,
+                Format::Human,
+            );
+            if !matches!(failed, Ok(false)) {
+                std::fs::remove_file(&output)?;
+                return Err(failed.err().unwrap_or("validation failed".into()));
+            }
+            println!("packed {} into {}", dir.display(), output.display());
+        }
+    }
+    Ok(())
+}
 
-    let errors = get_errors(&ast).collect::<Vec<_>>();
-    let has_errors = !errors.is_empty();
+fn workflow(command: WorkflowCommand) -> Result<(), Box<dyn Error + Send + Sync>> {
+    match command {
+        WorkflowCommand::Show {
+            template,
+            name,
+            offline,
+        } => {
+            let mut ast = toto_ast::AST::<models::Entity, models::Relation>::new();
+            let uris = discover::discover(&base_url(), std::slice::from_ref(&template))?;
+            let doc_root = parser(offline, &[], &[])?.parse(&uris[0], &mut ast)?;
 
-    errors
-        .into_iter()
-        .for_each(|(what, loc)| report_error(what, loc, &ast));
+            // Steps of a broken template would be misleading.
+            let errors = get_errors(&ast)
+                .filter(|(what, _)| ast[*what].as_parse().unwrap().severity() == Severity::Error)
+                .collect::<Vec<_>>();
+            if !errors.is_empty() {
+                for (what, loc) in errors {
+                    report_error(what, loc, &ast);
+                }
+                return Err("validation failed".into());
+            }
 
-    if has_errors {
-        Err("validation failed".into())
-    } else {
-        Ok(())
+            let service_template = ToscaParser::get_service_template(doc_root, &ast)
+                .ok_or_else(|| format!("{} has no service template", template))?;
+            let workflow = DeclarativeWorkflow::new(&name, service_template, &ast)
+                .map_err(|err| err.to_string())?;
+            print!("{}", workflow::render(&workflow));
+        }
     }
+    Ok(())
+}
+
+fn explain(code: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let Some(code) = toto_tosca::codes::find(code) else {
+        return Err(format!("unknown diagnostic code: {}", code).into());
+    };
+
+    println!("{} ({})\n", code, code.severity);
+    print!("{}", code.explanation);
+    Ok(())
 }
 
 fn run_ls() -> Result<(), Box<dyn Error + Send + Sync>> {
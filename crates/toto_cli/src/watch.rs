@@ -0,0 +1,35 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Blocks until one of the files is modified, created or removed, or until
+/// the list of files itself changes.
+pub fn wait_for_change(files: impl Fn() -> Vec<PathBuf>) {
+    let snapshot = || {
+        files()
+            .into_iter()
+            .map(|f| {
+                let modified = fs::metadata(&f).and_then(|m| m.modified()).ok();
+                (f, modified)
+            })
+            .collect::<Vec<(PathBuf, Option<SystemTime>)>>()
+    };
+
+    let initial = snapshot();
+    while snapshot() == initial {
+        thread::sleep(POLL_INTERVAL);
+    }
+    // Editors often write a file in several steps, let them finish.
+    thread::sleep(POLL_INTERVAL);
+}
+
+pub fn clear_terminal() {
+    print!("\x1B[2J\x1B[1;1H");
+    let _ = io::stdout().flush();
+}
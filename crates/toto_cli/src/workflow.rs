@@ -0,0 +1,37 @@
+use std::fmt::Write;
+
+use toto_tosca::semantic::{Activity, DeclarativeWorkflow};
+
+/// Renders the workflow the way it would be written in `workflows`, steps in
+/// the order they may run.
+pub fn render(workflow: &DeclarativeWorkflow) -> String {
+    let mut out = String::new();
+    writeln!(out, "{}:", workflow.name).unwrap();
+    writeln!(out, "  steps:").unwrap();
+    for step in workflow.steps.iter() {
+        writeln!(out, "    {}:", step.name).unwrap();
+        writeln!(out, "      target: {}", step.target).unwrap();
+        if let Some(requirement) = &step.target_relationship {
+            writeln!(out, "      target_relationship: {}", requirement).unwrap();
+        }
+        writeln!(out, "      activities:").unwrap();
+        for activity in step.activities.iter() {
+            match activity {
+                Activity::SetState(state) => writeln!(out, "        - set_state: {}", state),
+                Activity::CallOperation(operation) => {
+                    writeln!(out, "        - call_operation: {}", operation)
+                }
+            }
+            .unwrap();
+        }
+        if !step.on_success.is_empty() {
+            let next = step
+                .on_success
+                .iter()
+                .map(|i| workflow.steps[*i].name.as_str())
+                .collect::<Vec<_>>();
+            writeln!(out, "      on_success: [ {} ]", next.join(", ")).unwrap();
+        }
+    }
+    out
+}
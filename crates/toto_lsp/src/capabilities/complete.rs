@@ -35,7 +35,7 @@ pub(crate) fn complete_at(
         .filter_map(|e| e.weight().as_file().map(|pos| (pos.0, e.source())))
         .filter_map(
             |(pos, source)| match ast.node_weight(source).unwrap().as_yaml() {
-                Some(_) => Some((pos, get_yaml_len(source, &ast), source)),
+                Some(_) => Some((pos, get_yaml_len(source, ast), source)),
                 _ => None,
             },
         )
@@ -62,7 +62,7 @@ pub(crate) fn complete_at(
 
     lookuper
         .1
-        .lookup_suggests(&ast, lookuper.0)
+        .lookup_suggests(ast, lookuper.0)
         .into_iter()
         .map(|(name, detail, rel)| {
             let mut item = lsp_types::CompletionItem::new_simple(name, detail.unwrap_or_default());
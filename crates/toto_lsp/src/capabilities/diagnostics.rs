@@ -1,8 +1,6 @@
 use std::{collections::HashMap, error::Error};
 
-use petgraph::visit::EdgeRef;
 use toto_parser::AsParseError;
-use toto_yaml::{AsFileEntity, AsFileRelation};
 
 use crate::models;
 
@@ -17,40 +15,36 @@ pub(crate) fn get_diagnostics(
 
     let mut diagnostics = HashMap::<url::Url, Vec<lsp_types::Diagnostic>>::new();
 
-    toto_parser::get_errors(&ast).for_each(|(what, loc)| {
-        let len = loc.map_or(1, |l| toto_parser::get_yaml_len(l, &ast));
-        let (pos, file) = ast
-            .edges(what)
-            .find_map(|e| e.weight().as_file().map(|pos| (pos.0, e.target())))
-            .unwrap();
-
-        let doc = ast[file].as_file().unwrap();
-
-        let (lineno_start, charno_start) = toto_yaml::get_lc(doc.content.as_ref().unwrap(), pos);
-        let (lineno_end, charno_end) = toto_yaml::get_lc(doc.content.as_ref().unwrap(), pos + len);
-
-        if !diagnostics.contains_key(&doc.url) {
-            diagnostics.insert(doc.url.clone(), vec![]);
-        }
+    toto_parser::get_errors(ast).for_each(|(what, loc)| {
+        let err = ast.node_weight(what).unwrap().as_parse().unwrap();
+        let severity = match err.severity() {
+            toto_parser::Severity::Error => lsp_types::DiagnosticSeverity::ERROR,
+            toto_parser::Severity::Warning => lsp_types::DiagnosticSeverity::WARNING,
+            toto_parser::Severity::Info => lsp_types::DiagnosticSeverity::INFORMATION,
+            toto_parser::Severity::Hint => lsp_types::DiagnosticSeverity::HINT,
+        };
+        let span = toto_parser::get_error_span(what, loc, ast);
 
         diagnostics
-            .get_mut(&doc.url)
-            .unwrap()
+            .entry(span.url)
+            .or_default()
             .push(lsp_types::Diagnostic::new(
                 lsp_types::Range {
                     start: lsp_types::Position {
-                        line: lineno_start,
-                        character: charno_start,
+                        line: span.start.0,
+                        character: span.start.1,
                     },
                     end: lsp_types::Position {
-                        line: lineno_end,
-                        character: charno_end,
+                        line: span.end.0,
+                        character: span.end.1,
                     },
                 },
-                None,
-                None,
-                None,
-                format!("{}", ast.node_weight(what).unwrap().as_parse().unwrap()),
+                Some(severity),
+                Some(lsp_types::NumberOrString::String(
+                    err.code().code.to_string(),
+                )),
+                Some("toto".to_string()),
+                format!("{}", err),
                 None,
                 None,
             ));
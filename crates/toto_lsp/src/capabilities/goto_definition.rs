@@ -1,3 +1,9 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+
 use lsp_types::Location;
 use petgraph::{
     visit::EdgeRef,
@@ -38,7 +44,7 @@ pub fn goto_definition(
         .filter_map(|e| e.weight().as_file().map(|pos| (pos.0, e.source())))
         .filter_map(
             |(pos, source)| match ast.node_weight(source).unwrap().as_yaml() {
-                Some(_) => Some((pos, get_yaml_len(source, &ast), source)),
+                Some(_) => Some((pos, get_yaml_len(source, ast), source)),
                 _ => None,
             },
         )
@@ -93,18 +99,80 @@ pub fn goto_definition(
                 .unwrap()
         };
 
-    if target_file.url.scheme() == "builtin" {
+    let Some(content) = target_file.content.as_ref() else {
         eprintln!("can't go to builtin spec");
         return None;
-    }
+    };
+    let target_url = if target_file.url.scheme() == "builtin" {
+        materialize_builtin(&target_file.url, content)?
+    } else {
+        target_file.url.clone()
+    };
 
-    let (target_l, target_c) = toto_yaml::get_lc(target_file.content.as_ref().unwrap(), target_pos);
+    let (target_l, target_c) = toto_yaml::get_lc(content, target_pos);
 
     Some(Location::new(
-        target_file.url.clone(),
+        target_url,
         lsp_types::Range::new(
             lsp_types::Position::new(target_l, target_c),
             lsp_types::Position::new(target_l, target_c + 1),
         ),
     ))
 }
+
+/// Bundled files have no location the editor could open, so they are written
+/// to a directory in the user's cache that only the user can access.
+fn materialize_builtin(url: &url::Url, content: &str) -> Option<url::Url> {
+    let root = toto_tosca::fetch::user_cache_dir()
+        .join("toto")
+        .join("builtin");
+    let path = root
+        .join(url.host_str().unwrap_or_default())
+        .join(url.path().trim_start_matches('/'));
+
+    if let Err(err) = write_builtin(&root, &path, content) {
+        eprintln!("can't write {}: {}", path.display(), err);
+        return None;
+    }
+    url::Url::from_file_path(path).ok()
+}
+
+fn write_builtin(root: &Path, path: &Path, content: &str) -> io::Result<()> {
+    let dir = path.parent().unwrap();
+    let mut builder = fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder.create(dir)?;
+
+    // Nothing below the root may point elsewhere.
+    for p in path.ancestors().take_while(|p| p.starts_with(root)) {
+        if fs::symlink_metadata(p).is_ok_and(|m| m.file_type().is_symlink()) {
+            return Err(io::Error::other(format!("{} is a symlink", p.display())));
+        }
+    }
+
+    if fs::read_to_string(path).ok().as_deref() == Some(content) {
+        return Ok(());
+    }
+
+    // `create_new` refuses an existing entry, symlink or not, and `rename`
+    // replaces the directory entry instead of following it.
+    let tmp = dir.join(format!(
+        ".{}.{}.tmp",
+        path.file_name().unwrap().to_string_lossy(),
+        std::process::id()
+    ));
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let written = options
+        .open(&tmp)
+        .and_then(|mut file| file.write_all(content.as_bytes()))
+        .and_then(|_| fs::rename(&tmp, path));
+    if written.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    written
+}
@@ -12,6 +12,12 @@ pub struct Server {
     parser: toto_tosca::ToscaParser,
 }
 
+impl Default for Server {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Server {
     pub fn new() -> Self {
         let (connection, io_threads) = lsp_server::Connection::stdio();
@@ -0,0 +1,106 @@
+//! Diagnostic codes of the generic YAML grammar checks.
+//!
+//! Codes of this crate occupy the `T00xx` range, grammars built on top of it
+//! are expected to use the ranges above.
+
+use crate::{DiagnosticCode, Severity};
+
+pub static YAML_SYNTAX: DiagnosticCode = DiagnosticCode {
+    code: "T0001",
+    name: "yaml-syntax",
+    severity: Severity::Error,
+    explanation: "\
+The file is not a well-formed YAML document, so nothing else in it can be
+checked. The reported location points to the place where the YAML parser
+gave up, the actual mistake is often on the line before it.
+
+Plain scalars which start like a number but continue with text, as well as
+timestamps, must be quoted.
+
+Example:
+
+    properties:
+      frequency: 2.5 GHz
+
+Fix:
+
+    properties:
+      frequency: \"2.5 GHz\"
+",
+};
+
+pub static UNKNOWN_FIELD: DiagnosticCode = DiagnosticCode {
+    code: "T0002",
+    name: "unknown-field",
+    severity: Severity::Error,
+    explanation: "\
+The key is not part of the grammar of the enclosing entity. This is usually a
+typo or a keyname which belongs to another version of the specification.
+
+Example:
+
+    node_types:
+      MyNode:
+        derivedfrom: tosca.nodes.Root
+
+Fix:
+
+    node_types:
+      MyNode:
+        derived_from: tosca.nodes.Root
+",
+};
+
+pub static MISSING_FIELD: DiagnosticCode = DiagnosticCode {
+    code: "T0003",
+    name: "missing-field",
+    severity: Severity::Error,
+    explanation: "\
+A keyname which is mandatory for the entity is absent.
+
+Example:
+
+    node_templates:
+      server:
+        properties:
+          name: web
+
+Fix:
+
+    node_templates:
+      server:
+        type: Compute
+        properties:
+          name: web
+",
+};
+
+pub static UNEXPECTED_TYPE: DiagnosticCode = DiagnosticCode {
+    code: "T0004",
+    name: "unexpected-type",
+    severity: Severity::Error,
+    explanation: "\
+The value has a YAML type other than the one required at this place, e.g. a
+list where a map is expected or a map where a string is expected. The message
+names the expected type.
+
+Example:
+
+    node_types:
+      MyNode:
+        derived_from: [ tosca.nodes.Root ]
+
+Fix:
+
+    node_types:
+      MyNode:
+        derived_from: tosca.nodes.Root
+",
+};
+
+pub static ALL: &[&DiagnosticCode] = &[
+    &YAML_SYNTAX,
+    &UNKNOWN_FIELD,
+    &MISSING_FIELD,
+    &UNEXPECTED_TYPE,
+];
@@ -1,7 +1,7 @@
 use ariadne::{Label, Report, ReportKind, Source};
 use petgraph::visit::EdgeRef;
 
-use crate::{ParseCompatibleEntity, ParseCompatibleRelation};
+use crate::{ParseCompatibleEntity, ParseCompatibleRelation, Severity};
 
 pub fn get_errors<E, R>(
     ast: &toto_ast::AST<E, R>,
@@ -42,7 +42,14 @@ pub fn report_error<E, R>(
     let err = ast.node_weight(what).unwrap().as_parse().unwrap();
     let file = ast.node_weight(file).unwrap().as_file().unwrap();
 
-    Report::build(ReportKind::Error, file.url.as_str(), pos)
+    let kind = match err.severity() {
+        Severity::Error => ReportKind::Error,
+        Severity::Warning => ReportKind::Warning,
+        Severity::Info | Severity::Hint => ReportKind::Advice,
+    };
+
+    Report::build(kind, file.url.as_str(), pos)
+        .with_code(err.code().code)
         .with_label(
             Label::new((file.url.as_str(), pos..pos + len)).with_message(format!("{}", err)),
         )
@@ -54,6 +61,44 @@ pub fn report_error<E, R>(
         .unwrap();
 }
 
+/// Location of a reported error within its file. Lines and columns are
+/// zero-based, `offset` and `len` are in bytes.
+#[derive(Debug, Clone)]
+pub struct ErrorSpan {
+    pub url: url::Url,
+    pub offset: usize,
+    pub len: usize,
+    pub start: (u32, u32),
+    pub end: (u32, u32),
+}
+
+pub fn get_error_span<E, R>(
+    what: toto_ast::GraphHandle,
+    loc: Option<toto_ast::GraphHandle>,
+    ast: &toto_ast::AST<E, R>,
+) -> ErrorSpan
+where
+    E: ParseCompatibleEntity,
+    R: ParseCompatibleRelation,
+{
+    let len = loc.map_or(1, |l| get_yaml_len(l, ast));
+    let (offset, file) = ast
+        .edges(what)
+        .find_map(|e| e.weight().as_file().map(|pos| (pos.0, e.target())))
+        .unwrap();
+
+    let doc = ast[file].as_file().unwrap();
+    let content = doc.content.as_deref().unwrap_or_default();
+
+    ErrorSpan {
+        url: doc.url.clone(),
+        offset,
+        len,
+        start: toto_yaml::get_lc(content, offset.min(content.len())),
+        end: toto_yaml::get_lc(content, (offset + len).min(content.len())),
+    }
+}
+
 pub fn get_yaml_len<E, R>(n: toto_ast::GraphHandle, ast: &toto_ast::AST<E, R>) -> usize
 where
     E: ParseCompatibleEntity,
@@ -3,21 +3,80 @@ use std::fmt::{Debug, Display};
 use petgraph::{data::DataMap, visit::EdgeRef, Direction::Outgoing};
 use toto_yaml::{AsFileEntity, AsFileRelation, FileRelation};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+    Hint,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Info => "info",
+            Self::Hint => "hint",
+        })
+    }
+}
+
+/// Stable identifier of a kind of diagnostic.
+///
+/// `code` and `name` never change once published, so they can be referred to
+/// from documentation, editors and suppression lists. `explanation` is a long
+/// description of the diagnostic with an example of how to fix it.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct DiagnosticCode {
+    pub code: &'static str,
+    pub name: &'static str,
+    pub severity: Severity,
+    pub explanation: &'static str,
+}
+
+impl DiagnosticCode {
+    pub fn with(&'static self, message: impl Into<String>) -> ParseError {
+        ParseError::Diagnostic(self, message.into())
+    }
+}
+
+impl Display for DiagnosticCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{} {}", self.code, self.name))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum ParseError {
     UnknownField(String),
     MissingField(&'static str),
     UnexpectedType(&'static str),
-    Custom(String),
+    Diagnostic(&'static DiagnosticCode, String),
+}
+
+impl ParseError {
+    pub fn code(&self) -> &'static DiagnosticCode {
+        match self {
+            Self::UnknownField(_) => &crate::codes::UNKNOWN_FIELD,
+            Self::MissingField(_) => &crate::codes::MISSING_FIELD,
+            Self::UnexpectedType(_) => &crate::codes::UNEXPECTED_TYPE,
+            Self::Diagnostic(code, _) => code,
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.code().severity
+    }
 }
 
 impl Display for ParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match &self {
-            &Self::Custom(err) => f.write_str(&err),
-            &Self::MissingField(f_name) => f.write_fmt(format_args!("missing field: {}", f_name)),
-            &Self::UnknownField(_) => f.write_str("unsupported field"),
-            &Self::UnexpectedType(t_name) => f.write_fmt(format_args!("expected {}", t_name)),
+        match self {
+            Self::Diagnostic(_, err) => f.write_str(err),
+            Self::MissingField(f_name) => f.write_fmt(format_args!("missing field: {}", f_name)),
+            Self::UnknownField(_) => f.write_str("unsupported field"),
+            Self::UnexpectedType(t_name) => f.write_fmt(format_args!("expected {}", t_name)),
         }
     }
 }
@@ -0,0 +1,1452 @@
+//! Diagnostic codes reported by the TOSCA grammar and semantic passes.
+//!
+//! - `T01xx` names and types,
+//! - `T02xx` documents, imports and grammar,
+//! - `T03xx` refinements,
+//! - `T04xx` requirements,
+//! - `T05xx` functions,
+//! - `T06xx` validation,
+//! - `T07xx` node templates,
+//! - `T08xx` workflows.
+
+use toto_parser::{DiagnosticCode, Severity};
+
+pub static UNKNOWN_TYPE: DiagnosticCode = DiagnosticCode {
+    code: "T0101",
+    name: "unknown-type",
+    severity: Severity::Error,
+    explanation: "\
+The type name does not refer to a type of the expected kind. Types are looked
+up among the types defined in the file, the imported ones and the builtin
+types of the TOSCA version. A node type can't be used where a data type is
+expected even if the names match.
+
+Example:
+
+    node_templates:
+      server:
+        type: Compute
+
+Fix: define or import the type, or correct its name.
+
+    imports:
+      - url: types.yaml
+    node_templates:
+      server:
+        type: Compute
+",
+};
+
+pub static UNKNOWN_DEFINITION: DiagnosticCode = DiagnosticCode {
+    code: "T0102",
+    name: "unknown-definition",
+    severity: Severity::Error,
+    explanation: "\
+The name does not refer to a definition of the expected kind: a node template
+of the service template, a repository of the file or a function.
+
+Example:
+
+    node_templates:
+      app:
+        type: App
+        requirements:
+          - host: sever
+
+Fix:
+
+    node_templates:
+      app:
+        type: App
+        requirements:
+          - host: server
+",
+};
+
+pub static UNKNOWN_ASSIGNMENT: DiagnosticCode = DiagnosticCode {
+    code: "T0103",
+    name: "unknown-assignment",
+    severity: Severity::Error,
+    explanation: "\
+A template or a type assigns a property, attribute, capability, requirement,
+interface or artifact which is not defined by its type.
+
+Example:
+
+    node_types:
+      App:
+        properties:
+          port:
+            type: integer
+    node_templates:
+      app:
+        type: App
+        properties:
+          prot: 80
+
+Fix:
+
+    node_templates:
+      app:
+        type: App
+        properties:
+          port: 80
+",
+};
+
+pub static CIRCULAR_DERIVATION: DiagnosticCode = DiagnosticCode {
+    code: "T0104",
+    name: "circular-derivation",
+    severity: Severity::Error,
+    explanation: "\
+A type is derived from itself, directly or through other types. Derivation
+must form a tree rooted in a type without `derived_from`.
+
+Example:
+
+    node_types:
+      A:
+        derived_from: B
+      B:
+        derived_from: A
+
+Fix:
+
+    node_types:
+      A: {}
+      B:
+        derived_from: A
+",
+};
+
+pub static TYPE_COLLISION: DiagnosticCode = DiagnosticCode {
+    code: "T0105",
+    name: "type-collision",
+    severity: Severity::Error,
+    explanation: "\
+Two imports bring types of the same kind and name into a file, so the name
+can't be resolved unambiguously. The type from the colliding import is not
+available in the file.
+
+Example:
+
+    imports:
+      - url: a.yaml
+      - url: b.yaml
+
+Fix: import one of the files under a namespace.
+
+    imports:
+      - url: a.yaml
+      - url: b.yaml
+        namespace: b
+",
+};
+
+pub static DEPRECATED_TYPE: DiagnosticCode = DiagnosticCode {
+    code: "T0106",
+    name: "deprecated-type",
+    severity: Severity::Warning,
+    explanation: "\
+The type is marked with `status: deprecated` and is going to be removed in a
+future version of the types it belongs to.
+
+Example:
+
+    node_templates:
+      db:
+        type: LegacyDatabase
+
+Fix: switch to the type recommended in the description of the deprecated one.
+
+    node_templates:
+      db:
+        type: Database
+",
+};
+
+pub static EXPERIMENTAL_TYPE: DiagnosticCode = DiagnosticCode {
+    code: "T0107",
+    name: "experimental-type",
+    severity: Severity::Warning,
+    explanation: "\
+The type is marked with `status: experimental`, its definition may change in
+incompatible ways.
+
+Example:
+
+    node_templates:
+      cache:
+        type: BetaCache
+
+Fix: use a supported type, or accept the risk and deny warnings only in
+builds which must not depend on experimental types.
+",
+};
+
+pub static UNSUPPORTED_TYPE: DiagnosticCode = DiagnosticCode {
+    code: "T0108",
+    name: "unsupported-type",
+    severity: Severity::Error,
+    explanation: "\
+The type is marked with `status: unsupported` and must not be used.
+
+Example:
+
+    node_types:
+      MyServer:
+        derived_from: OldServer
+
+Fix:
+
+    node_types:
+      MyServer:
+        derived_from: Server
+",
+};
+
+pub static UNKNOWN_TOSCA_VERSION: DiagnosticCode = DiagnosticCode {
+    code: "T0201",
+    name: "unknown-tosca-version",
+    severity: Severity::Error,
+    explanation: "\
+The value of `tosca_definitions_version` is not one of the supported versions:
+`tosca_simple_yaml_1_3` and `tosca_2_0`.
+
+Example:
+
+    tosca_definitions_version: tosca_2
+
+Fix:
+
+    tosca_definitions_version: tosca_2_0
+",
+};
+
+pub static TOSCA_VERSION_MISMATCH: DiagnosticCode = DiagnosticCode {
+    code: "T0202",
+    name: "tosca-version-mismatch",
+    severity: Severity::Error,
+    explanation: "\
+The imported file declares another `tosca_definitions_version` than the
+importing one. TOSCA 2.0 files may import TOSCA 1.3 files, but not the
+other way round.
+
+Example:
+
+    tosca_definitions_version: tosca_simple_yaml_1_3
+    imports:
+      - url: types_2_0.yaml
+
+Fix: import a file of the same version, or migrate one of the files.
+",
+};
+
+pub static CIRCULAR_IMPORT: DiagnosticCode = DiagnosticCode {
+    code: "T0203",
+    name: "circular-import",
+    severity: Severity::Error,
+    explanation: "\
+A file imports itself, directly or through other files.
+
+Example:
+
+    # a.yaml
+    imports:
+      - url: b.yaml
+    # b.yaml
+    imports:
+      - url: a.yaml
+
+Fix: move the definitions both files need into a third file imported by
+both of them.
+",
+};
+
+pub static UNKNOWN_STATUS: DiagnosticCode = DiagnosticCode {
+    code: "T0204",
+    name: "unknown-status",
+    severity: Severity::Error,
+    explanation: "\
+The status is not one of `supported`, `unsupported`, `experimental` and
+`deprecated`.
+
+Example:
+
+    properties:
+      legacy:
+        type: string
+        status: obsolete
+
+Fix:
+
+    properties:
+      legacy:
+        type: string
+        status: deprecated
+",
+};
+
+pub static CONFLICTING_FIELDS: DiagnosticCode = DiagnosticCode {
+    code: "T0205",
+    name: "conflicting-fields",
+    severity: Severity::Error,
+    explanation: "\
+The entity uses keynames which are mutually exclusive, e.g. `url` and
+`profile` of an import or `steps` and `implementation` of a workflow.
+
+Example:
+
+    imports:
+      - url: types.yaml
+        profile: org.example.types
+
+Fix: keep only one of them.
+
+    imports:
+      - profile: org.example.types
+",
+};
+
+pub static DEPENDENT_FIELD: DiagnosticCode = DiagnosticCode {
+    code: "T0206",
+    name: "dependent-field",
+    severity: Severity::Error,
+    explanation: "\
+The keyname only makes sense together with another one which is absent, e.g.
+`repository` of an import requires `url`.
+
+Example:
+
+    imports:
+      - profile: org.example.types
+        repository: example
+
+Fix:
+
+    imports:
+      - url: types.yaml
+        repository: example
+",
+};
+
+pub static SINGLE_KEY_MAP: DiagnosticCode = DiagnosticCode {
+    code: "T0207",
+    name: "single-key-map",
+    severity: Severity::Error,
+    explanation: "\
+Entries of ordered lists, such as requirements or workflow activities, are
+maps with exactly one key naming the entry.
+
+Example:
+
+    requirements:
+      - host: server
+        database: db
+
+Fix:
+
+    requirements:
+      - host: server
+      - database: db
+",
+};
+
+pub static UNKNOWN_ACTIVITY: DiagnosticCode = DiagnosticCode {
+    code: "T0208",
+    name: "unknown-activity",
+    severity: Severity::Error,
+    explanation: "\
+The workflow activity is not one of `delegate`, `set_state`,
+`call_operation` and `inline`.
+
+Example:
+
+    activities:
+      - call: Standard.create
+
+Fix:
+
+    activities:
+      - call_operation: Standard.create
+",
+};
+
+pub static UNRESOLVED_IMPORT: DiagnosticCode = DiagnosticCode {
+    code: "T0209",
+    name: "unresolved-import",
+    severity: Severity::Error,
+    explanation: "\
+The imported file can't be read: the path or the URL does not exist, or the
+file is not accessible. Relative paths are resolved against the url of the
+import's repository, or against the importing file if there is none.
+
+Example:
+
+    imports:
+      - url: ../types/compute.yml
+
+Fix:
+
+    imports:
+      - url: ../types/compute.yaml
+",
+};
+
+pub static INVALID_REPOSITORY_URL: DiagnosticCode = DiagnosticCode {
+    code: "T0210",
+    name: "invalid-repository-url",
+    severity: Severity::Error,
+    explanation: "\
+The url of a repository definition is not a valid URL, or can't be used as a
+base for the imported files. Relative urls are resolved against the file
+defining the repository.
+
+Example:
+
+    repositories:
+      profiles:
+        url: https://example com/profiles/
+
+Fix:
+
+    repositories:
+      profiles:
+        url: https://example.com/profiles/
+",
+};
+
+pub static UNKNOWN_PROFILE: DiagnosticCode = DiagnosticCode {
+    code: "T0211",
+    name: "unknown-profile",
+    severity: Severity::Error,
+    explanation: "\
+The imported profile is not installed. Profiles are looked up by the name
+declared with the `profile` keyname of their root file in the profile
+directories, install one with `toto profile add <path>` and list the
+installed ones with `toto profile list`.
+
+Example:
+
+    imports:
+      - profile: org.oasis-open.simple:2.0
+
+Fix:
+
+    $ toto profile add path/to/simple-profile/
+",
+};
+
+pub static MISSING_ARTIFACT_FILE: DiagnosticCode = DiagnosticCode {
+    code: "T0212",
+    name: "missing-artifact-file",
+    severity: Severity::Error,
+    explanation: "\
+The file of an artifact doesn't exist. Relative paths are resolved against
+the TOSCA file defining the artifact, files of the artifacts defined in an
+archive (CSAR) must be in the archive. Files from repositories and remote
+files are not checked.
+
+Example:
+
+    artifacts:
+      install:
+        type: Implementation.Bash
+        file: scripts/install.sh
+
+Fix: add scripts/install.sh next to the TOSCA file, or correct the path.
+",
+};
+
+pub static CHECKSUM_MISMATCH: DiagnosticCode = DiagnosticCode {
+    code: "T0213",
+    name: "checksum-mismatch",
+    severity: Severity::Error,
+    explanation: "\
+The checksum of an artifact file differs from the declared one, the file
+has likely changed since the checksum was computed. SHA-256 is assumed
+unless `checksum_algorithm` says otherwise.
+
+Example:
+
+    file: scripts/install.sh
+    checksum: 3f4e...
+    checksum_algorithm: SHA-256
+
+Fix: update the checksum, e.g. with `sha256sum scripts/install.sh`.
+",
+};
+
+pub static UNKNOWN_CHECKSUM_ALGORITHM: DiagnosticCode = DiagnosticCode {
+    code: "T0214",
+    name: "unknown-checksum-algorithm",
+    severity: Severity::Error,
+    explanation: "\
+The checksum of an artifact is computed with an algorithm the checksum
+can't be verified with. Supported algorithms are SHA-256, SHA-512 and MD5.
+
+Example:
+
+    checksum_algorithm: CRC32
+
+Fix:
+
+    checksum_algorithm: SHA-256
+",
+};
+
+pub static ARTIFACT_FILE_TYPE_MISMATCH: DiagnosticCode = DiagnosticCode {
+    code: "T0215",
+    name: "artifact-file-type-mismatch",
+    severity: Severity::Warning,
+    explanation: "\
+The file of an artifact doesn't fit its artifact type: it has an extension
+not listed in the `file_ext` of the type, or, for types declaring only a
+`mime_type`, an extension of another well-known mime type.
+
+Example:
+
+    artifact_types:
+      Implementation.Bash:
+        file_ext: [ sh ]
+    ...
+        file: scripts/install.py
+
+Fix: use an artifact type matching the file, e.g. Implementation.Python.
+",
+};
+
+pub static LOSSY_VERSION_MAPPING: DiagnosticCode = DiagnosticCode {
+    code: "T0216",
+    name: "lossy-version-mapping",
+    severity: Severity::Info,
+    explanation: "\
+A TOSCA 1.3 file imported by a TOSCA 2.0 file uses a keyname that has no
+TOSCA 2.0 counterpart, so it is ignored. Constraints, valid source and
+target types and the other keynames with a counterpart are mapped to it.
+
+Example:
+
+    capabilities:
+      host:
+        type: tosca.capabilities.Compute
+        occurrences: [ 1, 1 ]
+
+Fix: nothing to do unless the keyname matters for the importing file,
+in which case the imported file has to be migrated to TOSCA 2.0.
+",
+};
+
+pub static INCOMPATIBLE_REFINEMENT: DiagnosticCode = DiagnosticCode {
+    code: "T0301",
+    name: "incompatible-refinement",
+    severity: Severity::Error,
+    explanation: "\
+A definition in a derived type refines a definition of the same name in a
+parent type, but uses a type which is not the refined type or derived from
+it. Refinements may only narrow types.
+
+Example:
+
+    node_types:
+      Base:
+        properties:
+          port:
+            type: integer
+      Derived:
+        derived_from: Base
+        properties:
+          port:
+            type: string
+
+Fix:
+
+    node_types:
+      Derived:
+        derived_from: Base
+        properties:
+          port:
+            type: integer
+",
+};
+
+pub static OPTIONAL_REFINEMENT: DiagnosticCode = DiagnosticCode {
+    code: "T0302",
+    name: "optional-refinement",
+    severity: Severity::Error,
+    explanation: "\
+A refinement makes a required definition optional. Every template valid for
+the derived type must also be valid for the parent type, so a definition
+can only become required, never optional.
+
+Example:
+
+    node_types:
+      Derived:
+        derived_from: Base
+        properties:
+          name:
+            type: string
+            required: false
+
+Fix: drop `required: false` from the refinement.
+",
+};
+
+pub static STATUS_REFINEMENT: DiagnosticCode = DiagnosticCode {
+    code: "T0303",
+    name: "status-refinement",
+    severity: Severity::Error,
+    explanation: "\
+A refinement changes the status of the refined definition.
+
+Example:
+
+    node_types:
+      Base:
+        properties:
+          legacy:
+            type: string
+            status: deprecated
+      Derived:
+        derived_from: Base
+        properties:
+          legacy:
+            type: string
+            status: supported
+
+Fix: keep the status of the refined definition, or omit it.
+",
+};
+
+pub static TARGET_TYPE_MISMATCH: DiagnosticCode = DiagnosticCode {
+    code: "T0401",
+    name: "target-type-mismatch",
+    severity: Severity::Error,
+    explanation: "\
+The node template a requirement assignment points to is not of a type the
+requirement definition accepts in its `node` keyname.
+
+Example:
+
+    node_templates:
+      app:
+        type: App
+        requirements:
+          - database: web_server
+
+Fix:
+
+    node_templates:
+      app:
+        type: App
+        requirements:
+          - database: db
+",
+};
+
+pub static MISSING_TARGET_CAPABILITY: DiagnosticCode = DiagnosticCode {
+    code: "T0402",
+    name: "missing-target-capability",
+    severity: Severity::Error,
+    explanation: "\
+The target node template has no capability of the type required by the
+requirement, or no capability of the requested name.
+
+Example:
+
+    requirements:
+      - host:
+          node: server
+          capability: hosting
+
+Fix: target a capability the node template has.
+
+    requirements:
+      - host:
+          node: server
+          capability: host
+",
+};
+
+pub static UNFULFILLED_REQUIREMENT: DiagnosticCode = DiagnosticCode {
+    code: "T0403",
+    name: "unfulfilled-requirement",
+    severity: Severity::Error,
+    explanation: "\
+A requirement without an explicit target node can't be fulfilled because no
+node template of the service template matches its node and capability types.
+
+Example:
+
+    node_templates:
+      app:
+        type: App
+
+Fix: add a matching node template, or point the requirement to one.
+
+    node_templates:
+      app:
+        type: App
+      server:
+        type: Compute
+",
+};
+
+pub static AMBIGUOUS_TARGET: DiagnosticCode = DiagnosticCode {
+    code: "T0404",
+    name: "ambiguous-target",
+    severity: Severity::Warning,
+    explanation: "\
+A requirement without an explicit target node matches several node templates,
+so the orchestrator picks one of them.
+
+Example:
+
+    node_templates:
+      app:
+        type: App
+      server_a:
+        type: Compute
+      server_b:
+        type: Compute
+
+Fix:
+
+    node_templates:
+      app:
+        type: App
+        requirements:
+          - host: server_a
+",
+};
+
+pub static AMBIGUOUS_CAPABILITY: DiagnosticCode = DiagnosticCode {
+    code: "T0405",
+    name: "ambiguous-capability",
+    severity: Severity::Warning,
+    explanation: "\
+The target node template has several capabilities matching the requirement,
+so the orchestrator picks one of them.
+
+Example:
+
+    requirements:
+      - endpoint: api
+
+Fix: name the capability explicitly.
+
+    requirements:
+      - endpoint:
+          node: api
+          capability: public_endpoint
+",
+};
+
+pub static UNKNOWN_FILTER_REFERENCE: DiagnosticCode = DiagnosticCode {
+    code: "T0406",
+    name: "unknown-filter-reference",
+    severity: Severity::Error,
+    explanation: "\
+A node filter or a substitution filter refers to a property or a capability
+the filtered node type does not define. The filtered node type is the node
+type of the requirement, the type of the node template or the substituted
+node type.
+
+Example:
+
+    requirements:
+      - host:
+          capability: Compute
+          node_filter:
+            $greater_or_equal:
+              - $get_property: [ SELF, CAPABILITY, host, num_cpu ]
+              - 2
+
+Fix:
+
+              - $get_property: [ SELF, CAPABILITY, host, num_cpus ]
+",
+};
+
+pub static CIRCULAR_FUNCTION_CALL: DiagnosticCode = DiagnosticCode {
+    code: "T0501",
+    name: "circular-function-call",
+    severity: Severity::Error,
+    explanation: "\
+Evaluating the function leads back to the same value, e.g. two properties
+which get values of each other.
+
+Example:
+
+    properties:
+      a: { $get_property: [ SELF, b ] }
+      b: { $get_property: [ SELF, a ] }
+
+Fix: give one of the values a literal.
+
+    properties:
+      a: { $get_property: [ SELF, b ] }
+      b: 8080
+",
+};
+
+pub static ARGUMENT_COUNT: DiagnosticCode = DiagnosticCode {
+    code: "T0502",
+    name: "argument-count",
+    severity: Severity::Error,
+    explanation: "\
+A function or a validation clause is called with a wrong number of arguments.
+
+Example:
+
+    validation: { $greater_than: [ $value ] }
+
+Fix:
+
+    validation: { $greater_than: [ $value, 0 ] }
+",
+};
+
+pub static UNAVAILABLE_CONTEXT: DiagnosticCode = DiagnosticCode {
+    code: "T0503",
+    name: "unavailable-context",
+    severity: Severity::Error,
+    explanation: "\
+The function refers to an entity which does not exist where it is used:
+`$get_input` outside of a service template, `SELF` outside of a template, or
+`SOURCE` and `TARGET` outside of a relationship.
+
+Example:
+
+    node_templates:
+      app:
+        type: App
+        properties:
+          host: { $get_attribute: [ TARGET, address ] }
+
+Fix:
+
+    node_templates:
+      app:
+        type: App
+        properties:
+          host: { $get_attribute: [ SELF, RELATIONSHIP, host, TARGET, address ] }
+",
+};
+
+pub static UNKNOWN_REFERENCE: DiagnosticCode = DiagnosticCode {
+    code: "T0504",
+    name: "unknown-reference",
+    severity: Severity::Error,
+    explanation: "\
+A function refers to an input, node template, capability, requirement,
+property, attribute or artifact which does not exist.
+
+Example:
+
+    inputs:
+      port:
+        type: integer
+    node_templates:
+      app:
+        type: App
+        properties:
+          port: { $get_input: prot }
+
+Fix:
+
+    node_templates:
+      app:
+        type: App
+        properties:
+          port: { $get_input: port }
+",
+};
+
+pub static MISSING_REFERENCE_NAME: DiagnosticCode = DiagnosticCode {
+    code: "T0505",
+    name: "missing-reference-name",
+    severity: Severity::Error,
+    explanation: "\
+A path of a function ends where the name of a property, attribute,
+capability, requirement or artifact is expected.
+
+Example:
+
+    properties:
+      port: { $get_property: [ SELF, CAPABILITY ] }
+
+Fix:
+
+    properties:
+      port: { $get_property: [ SELF, CAPABILITY, endpoint, port ] }
+",
+};
+
+pub static NO_SUCH_ELEMENT: DiagnosticCode = DiagnosticCode {
+    code: "T0506",
+    name: "no-such-element",
+    severity: Severity::Error,
+    explanation: "\
+A key or an index used to access a part of a value does not exist in it.
+
+Example:
+
+    properties:
+      ports: [ 80, 443 ]
+      tls_port: { $get_property: [ SELF, ports, 2 ] }
+
+Fix:
+
+    properties:
+      tls_port: { $get_property: [ SELF, ports, 1 ] }
+",
+};
+
+pub static DIVISION_BY_ZERO: DiagnosticCode = DiagnosticCode {
+    code: "T0507",
+    name: "division-by-zero",
+    severity: Severity::Error,
+    explanation: "\
+The divisor of `$quotient` or `$remainder` evaluates to zero.
+
+Example:
+
+    properties:
+      share: { $quotient: [ 100, { $get_input: replicas } ] }
+
+Fix: make sure the divisor can't be zero, e.g. by validating the input.
+
+    inputs:
+      replicas:
+        type: integer
+        validation: { $greater_than: [ $value, 0 ] }
+",
+};
+
+pub static INTEGER_OVERFLOW: DiagnosticCode = DiagnosticCode {
+    code: "T0508",
+    name: "integer-overflow",
+    severity: Severity::Error,
+    explanation: "\
+The result of an integer function doesn't fit into a 64-bit integer.
+
+Example:
+
+    properties:
+      rest: { $remainder: [ -9223372036854775808, -1 ] }
+
+Fix: keep the operands within the integer range.
+",
+};
+
+pub static INVALID_VALIDATION_CLAUSE: DiagnosticCode = DiagnosticCode {
+    code: "T0601",
+    name: "invalid-validation-clause",
+    severity: Severity::Error,
+    explanation: "\
+A validation clause is a call of a boolean function, such as `$and`,
+`$equal` or `$greater_than`.
+
+Example:
+
+    validation: { $concat: [ $value, x ] }
+
+Fix:
+
+    validation: { $has_suffix: [ $value, x ] }
+",
+};
+
+pub static UNKNOWN_CONSTRAINT: DiagnosticCode = DiagnosticCode {
+    code: "T0602",
+    name: "unknown-constraint",
+    severity: Severity::Error,
+    explanation: "\
+The TOSCA 1.3 constraint operator does not exist.
+
+Example:
+
+    constraints:
+      - greater: 0
+
+Fix:
+
+    constraints:
+      - greater_than: 0
+",
+};
+
+pub static INVALID_REGEX: DiagnosticCode = DiagnosticCode {
+    code: "T0603",
+    name: "invalid-regex",
+    severity: Severity::Error,
+    explanation: "\
+The pattern of a `pattern` constraint or a `$matches` clause is not a valid
+regular expression.
+
+Example:
+
+    validation: { $matches: [ $value, \"[a-z\" ] }
+
+Fix:
+
+    validation: { $matches: [ $value, \"[a-z]+\" ] }
+",
+};
+
+pub static INAPPLICABLE_CONSTRAINT: DiagnosticCode = DiagnosticCode {
+    code: "T0604",
+    name: "inapplicable-constraint",
+    severity: Severity::Error,
+    explanation: "\
+The constraint can't be applied to values of the type it is attached to,
+e.g. `length` to an integer or `pattern` to a list.
+
+Example:
+
+    properties:
+      port:
+        type: integer
+        constraints:
+          - min_length: 2
+
+Fix:
+
+    properties:
+      port:
+        type: integer
+        constraints:
+          - greater_or_equal: 10
+",
+};
+
+pub static MISSING_REQUIRED_PROPERTY: DiagnosticCode = DiagnosticCode {
+    code: "T0605",
+    name: "missing-required-property",
+    severity: Severity::Error,
+    explanation: "\
+A template or a value of a complex data type does not assign a required
+property which has no default value.
+
+Example:
+
+    node_types:
+      App:
+        properties:
+          port:
+            type: integer
+    node_templates:
+      app:
+        type: App
+
+Fix:
+
+    node_templates:
+      app:
+        type: App
+        properties:
+          port: 8080
+",
+};
+
+pub static CONSTRAINT_VIOLATION: DiagnosticCode = DiagnosticCode {
+    code: "T0606",
+    name: "constraint-violation",
+    severity: Severity::Error,
+    explanation: "\
+The value does not satisfy a validation clause or a constraint of its
+definition or of its data type.
+
+Example:
+
+    properties:
+      port:
+        type: integer
+        validation: { $less_than: [ $value, 65536 ] }
+    ...
+      port: 70000
+
+Fix:
+
+      port: 7000
+",
+};
+
+pub static INVALID_LITERAL: DiagnosticCode = DiagnosticCode {
+    code: "T0607",
+    name: "invalid-literal",
+    severity: Severity::Error,
+    explanation: "\
+The value can't be parsed as a value of its type: a scalar-unit without a
+known unit, a malformed version, timestamp or range.
+
+Example:
+
+    properties:
+      memory:
+        type: scalar-unit.size
+    ...
+      memory: 4 GiBs
+
+Fix:
+
+      memory: 4 GiB
+",
+};
+
+pub static INVALID_CONDITION: DiagnosticCode = DiagnosticCode {
+    code: "T0608",
+    name: "invalid-condition",
+    severity: Severity::Error,
+    explanation: "\
+A condition, such as a node filter, is a call of a boolean function, or a
+list of them which must all hold.
+
+Example:
+
+    node_filter:
+      $get_property: [ SELF, port ]
+
+Fix:
+
+    node_filter:
+      $equal: [ $get_property: [ SELF, port ], 80 ]
+",
+};
+
+pub static CIRCULAR_COPY: DiagnosticCode = DiagnosticCode {
+    code: "T0701",
+    name: "circular-copy",
+    severity: Severity::Error,
+    explanation: "\
+A node template copies itself, directly or through the templates it copies.
+None of the templates in the cycle is copied.
+
+Example:
+
+    node_templates:
+      web:
+        copy: backup
+      backup:
+        copy: web
+
+Fix:
+
+      backup:
+        type: WebServer
+",
+};
+
+pub static INVALID_COUNT: DiagnosticCode = DiagnosticCode {
+    code: "T0702",
+    name: "invalid-count",
+    severity: Severity::Error,
+    explanation: "\
+The count of a node template, the number of nodes created from it, is a
+non-negative integer, or a function call evaluating to one.
+
+Example:
+
+    web:
+      type: WebServer
+      count: -1
+
+Fix:
+
+      count: 0
+",
+};
+
+pub static SELECT_ASSIGNMENT: DiagnosticCode = DiagnosticCode {
+    code: "T0703",
+    name: "select-assignment",
+    severity: Severity::Error,
+    explanation: "\
+A node template with the `select` directive stands for a node selected
+from the inventory, its properties, capabilities, requirements, interfaces
+and artifacts are those of the selected node. Use a `node_filter` to
+constrain the node instead.
+
+Example:
+
+    database:
+      type: Database
+      directives: [ select ]
+      properties:
+        engine: postgres
+
+Fix:
+
+      node_filter:
+        $equal: [ $get_property: [ SELF, engine ], postgres ]
+",
+};
+
+pub static MISSING_SUBSTITUTION: DiagnosticCode = DiagnosticCode {
+    code: "T0704",
+    name: "missing-substitution",
+    severity: Severity::Error,
+    explanation: "\
+A node template with the `substitute` directive is substituted by a service
+template, one of the loaded files must have `substitution_mappings` for the
+type of the template or one of its derived types.
+
+Example:
+
+    database:
+      type: Database
+      directives: [ substitute ]
+
+Fix, in another file:
+
+    service_template:
+      substitution_mappings:
+        node_type: Database
+",
+};
+
+pub static UNKNOWN_MAPPING: DiagnosticCode = DiagnosticCode {
+    code: "T0705",
+    name: "unknown-mapping",
+    severity: Severity::Error,
+    explanation: "\
+A substitution mapping maps a property, an attribute, a capability, a
+requirement or an interface operation the substituted node type doesn't
+define, or maps it to a capability or a requirement the node template of
+the service template doesn't have.
+
+Example:
+
+    substitution_mappings:
+      node_type: Database
+      capabilities:
+        endpoint: [ db, endpoint ]
+
+Fix:
+
+        endpoint: [ db, database_endpoint ]
+",
+};
+
+pub static UNMAPPED_DEFINITION: DiagnosticCode = DiagnosticCode {
+    code: "T0706",
+    name: "unmapped-definition",
+    severity: Severity::Error,
+    explanation: "\
+A substitution mapping leaves a required property or a mandatory
+requirement of the substituted node type unmapped. A required property
+without a default value is mapped to an input, an input of the same name
+maps it implicitly. A requirement is mandatory unless its `count_range`,
+or `occurrences` in TOSCA 1.3, starts at 0.
+
+Example:
+
+    substitution_mappings:
+      node_type: Database
+
+Fix:
+
+    inputs:
+      engine:
+        type: string
+    substitution_mappings:
+      node_type: Database
+      properties:
+        engine: [ engine ]
+",
+};
+
+pub static CIRCULAR_STEP_TRANSITION: DiagnosticCode = DiagnosticCode {
+    code: "T0801",
+    name: "circular-step-transition",
+    severity: Severity::Error,
+    explanation: "\
+The steps of a workflow and their `on_success` and `on_failure` transitions
+form a directed acyclic graph, a step can't lead back to itself.
+
+Example:
+
+    steps:
+      create:
+        target: web
+        activities:
+          - call_operation: Standard.create
+        on_success: [ start ]
+      start:
+        target: web
+        activities:
+          - call_operation: Standard.start
+        on_failure: [ create ]
+
+Fix:
+
+        on_failure: [ cleanup ]
+",
+};
+
+pub static UNREACHABLE_STEP: DiagnosticCode = DiagnosticCode {
+    code: "T0802",
+    name: "unreachable-step",
+    severity: Severity::Warning,
+    explanation: "\
+A workflow starts with the steps no other step transitions to, a step that
+can't be reached from them never runs. This happens when the only steps
+leading to it are part of a cycle.
+
+Example:
+
+    steps:
+      retry:
+        target: web
+        activities:
+          - call_operation: Standard.start
+        on_failure: [ retry ]
+
+Fix:
+
+        on_failure: [ cleanup ]
+",
+};
+
+pub static UNKNOWN_OPERATION: DiagnosticCode = DiagnosticCode {
+    code: "T0803",
+    name: "unknown-operation",
+    severity: Severity::Error,
+    explanation: "\
+A `call_operation` activity calls `<interface_name>.<operation_name>`, an
+operation of an interface of the type of the step's target node template.
+
+Example:
+
+    steps:
+      create:
+        target: web
+        activities:
+          - call_operation: Standard.install
+
+Fix:
+
+          - call_operation: Standard.create
+",
+};
+
+pub static UNKNOWN_NODE_STATE: DiagnosticCode = DiagnosticCode {
+    code: "T0804",
+    name: "unknown-node-state",
+    severity: Severity::Error,
+    explanation: "\
+A `set_state` activity moves the target node to a state of the node
+lifecycle: initial, creating, created, configuring, configured, starting,
+started, stopping, deleting, deleted or error.
+
+Example:
+
+    activities:
+      - set_state: running
+
+Fix:
+
+      - set_state: started
+",
+};
+
+pub static ALL: &[&DiagnosticCode] = &[
+    &UNKNOWN_TYPE,
+    &UNKNOWN_DEFINITION,
+    &UNKNOWN_ASSIGNMENT,
+    &CIRCULAR_DERIVATION,
+    &TYPE_COLLISION,
+    &DEPRECATED_TYPE,
+    &EXPERIMENTAL_TYPE,
+    &UNSUPPORTED_TYPE,
+    &UNKNOWN_TOSCA_VERSION,
+    &TOSCA_VERSION_MISMATCH,
+    &CIRCULAR_IMPORT,
+    &UNKNOWN_STATUS,
+    &CONFLICTING_FIELDS,
+    &DEPENDENT_FIELD,
+    &SINGLE_KEY_MAP,
+    &UNKNOWN_ACTIVITY,
+    &UNRESOLVED_IMPORT,
+    &INVALID_REPOSITORY_URL,
+    &UNKNOWN_PROFILE,
+    &MISSING_ARTIFACT_FILE,
+    &CHECKSUM_MISMATCH,
+    &UNKNOWN_CHECKSUM_ALGORITHM,
+    &ARTIFACT_FILE_TYPE_MISMATCH,
+    &LOSSY_VERSION_MAPPING,
+    &INCOMPATIBLE_REFINEMENT,
+    &OPTIONAL_REFINEMENT,
+    &STATUS_REFINEMENT,
+    &TARGET_TYPE_MISMATCH,
+    &MISSING_TARGET_CAPABILITY,
+    &UNFULFILLED_REQUIREMENT,
+    &AMBIGUOUS_TARGET,
+    &AMBIGUOUS_CAPABILITY,
+    &UNKNOWN_FILTER_REFERENCE,
+    &CIRCULAR_FUNCTION_CALL,
+    &ARGUMENT_COUNT,
+    &UNAVAILABLE_CONTEXT,
+    &UNKNOWN_REFERENCE,
+    &MISSING_REFERENCE_NAME,
+    &NO_SUCH_ELEMENT,
+    &DIVISION_BY_ZERO,
+    &INTEGER_OVERFLOW,
+    &INVALID_VALIDATION_CLAUSE,
+    &UNKNOWN_CONSTRAINT,
+    &INVALID_REGEX,
+    &INAPPLICABLE_CONSTRAINT,
+    &MISSING_REQUIRED_PROPERTY,
+    &CONSTRAINT_VIOLATION,
+    &INVALID_LITERAL,
+    &INVALID_CONDITION,
+    &CIRCULAR_COPY,
+    &INVALID_COUNT,
+    &SELECT_ASSIGNMENT,
+    &MISSING_SUBSTITUTION,
+    &UNKNOWN_MAPPING,
+    &UNMAPPED_DEFINITION,
+    &CIRCULAR_STEP_TRANSITION,
+    &UNREACHABLE_STEP,
+    &UNKNOWN_OPERATION,
+    &UNKNOWN_NODE_STATE,
+];
+
+/// Finds a diagnostic code of the parser or of the TOSCA passes by its code
+/// or by its name, e.g. `T0101` or `unknown-type`.
+pub fn find(code: &str) -> Option<&'static DiagnosticCode> {
+    toto_parser::codes::ALL
+        .iter()
+        .chain(ALL.iter())
+        .find(|c| c.code.eq_ignore_ascii_case(code) || c.name == code)
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn codes_are_unique() {
+        let all = toto_parser::codes::ALL
+            .iter()
+            .chain(ALL.iter())
+            .collect::<Vec<_>>();
+
+        let codes = all.iter().map(|c| c.code).collect::<HashSet<_>>();
+        let names = all.iter().map(|c| c.name).collect::<HashSet<_>>();
+        assert_eq!(codes.len(), all.len());
+        assert_eq!(names.len(), all.len());
+
+        for c in all {
+            assert!(c.code.len() == 5 && c.code.starts_with('T'), "{}", c);
+            assert!(!c.explanation.is_empty(), "{}", c);
+            assert_eq!(find(c.name), Some(*c));
+        }
+        assert_eq!(find("t0101"), Some(&UNKNOWN_TYPE));
+    }
+}
@@ -0,0 +1,293 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::anyhow;
+
+/// Location of the metadata file inside an archive.
+pub const META_PATH: &str = "TOSCA-Metadata/TOSCA.meta";
+
+/// Metadata of an archive, read from its `TOSCA.meta` or, if there is none,
+/// found by the root-YAML convention.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Metadata {
+    pub csar_version: Option<String>,
+    pub created_by: Option<String>,
+    pub entry_definitions: String,
+    pub other_definitions: Vec<String>,
+}
+
+/// A TOSCA Cloud Service Archive: a ZIP file with the TOSCA files and the
+/// artifacts they refer to.
+///
+/// Files of an archive are addressed with `csar:///<archive path>!/<entry>`
+/// urls, so imports and artifact paths relative to them stay inside the
+/// archive.
+#[derive(Debug)]
+pub struct Csar {
+    path: PathBuf,
+    entries: BTreeMap<String, Vec<u8>>,
+}
+
+impl Csar {
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        let file = fs::File::open(path)
+            .map_err(|err| anyhow!("failed to open {}: {}", path.display(), err))?;
+        let mut archive = zip::ZipArchive::new(file)
+            .map_err(|err| anyhow!("{} is not a valid archive: {}", path.display(), err))?;
+
+        let mut entries = BTreeMap::new();
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i)?;
+            if file.is_dir() {
+                continue;
+            }
+            // Entries pointing outside of the archive can't be referred to.
+            let Some(name) = file.enclosed_name() else {
+                continue;
+            };
+            let mut content = vec![];
+            file.read_to_end(&mut content)?;
+            entries.insert(entry_name(&name), content);
+        }
+
+        Ok(Self {
+            path: std::path::absolute(path)?,
+            entries,
+        })
+    }
+
+    /// Archives the files of `dir` into `output`, failing if the result
+    /// would not be a valid archive.
+    pub fn pack(dir: &Path, output: &Path) -> anyhow::Result<Self> {
+        let mut entries = BTreeMap::new();
+        collect_files(dir, dir, output, &mut entries)?;
+        let csar = Self {
+            path: std::path::absolute(output)?,
+            entries,
+        };
+        csar.metadata()?;
+
+        let mut writer = zip::ZipWriter::new(fs::File::create(output)?);
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+        for (name, content) in csar.entries.iter() {
+            writer.start_file(name.as_str(), options)?;
+            writer.write_all(content)?;
+        }
+        writer.finish()?;
+
+        Ok(csar)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    pub fn read(&self, entry: &str) -> Option<&[u8]> {
+        self.entries.get(entry).map(Vec::as_slice)
+    }
+
+    pub fn metadata(&self) -> anyhow::Result<Metadata> {
+        let metadata = match self.read(META_PATH) {
+            Some(content) => self.parse_meta(&String::from_utf8_lossy(content))?,
+            None => self.root_yaml()?,
+        };
+
+        for entry in
+            std::iter::once(&metadata.entry_definitions).chain(metadata.other_definitions.iter())
+        {
+            if self.read(entry).is_none() {
+                return Err(anyhow!(
+                    "{} refers to {}, which is not in the archive",
+                    META_PATH,
+                    entry
+                ));
+            }
+        }
+        Ok(metadata)
+    }
+
+    /// Urls of the entry definitions and the other definitions.
+    pub fn definitions(&self) -> anyhow::Result<Vec<url::Url>> {
+        let metadata = self.metadata()?;
+        Ok(std::iter::once(&metadata.entry_definitions)
+            .chain(metadata.other_definitions.iter())
+            .map(|entry| self.url(entry))
+            .collect())
+    }
+
+    pub fn url(&self, entry: &str) -> url::Url {
+        let archive = url::Url::from_file_path(&self.path).unwrap();
+        url::Url::parse(&format!("csar://{}!/", archive.path()))
+            .and_then(|root| root.join(&format!("./{}", entry)))
+            .unwrap()
+    }
+
+    /// Splits a `csar://` url into the archive path and the entry name.
+    pub fn split_url(url: &url::Url) -> Option<(PathBuf, String)> {
+        if url.scheme() != "csar" {
+            return None;
+        }
+        let path = url::Url::parse(&format!("file://{}", url.path()))
+            .ok()?
+            .to_file_path()
+            .ok()?;
+        let (archive, entry) = path.to_str()?.split_once("!/")?;
+        Some((PathBuf::from(archive), entry.to_string()))
+    }
+
+    /// Parses the first block of `TOSCA.meta`, a list of `Key: value` lines.
+    fn parse_meta(&self, content: &str) -> anyhow::Result<Metadata> {
+        let mut metadata = Metadata::default();
+        let mut entry_definitions = None;
+        for line in content.lines().take_while(|l| !l.trim().is_empty()) {
+            let Some((key, value)) = line.split_once(':') else {
+                return Err(anyhow!(
+                    "{}: expected `key: value`, got {}",
+                    META_PATH,
+                    line
+                ));
+            };
+            let value = value.trim().to_string();
+            match key.trim() {
+                "CSAR-Version" => metadata.csar_version = Some(value),
+                "Created-By" => metadata.created_by = Some(value),
+                "Entry-Definitions" => entry_definitions = Some(value),
+                "Other-Definitions" => metadata
+                    .other_definitions
+                    .extend(value.split_whitespace().map(String::from)),
+                _ => {}
+            }
+        }
+        metadata.entry_definitions = entry_definitions
+            .ok_or_else(|| anyhow!("{} doesn't declare Entry-Definitions", META_PATH))?;
+        Ok(metadata)
+    }
+
+    /// Without `TOSCA.meta`, the entry definitions is the only YAML file in
+    /// the root of the archive.
+    fn root_yaml(&self) -> anyhow::Result<Metadata> {
+        let roots = self
+            .entries()
+            .filter(|entry| !entry.contains('/'))
+            .filter(|entry| entry.ends_with(".yaml") || entry.ends_with(".yml"))
+            .collect::<Vec<_>>();
+        match roots.as_slice() {
+            [entry] => Ok(Metadata {
+                entry_definitions: entry.to_string(),
+                ..Default::default()
+            }),
+            [] => Err(anyhow!(
+                "archive has neither {} nor a YAML file in its root",
+                META_PATH
+            )),
+            _ => Err(anyhow!(
+                "archive without {} must have a single YAML file in its root, found {}",
+                META_PATH,
+                roots.join(", ")
+            )),
+        }
+    }
+}
+
+fn entry_name(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn collect_files(
+    root: &Path,
+    dir: &Path,
+    output: &Path,
+    entries: &mut BTreeMap<String, Vec<u8>>,
+) -> anyhow::Result<()> {
+    let mut paths = fs::read_dir(dir)?
+        .map(|e| e.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            collect_files(root, &path, output, entries)?;
+        } else if fs::canonicalize(&path).ok() != fs::canonicalize(output).ok() {
+            entries.insert(
+                entry_name(path.strip_prefix(root).unwrap()),
+                fs::read(&path)?,
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grammar::tests::errors;
+
+    fn fixtures() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/csar")
+    }
+
+    #[test]
+    fn pack_and_check() {
+        let out = tempfile::tempdir().unwrap();
+        let path = out.path().join("service.csar");
+        Csar::pack(&fixtures(), &path).unwrap();
+
+        let csar = Csar::open(&path).unwrap();
+        assert_eq!(
+            csar.metadata().unwrap(),
+            Metadata {
+                csar_version: Some("2.0".to_string()),
+                created_by: Some("totosca".to_string()),
+                entry_definitions: "Definitions/service.yaml".to_string(),
+                other_definitions: vec![],
+            }
+        );
+        let definitions = csar.definitions().unwrap();
+        assert_eq!(
+            Csar::split_url(&definitions[0]),
+            Some((path.clone(), "Definitions/service.yaml".to_string()))
+        );
+
+        let mut ast = toto_ast::AST::new();
+        crate::ToscaParser::new()
+            .parse_all(&definitions, &mut ast)
+            .unwrap();
+        assert_eq!(
+            errors(&ast),
+            vec![
+                (4, "missing.yaml not found in the archive".to_string()),
+                (22, "artifact file scripts/missing.sh not found".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn root_yaml() {
+        let out = tempfile::tempdir().unwrap();
+        let dir = out.path().join("service");
+        fs::create_dir(&dir).unwrap();
+        fs::write(
+            dir.join("service.yaml"),
+            "tosca_definitions_version: tosca_2_0\n",
+        )
+        .unwrap();
+        fs::write(dir.join("README.md"), "").unwrap();
+
+        let csar = Csar::pack(&dir, &out.path().join("service.csar")).unwrap();
+        assert_eq!(csar.metadata().unwrap().entry_definitions, "service.yaml");
+
+        fs::write(dir.join("other.yml"), "").unwrap();
+        assert!(Csar::pack(&dir, &out.path().join("other.csar")).is_err());
+    }
+}
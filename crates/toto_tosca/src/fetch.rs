@@ -0,0 +1,322 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::anyhow;
+use sha2::{Digest, Sha256};
+
+/// Loads contents of the parsed and imported files.
+pub trait Fetcher {
+    fn fetch(&self, url: &url::Url) -> anyhow::Result<String>;
+}
+
+/// Reads `file://` urls from the disk and downloads `http://` and `https://`
+/// urls through the cache.
+pub struct DefaultFetcher {
+    http: HttpFetcher,
+}
+
+impl DefaultFetcher {
+    pub fn new(http: HttpFetcher) -> Self {
+        Self { http }
+    }
+}
+
+impl Default for DefaultFetcher {
+    fn default() -> Self {
+        Self::new(HttpFetcher::new(HttpFetcher::default_cache_dir()))
+    }
+}
+
+impl Fetcher for DefaultFetcher {
+    fn fetch(&self, url: &url::Url) -> anyhow::Result<String> {
+        match url.scheme() {
+            "file" => {
+                let mut doc = toto_yaml::FileEntity::from_url(url.clone());
+                doc.fetch()?;
+                Ok(doc.content.unwrap_or_default())
+            }
+            "http" | "https" => self.http.fetch(url),
+            scheme => Err(anyhow!("unsupported url scheme: {}", scheme)),
+        }
+    }
+}
+
+/// Downloads files over http(s) and keeps them in a content-addressed cache.
+///
+/// The cache directory holds downloaded contents in `blobs/<sha256 of the
+/// content>` and an entry per url in `urls/<sha256 of the url>` referring to
+/// the blob along with the `ETag` and `Last-Modified` headers used to
+/// revalidate it. In offline mode only the cache is used.
+pub struct HttpFetcher {
+    cache_dir: PathBuf,
+    offline: bool,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct CacheEntry {
+    blob: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl HttpFetcher {
+    pub fn new(cache_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            cache_dir: cache_dir.into(),
+            offline: false,
+        }
+    }
+
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// `$TOTO_CACHE_DIR`, or `totosca` in the user cache directory.
+    pub fn default_cache_dir() -> PathBuf {
+        if let Some(dir) = env::var_os("TOTO_CACHE_DIR") {
+            return dir.into();
+        }
+        user_cache_dir().join("totosca")
+    }
+
+    fn hash(data: &[u8]) -> String {
+        format!("{:x}", Sha256::digest(data))
+    }
+
+    fn entry_path(&self, url: &url::Url) -> PathBuf {
+        self.cache_dir
+            .join("urls")
+            .join(Self::hash(url.as_str().as_bytes()))
+    }
+
+    fn blob_path(&self, blob: &str) -> PathBuf {
+        self.cache_dir.join("blobs").join(blob)
+    }
+
+    fn read_entry(&self, url: &url::Url) -> Option<CacheEntry> {
+        let content = fs::read_to_string(self.entry_path(url)).ok()?;
+        let mut entry = CacheEntry::default();
+        for line in content.lines() {
+            match line.split_once(' ') {
+                Some(("blob", v)) => entry.blob = v.to_string(),
+                Some(("etag", v)) => entry.etag = Some(v.to_string()),
+                Some(("last-modified", v)) => entry.last_modified = Some(v.to_string()),
+                _ => {}
+            }
+        }
+        Some(entry).filter(|e| !e.blob.is_empty())
+    }
+
+    fn read_blob(&self, entry: &CacheEntry) -> Option<String> {
+        fs::read_to_string(self.blob_path(&entry.blob)).ok()
+    }
+
+    fn store(&self, url: &url::Url, content: &str, mut entry: CacheEntry) -> anyhow::Result<()> {
+        entry.blob = Self::hash(content.as_bytes());
+        write_atomic(&self.blob_path(&entry.blob), content)?;
+
+        let mut index = format!("url {}\nblob {}\n", url, entry.blob);
+        if let Some(etag) = entry.etag {
+            index += &format!("etag {}\n", etag);
+        }
+        if let Some(last_modified) = entry.last_modified {
+            index += &format!("last-modified {}\n", last_modified);
+        }
+        write_atomic(&self.entry_path(url), &index)
+    }
+}
+
+impl Fetcher for HttpFetcher {
+    fn fetch(&self, url: &url::Url) -> anyhow::Result<String> {
+        let cached = self.read_entry(url);
+        if self.offline {
+            return cached
+                .and_then(|entry| self.read_blob(&entry))
+                .ok_or_else(|| anyhow!("{} is not available in the offline cache", url));
+        }
+
+        let mut request = ureq::get(url.as_str()).timeout(Duration::from_secs(30));
+        if let Some(entry) = cached.as_ref().filter(|e| self.read_blob(e).is_some()) {
+            if let Some(etag) = &entry.etag {
+                request = request.set("If-None-Match", etag);
+            }
+            if let Some(last_modified) = &entry.last_modified {
+                request = request.set("If-Modified-Since", last_modified);
+            }
+        }
+
+        match request.call() {
+            Ok(response) if response.status() == 304 => cached
+                .and_then(|entry| self.read_blob(&entry))
+                .ok_or_else(|| anyhow!("cached copy of {} is missing", url)),
+            Ok(response) => {
+                let entry = CacheEntry {
+                    blob: String::new(),
+                    etag: response.header("ETag").map(String::from),
+                    last_modified: response.header("Last-Modified").map(String::from),
+                };
+                let content = response.into_string()?;
+                self.store(url, &content, entry)?;
+                Ok(content)
+            }
+            Err(ureq::Error::Status(code, response)) => Err(anyhow!(
+                "failed to fetch {}: {} {}",
+                url,
+                code,
+                response.status_text()
+            )),
+            Err(err) => Err(anyhow!("failed to fetch {}: {}", url, err)),
+        }
+    }
+}
+
+/// `$XDG_CACHE_HOME`, or `~/.cache`.
+pub fn user_cache_dir() -> PathBuf {
+    env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))
+        .unwrap_or_else(env::temp_dir)
+}
+
+fn write_atomic(path: &Path, content: &str) -> anyhow::Result<()> {
+    let dir = path.parent().unwrap();
+    fs::create_dir_all(dir)?;
+    let tmp = dir.join(format!(
+        ".{}.tmp",
+        path.file_name().unwrap().to_string_lossy()
+    ));
+    fs::write(&tmp, content)?;
+    fs::rename(tmp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        sync::{Arc, Mutex},
+        thread,
+    };
+
+    use super::*;
+    use crate::grammar::tests::errors;
+
+    /// Received requests as (path, If-None-Match).
+    type RequestLog = Arc<Mutex<Vec<(String, Option<String>)>>>;
+
+    /// Serves `files` by path, honouring `If-None-Match`.
+    fn serve(files: &'static [(&'static str, &'static str)]) -> (url::Url, RequestLog) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = url::Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+        let log = Arc::new(Mutex::new(vec![]));
+
+        let requests = log.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut lines = vec![];
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim_end().is_empty() {
+                        break;
+                    }
+                    lines.push(line.trim_end().to_string());
+                }
+                let path = lines[0].split(' ').nth(1).unwrap().to_string();
+                let etag = lines
+                    .iter()
+                    .find_map(|l| l.strip_prefix("If-None-Match: "))
+                    .map(String::from);
+                requests.lock().unwrap().push((path.clone(), etag.clone()));
+
+                let response = match files.iter().find(|(p, _)| *p == path) {
+                    Some(_) if etag.as_deref() == Some("\"v1\"") => {
+                        "HTTP/1.1 304 Not Modified\r\nContent-Length: 0\r\n\r\n".to_string()
+                    }
+                    Some((_, content)) => format!(
+                        "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nContent-Length: {}\r\n\r\n{}",
+                        content.len(),
+                        content
+                    ),
+                    None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n".to_string(),
+                };
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        (base, log)
+    }
+
+    #[test]
+    fn http_cache() {
+        let (base, log) = serve(&[("/types.yaml", "tosca_definitions_version: tosca_2_0\n")]);
+        let cache = tempfile::tempdir().unwrap();
+        let url = base.join("types.yaml").unwrap();
+
+        let online = HttpFetcher::new(cache.path());
+        let content = online.fetch(&url).unwrap();
+        assert_eq!(content, "tosca_definitions_version: tosca_2_0\n");
+        assert_eq!(online.fetch(&url).unwrap(), content);
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                ("/types.yaml".to_string(), None),
+                ("/types.yaml".to_string(), Some("\"v1\"".to_string()))
+            ]
+        );
+
+        let offline = HttpFetcher::new(cache.path()).offline(true);
+        assert_eq!(offline.fetch(&url).unwrap(), content);
+        assert!(offline.fetch(&base.join("other.yaml").unwrap()).is_err());
+        assert_eq!(log.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn http_import() {
+        let (base, _) = serve(&[(
+            "/types.yaml",
+            "tosca_definitions_version: tosca_2_0\nnode_types:\n  Remote: {}\n",
+        )]);
+        let cache = tempfile::tempdir().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let main = dir.path().join("main.yaml");
+        fs::write(
+            &main,
+            format!(
+                "tosca_definitions_version: tosca_2_0\n\
+                 imports:\n  \
+                   - url: {}\n  \
+                   - url: {}\n\
+                 service_template:\n  \
+                   node_templates:\n    \
+                     node:\n      \
+                       type: Remote\n",
+                base.join("types.yaml").unwrap(),
+                base.join("missing.yaml").unwrap(),
+            ),
+        )
+        .unwrap();
+
+        let mut ast = toto_ast::AST::new();
+        crate::ToscaParser::with_fetcher(DefaultFetcher::new(HttpFetcher::new(cache.path())))
+            .parse(&url::Url::from_file_path(&main).unwrap(), &mut ast)
+            .unwrap();
+
+        assert_eq!(
+            errors(&ast),
+            vec![(
+                4,
+                format!(
+                    "failed to fetch {}: 404 Not Found",
+                    base.join("missing.yaml").unwrap()
+                )
+            )]
+        );
+    }
+}
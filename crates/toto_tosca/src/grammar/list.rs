@@ -2,7 +2,7 @@ use std::marker::PhantomData;
 
 use toto_parser::{add_with_loc, ParseError};
 
-use crate::{ToscaCompatibleEntity, ToscaCompatibleRelation};
+use crate::{codes, ToscaCompatibleEntity, ToscaCompatibleRelation};
 
 use super::field::Field;
 
@@ -60,13 +60,13 @@ where
                                 C::parse((k_str.0.clone(), i), root, v, ast);
                             }
                         } else {
-                            add_with_loc(ParseError::Custom("expected a key".to_string()), v, ast);
+                            add_with_loc(codes::SINGLE_KEY_MAP.with("expected a key"), v, ast);
                             return;
                         }
 
                         if items.next().is_some() {
                             add_with_loc(
-                                ParseError::Custom("expected only one key".to_string()),
+                                codes::SINGLE_KEY_MAP.with("expected only one key"),
                                 v,
                                 ast,
                             );
@@ -10,8 +10,37 @@ pub mod parser;
 pub mod v1_3;
 pub mod v2_0;
 
+/// Files bundled with the parser, loaded by their `builtin://` urls.
+const BUILTIN_FILES: &[(&str, &str)] = &[
+    (
+        "builtin://tosca_simple_yaml_1_3/normative.yaml",
+        include_str!("v1_3/normative.yaml"),
+    ),
+    (
+        "builtin://tosca_2_0/simple_profile.yaml",
+        include_str!("v2_0/simple_profile.yaml"),
+    ),
+];
+
+/// Profiles bundled with the parser as (name, url), used unless a profile of
+/// the same name is installed.
+pub const BUILTIN_PROFILES: &[(&str, &str)] = &[(
+    "org.oasis-open.simple:2.0",
+    "builtin://tosca_2_0/simple_profile.yaml",
+)];
+
+pub fn get_builtin_file(url: &url::Url) -> Option<&'static str> {
+    BUILTIN_FILES
+        .iter()
+        .find(|(u, _)| *u == url.as_str())
+        .map(|(_, content)| *content)
+}
+
 pub trait ToscaDefinitionsVersion: EntityParser<Self::Entity, Self::Relation> {
     const NAME: &'static str;
+    /// Url of the bundled file with the normative types, which every file of
+    /// this version imports implicitly.
+    const NORMATIVE_TYPES: Option<&'static str> = None;
 
     type Entity: ToscaCompatibleEntity;
     type Relation: ToscaCompatibleRelation;
@@ -50,6 +79,8 @@ pub trait ToscaDefinitionsVersion: EntityParser<Self::Entity, Self::Relation> {
     type RelationshipAssignment: toto_parser::EntityParser<Self::Entity, Self::Relation>;
     type RequirementDefinition: toto_parser::EntityParser<Self::Entity, Self::Relation>;
     type RequirementAssignment: toto_parser::EntityParser<Self::Entity, Self::Relation>;
+    type NodeFilterDefinition: toto_parser::EntityParser<Self::Entity, Self::Relation>;
+    type ConditionDefinition: toto_parser::EntityParser<Self::Entity, Self::Relation>;
 
     type WorkflowDefinition: toto_parser::EntityParser<Self::Entity, Self::Relation>;
     type WorkflowStepDefinition: toto_parser::EntityParser<Self::Entity, Self::Relation>;
@@ -69,7 +100,7 @@ pub trait ToscaDefinitionsVersion: EntityParser<Self::Entity, Self::Relation> {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     extern crate derive_more;
     use derive_more::{From, TryInto};
 
@@ -162,4 +193,70 @@ mod tests {
             }
         }
     }
+
+    /// Parses a file relative to the crate manifest directory.
+    pub fn parse(path: &str) -> toto_ast::AST<Entity, Relation> {
+        parse_all(&[path])
+    }
+
+    pub fn parse_all(paths: &[&str]) -> toto_ast::AST<Entity, Relation> {
+        let mut ast = toto_ast::AST::<Entity, Relation>::new();
+
+        let doc_path = "file://".to_string() + env!("CARGO_MANIFEST_DIR");
+        let doc_path = url::Url::parse(&doc_path).unwrap();
+        let doc_paths = paths
+            .iter()
+            .map(|path| doc_path.join(path).unwrap())
+            .collect::<Vec<_>>();
+
+        crate::ToscaParser::new()
+            .parse_all(&doc_paths, &mut ast)
+            .unwrap();
+        ast
+    }
+
+    /// Reported errors as (line, message) pairs, sorted.
+    pub fn errors(ast: &toto_ast::AST<Entity, Relation>) -> Vec<(u32, String)> {
+        use petgraph::visit::EdgeRef;
+        use toto_parser::AsParseError;
+        use toto_yaml::{AsFileEntity, AsFileRelation};
+
+        let mut errors = toto_parser::get_errors(ast)
+            .map(|(err, _)| {
+                let (pos, file) = ast
+                    .edges(err)
+                    .find_map(|e| e.weight().as_file().map(|pos| (pos.0, e.target())))
+                    .unwrap();
+                let doc = ast[file].as_file().unwrap().content.as_ref().unwrap();
+                (
+                    toto_yaml::get_lc(doc, pos).0 + 1,
+                    ast[err].as_parse().unwrap().to_string(),
+                )
+            })
+            .collect::<Vec<_>>();
+        errors.sort();
+        errors
+    }
+
+    #[test]
+    fn normative_types() {
+        let ast = parse_all(&[
+            "../tests/tosca_1_3_normative.yaml",
+            "../tests/tosca_2_0_simple_profile.yaml",
+        ]);
+
+        // The bundled files are checked along with the others, without errors.
+        assert_eq!(
+            errors(&ast),
+            vec![
+                (27, "value does not satisfy in_range".to_string()),
+                (
+                    31,
+                    "unknown node type: tosca.nodes.Storage.Volume".to_string()
+                ),
+                (31, "value does not satisfy $in_range".to_string()),
+                (35, "unknown node type: Storage.Volume".to_string()),
+            ]
+        );
+    }
 }
@@ -37,7 +37,7 @@ where
         "artifact_version" => Field::<VersionRelation, v2_0::value::StringValue>::parse,
         "checksum" => Field::<ChecksumRelation, v2_0::value::StringValue>::parse,
         "checksum_algorithm" => Field::<ChecksumAlgorithmRelation, v2_0::value::StringValue>::parse,
-        "properties" => Collection::<AssignmentRelation, v2_0::value::AnyValue>::parse,
+        "properties" => Collection::<AssignmentRelation, v2_0::value::PropertyAssignment>::parse,
     };
 
     const VALIDATION: &'static [toto_parser::ValidationFieldFn] = &[
@@ -7,7 +7,7 @@ use crate::{
         collection::Collection, field::Field, field_ref::TypeRef, list::ListRelator,
         ToscaDefinitionsVersion,
     },
-    AssignmentRelation, DefinitionRelation, DescriptionRelation, MetadataRelation,
+    AssignmentRelation, DefinitionRelation, DescriptionRelation, MetadataRelation, StatusRelation,
     ToscaCompatibleEntity, ToscaCompatibleRelation, VersionRelation,
 };
 
@@ -31,6 +31,7 @@ where
     const SELF: fn() -> E = || crate::Entity::from(crate::CapabilityEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "derived_from" => TypeRef::<crate::CapabilityEntity, crate::DerivedFromRelation>::parse,
+        "status" => Field::<StatusRelation, crate::grammar::v2_0::StatusValue>::parse,
         "version" => Field::<VersionRelation, value::StringValue>::parse,
         "metadata" => Collection::<MetadataRelation, value::AnyValue>::parse,
         "description" => Field::<DescriptionRelation, value::StringValue>::parse,
@@ -69,8 +70,8 @@ where
 {
     const SELF: fn() -> E = || crate::Entity::from(crate::CapabilityEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
-        "properties" => Collection::<AssignmentRelation, value::AnyValue>::parse,
-        "attributes" => Collection::<AssignmentRelation, value::AnyValue>::parse,
+        "properties" => Collection::<AssignmentRelation, value::PropertyAssignment>::parse,
+        "attributes" => Collection::<AssignmentRelation, value::AttributeAssignment>::parse,
         "occurrences" => |_, _, _| {},
     };
 }
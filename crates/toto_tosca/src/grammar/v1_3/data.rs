@@ -12,8 +12,8 @@ use crate::{
     },
     DefaultRelation, DefinitionRelation, DescriptionRelation, EntrySchemaRelation,
     ExternalSchemaRelation, KeySchemaRelation, MappingRelation, MetadataRelation, RequiredRelation,
-    ToscaCompatibleEntity, ToscaCompatibleRelation, ValidationRelation, ValueRelation,
-    VersionRelation,
+    StatusRelation, ToscaCompatibleEntity, ToscaCompatibleRelation, ValidationRelation,
+    ValueRelation, VersionRelation,
 };
 
 use super::v2_0::value;
@@ -42,6 +42,7 @@ where
     const SELF: fn() -> E = || crate::Entity::from(crate::DataEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "derived_from" => TypeRef::<crate::DataEntity, crate::DerivedFromRelation>::parse,
+        "status" => Field::<StatusRelation, crate::grammar::v2_0::StatusValue>::parse,
         "version" => Field::<VersionRelation, value::StringValue>::parse,
         "metadata" => Collection::<MetadataRelation, value::StringValue>::parse,
         "description" => Field::<DescriptionRelation, value::StringValue>::parse,
@@ -77,12 +78,12 @@ where
     R: ToscaCompatibleRelation,
     V: ToscaDefinitionsVersion<Entity = E, Relation = R>,
 {
-    const SELF: fn() -> E = || crate::Entity::from(crate::DataEntity).into();
+    const SELF: fn() -> E = || crate::Entity::from(crate::AttributeEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "type" => TypeRef::<crate::DataEntity, crate::HasTypeRelation>::parse,
         "description" => Field::<DescriptionRelation, value::StringValue>::parse,
-        "default" => Field::<DefaultRelation, value::AnyValue>::parse,
-        "status" => Field::<DefaultRelation, v2_0::StatusValue>::parse,
+        "default" => Field::<DefaultRelation, value::Expression>::parse,
+        "status" => Field::<StatusRelation, v2_0::StatusValue>::parse,
         "constraints" => List::<ValidationRelation, value::AnyValue>::parse,
         "key_schema" => Field::<KeySchemaRelation, V::SchemaDefinition>::parse,
         "entry_schema" => Field::<EntrySchemaRelation, V::SchemaDefinition>::parse,
@@ -99,15 +100,15 @@ where
     R: ToscaCompatibleRelation,
     V: ToscaDefinitionsVersion<Entity = E, Relation = R>,
 {
-    const SELF: fn() -> E = || crate::Entity::from(crate::DataEntity).into();
+    const SELF: fn() -> E = || crate::Entity::from(crate::PropertyEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "type" => TypeRef::<crate::DataEntity, crate::HasTypeRelation>::parse,
         "description" => Field::<DescriptionRelation, value::StringValue>::parse,
         "required" => Field::<RequiredRelation, value::BoolValue>::parse,
-        "default" => Field::<DefaultRelation, value::AnyValue>::parse,
-        "status" => Field::<DefaultRelation, v2_0::StatusValue>::parse,
+        "default" => Field::<DefaultRelation, value::Expression>::parse,
+        "status" => Field::<StatusRelation, v2_0::StatusValue>::parse,
         "constraints" => List::<ValidationRelation, value::AnyValue>::parse,
-        "value" => Field::<ValueRelation, value::AnyValue>::parse,
+        "value" => Field::<ValueRelation, value::Expression>::parse,
         "key_schema" => Field::<KeySchemaRelation, V::SchemaDefinition>::parse,
         "entry_schema" => Field::<EntrySchemaRelation, V::SchemaDefinition>::parse,
         "external-schema" => Field::<ExternalSchemaRelation, value::StringValue>::parse,
@@ -124,15 +125,15 @@ where
     R: ToscaCompatibleRelation,
     V: ToscaDefinitionsVersion<Entity = E, Relation = R>,
 {
-    const SELF: fn() -> E = || crate::Entity::from(crate::DataEntity).into();
+    const SELF: fn() -> E = || crate::Entity::from(crate::ParameterEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "type" => TypeRef::<crate::DataEntity, crate::HasTypeRelation>::parse,
         "description" => Field::<DescriptionRelation, value::StringValue>::parse,
         "required" => Field::<RequiredRelation, value::BoolValue>::parse,
-        "default" => Field::<DefaultRelation, value::AnyValue>::parse,
-        "status" => Field::<DefaultRelation, v2_0::StatusValue>::parse,
+        "default" => Field::<DefaultRelation, value::Expression>::parse,
+        "status" => Field::<StatusRelation, v2_0::StatusValue>::parse,
         "constraints" => List::<ValidationRelation, value::AnyValue>::parse,
-        "value" => Field::<ValueRelation, value::AnyValue>::parse,
+        "value" => Field::<ValueRelation, value::Expression>::parse,
         "mapping" => Field::<MappingRelation, value::AnyValue>::parse,
         "key_schema" => Field::<KeySchemaRelation, V::SchemaDefinition>::parse,
         "entry_schema" => Field::<EntrySchemaRelation, V::SchemaDefinition>::parse,
@@ -0,0 +1,104 @@
+use std::marker::PhantomData;
+
+use toto_parser::{RelationParser, ValueRelationParser};
+
+use crate::{
+    grammar::{
+        field::Field,
+        list::{KeyedList, KeyedListRelator},
+        ToscaDefinitionsVersion,
+    },
+    CapabilityFilterRelation, PropertyFilterRelation, ToscaCompatibleEntity,
+    ToscaCompatibleRelation,
+};
+
+use super::v2_0::value;
+
+/// Node filter or substitution filter: constraints on the properties of the
+/// filtered node and of its capabilities.
+#[derive(Debug)]
+pub struct NodeFilterDefinition<V: ToscaDefinitionsVersion>(PhantomData<V>);
+
+/// Constraints on the properties of a capability, given by its name or type.
+#[derive(Debug)]
+pub struct CapabilityFilterDefinition<V: ToscaDefinitionsVersion>(PhantomData<V>);
+
+/// Constraints of a property, a single constraint clause or a list of them.
+pub struct PropertyFilter;
+
+impl<E, R> ValueRelationParser<E, R, (String, usize)> for PropertyFilter
+where
+    E: ToscaCompatibleEntity,
+    R: ToscaCompatibleRelation,
+{
+    fn parse(
+        (name, _): (String, usize),
+        root: toto_ast::GraphHandle,
+        n: toto_ast::GraphHandle,
+        ast: &mut toto_ast::AST<E, R>,
+    ) {
+        let clauses = toto_yaml::as_list(n, ast)
+            .map(|items| items.map(|(_, item)| item).collect::<Vec<_>>())
+            .unwrap_or_else(|| vec![n]);
+        for clause in clauses {
+            <Field<PropertyFilterRelation, value::AnyValue> as ValueRelationParser<
+                E,
+                R,
+                String,
+            >>::parse(name.clone(), root, clause, ast);
+        }
+    }
+}
+
+impl<E, R, V> toto_parser::Schema<E, R> for NodeFilterDefinition<V>
+where
+    E: ToscaCompatibleEntity,
+    R: ToscaCompatibleRelation,
+    V: ToscaDefinitionsVersion<Entity = E, Relation = R>,
+{
+    const SELF: fn() -> E = || crate::Entity::from(crate::FilterEntity).into();
+    const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
+        "properties" => KeyedListRelator::<PropertyFilter>::parse,
+        "capabilities" => KeyedList::<CapabilityFilterRelation, CapabilityFilterDefinition<V>>::parse,
+    };
+}
+
+impl<E, R, V> toto_parser::Schema<E, R> for CapabilityFilterDefinition<V>
+where
+    E: ToscaCompatibleEntity,
+    R: ToscaCompatibleRelation,
+    V: ToscaDefinitionsVersion<Entity = E, Relation = R>,
+{
+    const SELF: fn() -> E = || crate::Entity::from(crate::FilterEntity).into();
+    const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
+        "properties" => KeyedListRelator::<PropertyFilter>::parse,
+    };
+}
+
+impl<E, R, V> toto_parser::EntityParser<E, R> for NodeFilterDefinition<V>
+where
+    E: ToscaCompatibleEntity,
+    R: ToscaCompatibleRelation,
+    V: ToscaDefinitionsVersion<Entity = E, Relation = R>,
+{
+    fn parse(
+        n: toto_ast::GraphHandle,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle> {
+        <Self as toto_parser::Schema<E, R>>::parse(n, ast)
+    }
+}
+
+impl<E, R, V> toto_parser::EntityParser<E, R> for CapabilityFilterDefinition<V>
+where
+    E: ToscaCompatibleEntity,
+    R: ToscaCompatibleRelation,
+    V: ToscaDefinitionsVersion<Entity = E, Relation = R>,
+{
+    fn parse(
+        n: toto_ast::GraphHandle,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle> {
+        <Self as toto_parser::Schema<E, R>>::parse(n, ast)
+    }
+}
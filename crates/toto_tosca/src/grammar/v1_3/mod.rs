@@ -1,6 +1,8 @@
 use std::marker::PhantomData;
 
-use crate::{ToscaCompatibleEntity, ToscaCompatibleRelation};
+use petgraph::visit::{Dfs, EdgeFiltered, EdgeRef};
+
+use crate::{codes, ToscaCompatibleEntity, ToscaCompatibleRelation};
 
 use super::{v2_0, ToscaDefinitionsVersion};
 
@@ -10,6 +12,7 @@ pub mod artifact;
 pub mod capability;
 pub mod data;
 pub mod file;
+pub mod filter;
 pub mod import;
 pub mod relationship;
 pub mod requirement;
@@ -19,6 +22,7 @@ pub use artifact::*;
 pub use capability::*;
 pub use data::*;
 pub use file::*;
+pub use filter::*;
 pub use import::*;
 pub use relationship::*;
 pub use requirement::*;
@@ -31,6 +35,8 @@ where
     R: ToscaCompatibleRelation,
 {
     const NAME: &'static str = "tosca_simple_yaml_1_3";
+    const NORMATIVE_TYPES: Option<&'static str> =
+        Some("builtin://tosca_simple_yaml_1_3/normative.yaml");
 
     type Entity = E;
     type Relation = R;
@@ -69,6 +75,10 @@ where
     type NotificationAssignment = v2_0::NotificationAssignment<Self>;
     type RequirementDefinition = self::RequirementDefinition<Self>;
     type RequirementAssignment = self::RequirementAssignment<Self>;
+    type NodeFilterDefinition = self::NodeFilterDefinition<Self>;
+    // Condition clauses of TOSCA 1.3 are constraints on attributes, they
+    // aren't checked.
+    type ConditionDefinition = v2_0::AnyValue;
     type RelationshipDefinition = v2_0::RelationshipDefinition<Self>;
     type RelationshipAssignment = v2_0::RelationshipAssignment<Self>;
     type RelationshipTypeDefinition = self::RelationshipTypeDefinition<Self>;
@@ -129,6 +139,80 @@ where
     }
 }
 
+impl<E, R> Tosca1_3<E, R>
+where
+    E: ToscaCompatibleEntity,
+    R: ToscaCompatibleRelation,
+{
+    /// Keynames of TOSCA 1.3 definitions having no TOSCA 2.0 counterpart,
+    /// which are dropped when a TOSCA 2.0 file imports a TOSCA 1.3 one.
+    const LOSSY_FIELDS: &'static [(crate::Entity, &'static str, &'static str)] = &[
+        (
+            crate::Entity::Capability(crate::CapabilityEntity),
+            "occurrences",
+            "capabilities",
+        ),
+        (
+            crate::Entity::Artifact(crate::ArtifactEntity),
+            "deploy_path",
+            "artifacts",
+        ),
+    ];
+
+    /// Reports the fields of a file imported by a TOSCA 2.0 file that are
+    /// lost when its definitions are used as TOSCA 2.0 ones. Other fields,
+    /// like `constraints` or `valid_source_types`, are parsed into the same
+    /// definitions as their TOSCA 2.0 counterparts.
+    pub fn report_lossy_fields(file_handle: toto_ast::GraphHandle, ast: &mut toto_ast::AST<E, R>) {
+        let own_definitions = EdgeFiltered::from_fn(&*ast, |e| {
+            !matches!(
+                e.weight().as_tosca(),
+                Some(crate::Relation::ImportFile(_) | crate::Relation::Root(_)) | None
+            )
+        });
+
+        let mut lossy = vec![];
+        let mut dfs = Dfs::new(&own_definitions, file_handle);
+        while let Some(n) = dfs.next(&own_definitions) {
+            let Some(entity) = ast.node_weight(n).unwrap().as_tosca() else {
+                continue;
+            };
+            let Some(yaml) = ast
+                .edges(n)
+                .find_map(|e| e.weight().as_parse_loc().map(|_| e.target()))
+            else {
+                continue;
+            };
+            let Some(fields) = toto_yaml::as_map(yaml, ast) else {
+                continue;
+            };
+            for (k, _) in fields {
+                let Some(key) = toto_yaml::as_string(k, ast) else {
+                    continue;
+                };
+                if let Some((_, field, what)) = Self::LOSSY_FIELDS
+                    .iter()
+                    .find(|(e, field, _)| e == entity && *field == key.0)
+                {
+                    lossy.push((
+                        codes::LOSSY_VERSION_MAPPING.with(format!(
+                            "{} of {} is not supported by {} and is ignored",
+                            field,
+                            what,
+                            v2_0::Tosca2_0::<E, R>::NAME
+                        )),
+                        k,
+                    ));
+                }
+            }
+        }
+
+        for (err, loc) in lossy {
+            add_with_loc(err, loc, ast);
+        }
+    }
+}
+
 impl<E, R> toto_parser::EntityParser<E, R> for Tosca1_3<E, R>
 where
     E: ToscaCompatibleEntity,
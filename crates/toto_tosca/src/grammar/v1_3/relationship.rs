@@ -5,8 +5,8 @@ use crate::{
         collection::Collection, field::Field, field_ref::TypeRef, list::ListRelator,
         ToscaDefinitionsVersion,
     },
-    DefinitionRelation, DescriptionRelation, MetadataRelation, ToscaCompatibleEntity,
-    ToscaCompatibleRelation, VersionRelation,
+    DefinitionRelation, DescriptionRelation, MetadataRelation, StatusRelation,
+    ToscaCompatibleEntity, ToscaCompatibleRelation, VersionRelation,
 };
 use toto_parser::RelationParser;
 
@@ -24,6 +24,7 @@ where
     const SELF: fn() -> E = || crate::Entity::from(crate::RelationshipEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "derived_from" => TypeRef::<crate::RelationshipEntity, crate::DerivedFromRelation>::parse,
+        "status" => Field::<StatusRelation, crate::grammar::v2_0::StatusValue>::parse,
         "version" => Field::<VersionRelation, value::StringValue>::parse,
         "metadata" => Collection::<MetadataRelation, value::AnyValue>::parse,
         "description" => Field::<DescriptionRelation, value::StringValue>::parse,
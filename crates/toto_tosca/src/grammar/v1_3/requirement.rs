@@ -6,8 +6,8 @@ use crate::{
         field_ref::{DefRef, TypeRef},
         ToscaDefinitionsVersion,
     },
-    AssignmentRelation, DefinitionRelation, TargetCapabilityRelation, ToscaCompatibleEntity,
-    ToscaCompatibleRelation,
+    AssignmentRelation, CountRangeRelation, DefinitionRelation, NodeFilterRelation,
+    TargetCapabilityRelation, ToscaCompatibleEntity, ToscaCompatibleRelation,
 };
 use toto_parser::{add_with_loc, mandatory, RelationParser};
 
@@ -30,7 +30,7 @@ where
         "relationship" => Field::<DefinitionRelation, V::RelationshipDefinition>::parse,
         "node" => TypeRef::<crate::NodeEntity, crate::ValidTargetNodeTypeRelation>::parse,
         "capability" => TypeRef::<crate::CapabilityEntity, crate::ValidCapabilityTypeRelation>::parse,
-        "occurrences" => |_, _, _| {},
+        "occurrences" => Field::<CountRangeRelation, value::RangeValue>::parse,
     };
 
     const VALIDATION: &'static [toto_parser::ValidationFieldFn] =
@@ -48,7 +48,7 @@ where
         "node" => DefRef::<crate::ServiceTemplateEntity, crate::NodeEntity, crate::TargetNodeRelation>::parse,
         "capability" => Field::<TargetCapabilityRelation, value::StringValue>::parse,
         "relationship" => Field::<AssignmentRelation, V::RelationshipAssignment>::parse,
-        "node_filter" => |_, _, _| {},
+        "node_filter" => Field::<NodeFilterRelation, V::NodeFilterDefinition>::parse,
         "occurrences" => |_, _, _| {},
     };
 }
@@ -63,7 +63,28 @@ where
         n: toto_ast::GraphHandle,
         ast: &mut toto_ast::AST<E, R>,
     ) -> Option<toto_ast::GraphHandle> {
-        <Self as toto_parser::Schema<E, R>>::parse(n, ast)
+        let req = <Self as toto_parser::Schema<E, R>>::parse(n, ast)?;
+
+        // Unlike `count_range` of TOSCA 2.0, `occurrences` defaults to [ 1, 1 ].
+        let has_occurrences = toto_yaml::as_map(n, ast).is_some_and(|mut items| {
+            items.any(|(k, _)| toto_yaml::as_string(k, ast).is_some_and(|k| k.0 == "occurrences"))
+        });
+        if !has_occurrences {
+            let occurrences = add_with_loc(
+                crate::Entity::from(crate::RangeEntity {
+                    lower: Some(1),
+                    upper: Some(1),
+                }),
+                n,
+                ast,
+            );
+            ast.add_edge(
+                req,
+                occurrences,
+                crate::Relation::from(CountRangeRelation).into(),
+            );
+        }
+        Some(req)
     }
 }
 
@@ -3,11 +3,11 @@ use std::{collections::HashSet, marker::PhantomData};
 use toto_parser::{mandatory, RelationParser};
 
 use crate::{
-    grammar::{collection::Collection, field_ref::TypeRef, ToscaDefinitionsVersion},
-    DefinitionRelation, ToscaCompatibleEntity, ToscaCompatibleRelation,
+    grammar::{collection::Collection, field::Field, field_ref::TypeRef, ToscaDefinitionsVersion},
+    DefinitionRelation, SubstitutionFilterRelation, ToscaCompatibleEntity, ToscaCompatibleRelation,
 };
 
-use super::v2_0::value;
+use super::v2_0::{self, value};
 
 #[derive(Debug)]
 pub struct SubstitutionMapping<V: ToscaDefinitionsVersion>(PhantomData<V>);
@@ -21,12 +21,12 @@ where
     const SELF: fn() -> E = || crate::Entity::from(crate::SubstitutionMappingEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "node_type" => TypeRef::<crate::NodeEntity, crate::SubstitutesTypeRelation>::parse,
-        "substitution_filter" => |_, _, _| {},
-        "properties" => Collection::<DefinitionRelation, value::AnyValue>::parse,
+        "substitution_filter" => Field::<SubstitutionFilterRelation, V::NodeFilterDefinition>::parse,
+        "properties" => Collection::<DefinitionRelation, v2_0::PropertyMapping>::parse,
         "attributes" => Collection::<DefinitionRelation, value::AnyValue>::parse,
-        "capabilities" => Collection::<DefinitionRelation, value::AnyValue>::parse,
-        "requirements" => Collection::<DefinitionRelation, value::AnyValue>::parse,
-        "interfaces" => Collection::<DefinitionRelation, value::AnyValue>::parse,
+        "capabilities" => Collection::<DefinitionRelation, v2_0::CapabilityMapping>::parse,
+        "requirements" => Collection::<DefinitionRelation, v2_0::RequirementMapping>::parse,
+        "interfaces" => Collection::<DefinitionRelation, v2_0::InterfaceMapping>::parse,
     };
 
     const VALIDATION: &'static [toto_parser::ValidationFieldFn] =
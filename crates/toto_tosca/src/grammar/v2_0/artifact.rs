@@ -1,6 +1,6 @@
 use std::{collections::HashSet, marker::PhantomData};
 
-use toto_parser::{add_with_loc, mandatory, EntityParser, RelationParser};
+use toto_parser::{add_with_loc, mandatory, RelationParser};
 
 use crate::{
     grammar::{
@@ -13,7 +13,8 @@ use crate::{
     ArtifactEntity, AssignmentRelation, ChecksumAlgorithmRelation, ChecksumRelation,
     DefinitionRelation, DependencyArtifactRelation, DerivedFromRelation, DescriptionRelation,
     FileExtRelation, HasFileRelation, HasTypeRelation, MetadataRelation, MimeTypeRelation,
-    PrimaryArtifactRelation, ToscaCompatibleEntity, ToscaCompatibleRelation, VersionRelation,
+    PrimaryArtifactRelation, StatusRelation, ToscaCompatibleEntity, ToscaCompatibleRelation,
+    VersionRelation,
 };
 
 use super::value;
@@ -39,6 +40,7 @@ where
     const SELF: fn() -> E = || crate::Entity::from(crate::ArtifactEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "derived_from" => TypeRef::<ArtifactEntity, DerivedFromRelation>::parse,
+        "status" => Field::<StatusRelation, crate::grammar::v2_0::StatusValue>::parse,
         "version" => Field::<VersionRelation, value::StringValue>::parse,
         "metadata" => Collection::<MetadataRelation, value::AnyValue>::parse,
         "description" => Field::<DescriptionRelation, value::StringValue>::parse,
@@ -64,7 +66,7 @@ where
         "artifact_version" => Field::<VersionRelation, value::StringValue>::parse,
         "checksum" => Field::<ChecksumRelation, value::StringValue>::parse,
         "checksum_algorithm" => Field::<ChecksumAlgorithmRelation, value::StringValue>::parse,
-        "properties" => Collection::<AssignmentRelation, value::AnyValue>::parse,
+        "properties" => Collection::<AssignmentRelation, value::PropertyAssignment>::parse,
     };
 
     const VALIDATION: &'static [toto_parser::ValidationFieldFn] = &[
@@ -149,7 +151,7 @@ where
     fn parse(root: toto_ast::GraphHandle, n: toto_ast::GraphHandle, ast: &mut toto_ast::AST<E, R>) {
         match ast.node_weight(n).expect("node not found").as_yaml() {
             Some(toto_yaml::Entity::Map(_)) => {
-                V::ArtifactDefinition::parse(n, ast);
+                Field::<Rel, V::ArtifactDefinition>::parse(root, n, ast);
             }
             Some(toto_yaml::Entity::Str(_) | toto_yaml::Entity::Null(_)) => {
                 DefRef::<crate::NodeEntity, crate::ArtifactEntity, Rel>::parse(root, n, ast);
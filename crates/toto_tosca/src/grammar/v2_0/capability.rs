@@ -11,7 +11,8 @@ use crate::{
         ToscaDefinitionsVersion,
     },
     AssignmentRelation, DefinitionRelation, DescriptionRelation, DirectiveRelation,
-    MetadataRelation, ToscaCompatibleEntity, ToscaCompatibleRelation, VersionRelation,
+    MetadataRelation, StatusRelation, ToscaCompatibleEntity, ToscaCompatibleRelation,
+    VersionRelation,
 };
 
 use super::value;
@@ -34,6 +35,7 @@ where
     const SELF: fn() -> E = || crate::Entity::from(crate::CapabilityEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "derived_from" => TypeRef::<crate::CapabilityEntity, crate::DerivedFromRelation>::parse,
+        "status" => Field::<StatusRelation, crate::grammar::v2_0::StatusValue>::parse,
         "version" => Field::<VersionRelation, value::StringValue>::parse,
         "metadata" => Collection::<MetadataRelation, value::AnyValue>::parse,
         "description" => Field::<DescriptionRelation, value::StringValue>::parse,
@@ -74,8 +76,8 @@ where
     const SELF: fn() -> E = || crate::Entity::from(crate::CapabilityEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "directives" => List::<DirectiveRelation, value::StringValue>::parse,
-        "properties" => Collection::<AssignmentRelation, value::AnyValue>::parse,
-        "attributes" => Collection::<AssignmentRelation, value::AnyValue>::parse,
+        "properties" => Collection::<AssignmentRelation, value::PropertyAssignment>::parse,
+        "attributes" => Collection::<AssignmentRelation, value::AttributeAssignment>::parse,
     };
 }
 
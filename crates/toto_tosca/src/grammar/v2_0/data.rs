@@ -1,13 +1,14 @@
 use std::{collections::HashSet, marker::PhantomData};
 
-use toto_parser::{add_with_loc, mandatory, ParseError, RelationParser};
+use toto_parser::{add_with_loc, mandatory, RelationParser};
 
 use crate::{
+    codes,
     grammar::{collection::Collection, field::Field, field_ref::TypeRef, ToscaDefinitionsVersion},
     DefaultRelation, DefinitionRelation, DescriptionRelation, EntrySchemaRelation,
     ExternalSchemaRelation, KeySchemaRelation, MappingRelation, MetadataRelation, RequiredRelation,
-    ToscaCompatibleEntity, ToscaCompatibleRelation, ValidationRelation, ValueRelation,
-    VersionRelation,
+    StatusRelation, ToscaCompatibleEntity, ToscaCompatibleRelation, ValidationRelation,
+    ValueRelation, VersionRelation,
 };
 
 use super::value;
@@ -36,10 +37,11 @@ where
     const SELF: fn() -> E = || crate::Entity::from(crate::DataEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "derived_from" => TypeRef::<crate::DataEntity, crate::DerivedFromRelation>::parse,
+        "status" => Field::<StatusRelation, StatusValue>::parse,
         "version" => Field::<VersionRelation, value::StringValue>::parse,
         "metadata" => Collection::<MetadataRelation, value::StringValue>::parse,
         "description" => Field::<DescriptionRelation, value::StringValue>::parse,
-        "validation" => Field::<ValidationRelation, value::AnyValue>::parse,
+        "validation" => Field::<ValidationRelation, value::Expression>::parse,
         "properties" => Collection::<DefinitionRelation, V::PropertyDefinition>::parse,
         "key_schema" => Field::<KeySchemaRelation, V::SchemaDefinition>::parse,
         "entry_schema" => Field::<EntrySchemaRelation, V::SchemaDefinition>::parse,
@@ -56,7 +58,7 @@ where
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "type" => TypeRef::<crate::DataEntity, crate::HasTypeRelation>::parse,
         "description" => Field::<DescriptionRelation, value::StringValue>::parse,
-        "validation" => Field::<ValidationRelation, value::AnyValue>::parse,
+        "validation" => Field::<ValidationRelation, value::Expression>::parse,
         "key_schema" => Field::<KeySchemaRelation, V::SchemaDefinition>::parse,
         "entry_schema" => Field::<EntrySchemaRelation, V::SchemaDefinition>::parse,
     };
@@ -88,7 +90,7 @@ where {
             "unsupported" => Ok(crate::StatusEntity::Unsupported),
             "experimental" => Ok(crate::StatusEntity::Experimental),
             "deprecated" => Ok(crate::StatusEntity::Deprecated),
-            other => Err(ParseError::Custom(format!("unknown status: {}", other))),
+            other => Err(codes::UNKNOWN_STATUS.with(format!("unknown status: {}", other))),
         };
 
         match status {
@@ -107,13 +109,13 @@ where
     R: ToscaCompatibleRelation,
     V: ToscaDefinitionsVersion<Entity = E, Relation = R>,
 {
-    const SELF: fn() -> E = || crate::Entity::from(crate::DataEntity).into();
+    const SELF: fn() -> E = || crate::Entity::from(crate::AttributeEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "type" => TypeRef::<crate::DataEntity, crate::HasTypeRelation>::parse,
         "description" => Field::<DescriptionRelation, value::StringValue>::parse,
-        "default" => Field::<DefaultRelation, value::AnyValue>::parse,
-        "status" => Field::<DefaultRelation, StatusValue>::parse,
-        "validation" => Field::<ValidationRelation, value::AnyValue>::parse,
+        "default" => Field::<DefaultRelation, value::Expression>::parse,
+        "status" => Field::<StatusRelation, StatusValue>::parse,
+        "validation" => Field::<ValidationRelation, value::Expression>::parse,
         "key_schema" => Field::<KeySchemaRelation, V::SchemaDefinition>::parse,
         "entry_schema" => Field::<EntrySchemaRelation, V::SchemaDefinition>::parse,
         "metadata" => Collection::<MetadataRelation, value::StringValue>::parse,
@@ -129,15 +131,15 @@ where
     R: ToscaCompatibleRelation,
     V: ToscaDefinitionsVersion<Entity = E, Relation = R>,
 {
-    const SELF: fn() -> E = || crate::Entity::from(crate::DataEntity).into();
+    const SELF: fn() -> E = || crate::Entity::from(crate::PropertyEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "type" => TypeRef::<crate::DataEntity, crate::HasTypeRelation>::parse,
         "description" => Field::<DescriptionRelation, value::StringValue>::parse,
         "required" => Field::<RequiredRelation, value::BoolValue>::parse,
-        "default" => Field::<DefaultRelation, value::AnyValue>::parse,
-        "status" => Field::<DefaultRelation, StatusValue>::parse,
-        "validation" => Field::<ValidationRelation, value::AnyValue>::parse,
-        "value" => Field::<ValueRelation, value::AnyValue>::parse,
+        "default" => Field::<DefaultRelation, value::Expression>::parse,
+        "status" => Field::<StatusRelation, StatusValue>::parse,
+        "validation" => Field::<ValidationRelation, value::Expression>::parse,
+        "value" => Field::<ValueRelation, value::Expression>::parse,
         "key_schema" => Field::<KeySchemaRelation, V::SchemaDefinition>::parse,
         "entry_schema" => Field::<EntrySchemaRelation, V::SchemaDefinition>::parse,
         "external-schema" => Field::<ExternalSchemaRelation, value::StringValue>::parse,
@@ -154,15 +156,15 @@ where
     R: ToscaCompatibleRelation,
     V: ToscaDefinitionsVersion<Entity = E, Relation = R>,
 {
-    const SELF: fn() -> E = || crate::Entity::from(crate::DataEntity).into();
+    const SELF: fn() -> E = || crate::Entity::from(crate::ParameterEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "type" => TypeRef::<crate::DataEntity, crate::HasTypeRelation>::parse,
         "description" => Field::<DescriptionRelation, value::StringValue>::parse,
         "required" => Field::<RequiredRelation, value::BoolValue>::parse,
-        "default" => Field::<DefaultRelation, value::AnyValue>::parse,
-        "status" => Field::<DefaultRelation, StatusValue>::parse,
-        "validation" => Field::<ValidationRelation, value::AnyValue>::parse,
-        "value" => Field::<ValueRelation, value::AnyValue>::parse,
+        "default" => Field::<DefaultRelation, value::Expression>::parse,
+        "status" => Field::<StatusRelation, StatusValue>::parse,
+        "validation" => Field::<ValidationRelation, value::Expression>::parse,
+        "value" => Field::<ValueRelation, value::Expression>::parse,
         "mapping" => Field::<MappingRelation, value::AnyValue>::parse,
         "key_schema" => Field::<KeySchemaRelation, V::SchemaDefinition>::parse,
         "entry_schema" => Field::<EntrySchemaRelation, V::SchemaDefinition>::parse,
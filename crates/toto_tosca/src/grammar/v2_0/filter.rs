@@ -0,0 +1,37 @@
+use std::marker::PhantomData;
+
+use toto_parser::{add_with_loc, ValueRelationParser};
+
+use crate::{
+    grammar::{field::Field, ToscaDefinitionsVersion},
+    ConditionRelation, ToscaCompatibleEntity, ToscaCompatibleRelation,
+};
+
+use super::value;
+
+/// Node filter or substitution filter: a condition the filtered node must
+/// satisfy, `SELF` refers to the node. A list of conditions is satisfied if
+/// all of them are.
+#[derive(Debug)]
+pub struct NodeFilterDefinition<V: ToscaDefinitionsVersion>(PhantomData<V>);
+
+impl<E, R, V> toto_parser::EntityParser<E, R> for NodeFilterDefinition<V>
+where
+    E: ToscaCompatibleEntity,
+    R: ToscaCompatibleRelation,
+    V: ToscaDefinitionsVersion<Entity = E, Relation = R>,
+{
+    fn parse(
+        n: toto_ast::GraphHandle,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle> {
+        let filter = add_with_loc(crate::Entity::from(crate::FilterEntity), n, ast);
+        let conditions = toto_yaml::as_list(n, ast)
+            .map(|items| items.collect::<Vec<_>>())
+            .unwrap_or_else(|| vec![(0, n)]);
+        for (i, condition) in conditions {
+            Field::<ConditionRelation, value::Expression>::parse(i, filter, condition, ast);
+        }
+        Some(filter)
+    }
+}
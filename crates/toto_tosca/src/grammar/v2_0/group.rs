@@ -10,7 +10,7 @@ use crate::{
         list::ListRelator,
         ToscaDefinitionsVersion,
     },
-    AssignmentRelation, DefinitionRelation, DescriptionRelation, MetadataRelation,
+    AssignmentRelation, DefinitionRelation, DescriptionRelation, MetadataRelation, StatusRelation,
     ToscaCompatibleEntity, ToscaCompatibleRelation, VersionRelation,
 };
 
@@ -31,6 +31,7 @@ where
     const SELF: fn() -> E = || crate::Entity::from(crate::GroupEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "derived_from" => TypeRef::<crate::GroupEntity, crate::DerivedFromRelation>::parse,
+        "status" => Field::<StatusRelation, crate::grammar::v2_0::StatusValue>::parse,
         "version" => Field::<VersionRelation, value::StringValue>::parse,
         "metadata" => Collection::<MetadataRelation, value::AnyValue>::parse,
         "description" => Field::<DescriptionRelation, value::StringValue>::parse,
@@ -51,8 +52,8 @@ where
         "type" => TypeRef::<crate::GroupEntity, crate::HasTypeRelation>::parse,
         "description" => Field::<DescriptionRelation, value::StringValue>::parse,
         "metadata" => Collection::<MetadataRelation, value::AnyValue>::parse,
-        "properties" => Collection::<AssignmentRelation, value::AnyValue>::parse,
-        "attributes" => Collection::<AssignmentRelation, value::AnyValue>::parse,
+        "properties" => Collection::<AssignmentRelation, value::PropertyAssignment>::parse,
+        "attributes" => Collection::<AssignmentRelation, value::AttributeAssignment>::parse,
         "members" => ListRelator::<DefRef<crate::ServiceTemplateEntity, crate::NodeEntity, crate::MemberNodeTemplateRelation>>::parse,
     };
 
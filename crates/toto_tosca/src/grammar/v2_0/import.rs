@@ -3,6 +3,7 @@ use std::{collections::HashSet, marker::PhantomData};
 use toto_parser::{add_with_loc, RelationParser};
 
 use crate::{
+    codes,
     grammar::{field::Field, field_ref::DefRef, ToscaDefinitionsVersion},
     ImportNamespaceRelation, ImportProfileRelation, ImportUrlRelation, ToscaCompatibleEntity,
     ToscaCompatibleRelation,
@@ -30,9 +31,7 @@ where
     const VALIDATION: &'static [toto_parser::ValidationFieldFn] = &[
         |fields: &HashSet<String>| {
             if fields.contains("url") && fields.contains("profile") {
-                Some(toto_parser::ParseError::Custom(
-                    "url and profile are mutually exclusive".to_string(),
-                ))
+                Some(codes::CONFLICTING_FIELDS.with("url and profile are mutually exclusive"))
             } else {
                 None
             }
@@ -46,9 +45,7 @@ where
         },
         |fields: &HashSet<String>| {
             if fields.contains("repository") && !fields.contains("url") {
-                Some(toto_parser::ParseError::Custom(
-                    "can only be used when a url is specified".to_string(),
-                ))
+                Some(codes::DEPENDENT_FIELD.with("can only be used when a url is specified"))
             } else {
                 None
             }
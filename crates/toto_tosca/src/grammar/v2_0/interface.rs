@@ -4,7 +4,7 @@ use toto_parser::{mandatory, RelationParser};
 
 use crate::{
     grammar::{collection::Collection, field::Field, field_ref::TypeRef, ToscaDefinitionsVersion},
-    AssignmentRelation, DefinitionRelation, DescriptionRelation, MetadataRelation,
+    AssignmentRelation, DefinitionRelation, DescriptionRelation, MetadataRelation, StatusRelation,
     ToscaCompatibleEntity, ToscaCompatibleRelation, VersionRelation,
 };
 
@@ -28,6 +28,7 @@ where
     const SELF: fn() -> E = || crate::Entity::from(crate::InterfaceEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "derived_from" => TypeRef::<crate::InterfaceEntity, crate::DerivedFromRelation>::parse,
+        "status" => Field::<StatusRelation, crate::grammar::v2_0::StatusValue>::parse,
         "version" => Field::<VersionRelation, value::StringValue>::parse,
         "metadata" => Collection::<MetadataRelation, value::AnyValue>::parse,
         "description" => Field::<DescriptionRelation, value::StringValue>::parse,
@@ -67,7 +68,7 @@ where
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "operations" => Collection::<AssignmentRelation, V::OperationAssignment>::parse,
         "notifications" => Collection::<AssignmentRelation, V::NotificationAssignment>::parse,
-        "inputs" => Collection::<AssignmentRelation, value::AnyValue>::parse,
+        "inputs" => Collection::<AssignmentRelation, value::ParameterAssignment>::parse,
     };
 }
 
@@ -2,6 +2,7 @@ pub mod artifact;
 pub mod capability;
 pub mod data;
 pub mod file;
+pub mod filter;
 pub mod function;
 pub mod group;
 pub mod import;
@@ -24,6 +25,7 @@ pub use artifact::*;
 pub use capability::*;
 pub use data::*;
 pub use file::*;
+pub use filter::*;
 pub use function::*;
 pub use group::*;
 pub use import::*;
@@ -92,8 +94,10 @@ where
 
     type RequirementDefinition = self::RequirementDefinition<Self>;
     type RequirementAssignment = self::RequirementAssignment<Self>;
+    type NodeFilterDefinition = self::NodeFilterDefinition<Self>;
+    type ConditionDefinition = self::NodeFilterDefinition<Self>;
     type RelationshipDefinition = self::RelationshipDefinition<Self>;
-    type RelationshipAssignment = self::RequirementAssignment<Self>;
+    type RelationshipAssignment = self::RelationshipAssignment<Self>;
 
     type WorkflowDefinition = self::WorkflowDefinition<Self>;
     type WorkflowStepDefinition = self::WorkflowStepDefinition<Self>;
@@ -147,6 +151,138 @@ where
                 Self::Relation::from(crate::Relation::Description(crate::DescriptionRelation)),
             );
         }
+
+        const BUILTIN_FUNCTIONS: &[(&str, &str)] = &[
+            (
+                "get_input",
+                "Retrieves the value of a service template input",
+            ),
+            (
+                "get_property",
+                "Retrieves the value of a property of a TOSCA entity",
+            ),
+            (
+                "get_attribute",
+                "Retrieves the value of an attribute of a TOSCA entity",
+            ),
+            ("get_artifact", "Retrieves the location of an artifact"),
+            ("value", "Refers to the value being validated"),
+            (
+                "node_index",
+                "Index of the node in a node template with count",
+            ),
+            (
+                "relationship_index",
+                "Index of the relationship in a requirement with count",
+            ),
+            (
+                "available_allocation",
+                "Remaining allocation of a capability property",
+            ),
+            ("concat", "Concatenates strings"),
+            ("join", "Joins a list of strings with an optional delimiter"),
+            (
+                "token",
+                "Splits a string by separators and returns the token at index",
+            ),
+            ("equal", "True if all arguments are equal"),
+            (
+                "greater_than",
+                "True if the first argument is greater than the second",
+            ),
+            (
+                "greater_or_equal",
+                "True if the first argument is greater than or equal to the second",
+            ),
+            (
+                "less_than",
+                "True if the first argument is less than the second",
+            ),
+            (
+                "less_or_equal",
+                "True if the first argument is less than or equal to the second",
+            ),
+            (
+                "in_range",
+                "True if the value is within the inclusive range",
+            ),
+            (
+                "valid_values",
+                "True if the value is one of the listed values",
+            ),
+            (
+                "matches",
+                "True if the string matches the regular expression",
+            ),
+            (
+                "has_suffix",
+                "True if the string or list ends with the suffix",
+            ),
+            (
+                "has_prefix",
+                "True if the string or list starts with the prefix",
+            ),
+            (
+                "contains",
+                "True if the string or list contains the element",
+            ),
+            ("has_entry", "True if the list or map contains the entry"),
+            ("has_key", "True if the map contains the key"),
+            (
+                "has_all_entries",
+                "True if the list or map contains all of the entries",
+            ),
+            ("has_all_keys", "True if the map contains all of the keys"),
+            (
+                "has_any_entry",
+                "True if the list or map contains any of the entries",
+            ),
+            ("has_any_key", "True if the map contains any of the keys"),
+            ("and", "Logical conjunction"),
+            ("or", "Logical disjunction"),
+            ("not", "Logical negation"),
+            ("xor", "Logical exclusive disjunction"),
+            ("length", "Length of a string, list or map"),
+            ("union", "Union of lists"),
+            ("intersection", "Intersection of lists"),
+            ("sum", "Sum of numbers"),
+            ("difference", "Difference of two numbers"),
+            ("product", "Product of numbers"),
+            ("quotient", "Quotient of two numbers"),
+            ("remainder", "Remainder of integer division"),
+            ("round", "Rounds a float to the nearest integer"),
+            ("floor", "Rounds a float down"),
+            ("ceil", "Rounds a float up"),
+        ];
+
+        for (name, details) in BUILTIN_FUNCTIONS {
+            let function_handle = add_with_loc(
+                Self::Entity::from(crate::Entity::Function(crate::FunctionEntity)),
+                root,
+                ast,
+            );
+
+            ast.add_edge(
+                root,
+                function_handle,
+                Self::Relation::from(crate::Relation::Definition(crate::DefinitionRelation(
+                    name.to_string(),
+                ))),
+            );
+
+            let description_handle = add_with_loc(
+                Self::Entity::from(toto_yaml::Entity::Str(toto_yaml::YamlString(
+                    details.to_string(),
+                ))),
+                root,
+                ast,
+            );
+            ast.add_edge(
+                function_handle,
+                description_handle,
+                Self::Relation::from(crate::Relation::Description(crate::DescriptionRelation)),
+            );
+        }
     }
 }
 
@@ -183,4 +319,20 @@ mod tests {
 
         get_errors(&ast).for_each(|(what, loc)| report_error(what, loc, &ast));
     }
+
+    #[test]
+    fn relationship_assignment() {
+        let mut ast = toto_ast::AST::<Entity, Relation>::new();
+
+        let doc_path = "file://".to_string() + env!("CARGO_MANIFEST_DIR");
+        let doc_path = url::Url::parse(&doc_path).unwrap();
+        let doc_path = doc_path
+            .join("../tests/tosca_2_0_relationship_assignment.yaml")
+            .unwrap();
+
+        let mut parser = ToscaParser::new();
+        parser.parse(&doc_path, &mut ast).unwrap();
+
+        assert_eq!(get_errors(&ast).count(), 0);
+    }
 }
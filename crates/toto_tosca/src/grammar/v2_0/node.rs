@@ -4,13 +4,14 @@ use crate::{
     grammar::{
         collection::Collection,
         field::Field,
-        field_ref::TypeRef,
+        field_ref::{DefRef, TypeRef},
         list::{KeyedList, List},
         ToscaDefinitionsVersion,
     },
-    AssignmentRelation, DefinitionRelation, DescriptionRelation, DirectiveRelation,
-    MetadataRelation, OrderedAssignmentRelation, OrderedDefinitionRelation, ToscaCompatibleEntity,
-    ToscaCompatibleRelation, VersionRelation,
+    AssignmentRelation, CopyRelation, CountRelation, DefinitionRelation, DescriptionRelation,
+    DirectiveRelation, MetadataRelation, NodeFilterRelation, OrderedAssignmentRelation,
+    OrderedDefinitionRelation, StatusRelation, ToscaCompatibleEntity, ToscaCompatibleRelation,
+    VersionRelation,
 };
 use toto_parser::RelationParser;
 
@@ -31,6 +32,7 @@ where
     const SELF: fn() -> E = || crate::Entity::from(crate::NodeEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "derived_from" => TypeRef::<crate::NodeEntity, crate::DerivedFromRelation>::parse,
+        "status" => Field::<StatusRelation, crate::grammar::v2_0::StatusValue>::parse,
         "version" => Field::<VersionRelation, value::StringValue>::parse,
         "metadata" => Collection::<MetadataRelation, value::AnyValue>::parse,
         "description" => Field::<DescriptionRelation, value::StringValue>::parse,
@@ -55,15 +57,15 @@ where
         "description" => Field::<DescriptionRelation, value::StringValue>::parse,
         "metadata" => Collection::<MetadataRelation, value::AnyValue>::parse,
         "directives" => List::<DirectiveRelation, value::StringValue>::parse,
-        "properties" => Collection::<AssignmentRelation, value::AnyValue>::parse,
-        "attributes" => Collection::<AssignmentRelation, value::AnyValue>::parse,
+        "properties" => Collection::<AssignmentRelation, value::PropertyAssignment>::parse,
+        "attributes" => Collection::<AssignmentRelation, value::AttributeAssignment>::parse,
         "capabilities" => Collection::<AssignmentRelation, V::CapabilityAssignment>::parse,
         "requirements" => KeyedList::<OrderedAssignmentRelation, V::RequirementAssignment>::parse,
         "interfaces" => Collection::<AssignmentRelation, V::InterfaceAssignment>::parse,
         "artifacts" => Collection::<DefinitionRelation, V::ArtifactDefinition>::parse,
-        "count" => |_, _, _| {},
-        "node_filter" => |_, _, _| {},
-        "copy" => |_, _, _| {},
+        "count" => Field::<CountRelation, value::Expression>::parse,
+        "node_filter" => Field::<NodeFilterRelation, V::NodeFilterDefinition>::parse,
+        "copy" => DefRef::<crate::ServiceTemplateEntity, crate::NodeEntity, CopyRelation>::parse,
     };
 }
 
@@ -40,7 +40,7 @@ where
     const SELF: fn() -> E = || crate::Entity::from(crate::NotificationEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "implementation" => Field::<DefinitionRelation, V::ImplementationDefinition>::parse,
-        "inputs" => Collection::<AssignmentRelation, value::AnyValue>::parse,
+        "inputs" => Collection::<AssignmentRelation, value::ParameterAssignment>::parse,
         "outputs" => Collection::<AssignmentRelation, value::AnyValue>::parse,
     };
 }
@@ -11,8 +11,8 @@ use crate::{
         ToscaDefinitionsVersion,
     },
     AssignmentRelation, DefinitionRelation, DescriptionRelation, MetadataRelation,
-    PolicyTriggerEventRelation, ToscaCompatibleEntity, ToscaCompatibleRelation, VersionRelation,
-    WorkflowActivityRelation,
+    PolicyTriggerEventRelation, StatusRelation, ToscaCompatibleEntity, ToscaCompatibleRelation,
+    VersionRelation, WorkflowActivityRelation,
 };
 
 use super::value;
@@ -55,6 +55,7 @@ where
     const SELF: fn() -> E = || crate::Entity::from(crate::PolicyEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "derived_from" => TypeRef::<crate::PolicyEntity, crate::DerivedFromRelation>::parse,
+        "status" => Field::<StatusRelation, crate::grammar::v2_0::StatusValue>::parse,
         "version" => Field::<VersionRelation, value::StringValue>::parse,
         "metadata" => Collection::<MetadataRelation, value::AnyValue>::parse,
         "description" => Field::<DescriptionRelation, value::StringValue>::parse,
@@ -75,7 +76,7 @@ where
         "type" => TypeRef::<crate::PolicyEntity, crate::HasTypeRelation>::parse,
         "description" => Field::<DescriptionRelation, value::StringValue>::parse,
         "metadata" => Collection::<MetadataRelation, value::AnyValue>::parse,
-        "properties" => Collection::<AssignmentRelation, value::AnyValue>::parse,
+        "properties" => Collection::<AssignmentRelation, value::PropertyAssignment>::parse,
         // todo: target nodes and groups
         "targets" => ListRelator::<DefRef<crate::ServiceTemplateEntity, crate::NodeEntity, crate::ValidTargetNodeTypeRelation>>::parse,
         "triggers" => Collection::<DefinitionRelation, V::PolicyTriggerDefinition>::parse,
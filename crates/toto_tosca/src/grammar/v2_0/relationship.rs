@@ -8,7 +8,7 @@ use crate::{
         list::ListRelator,
         ToscaDefinitionsVersion,
     },
-    AssignmentRelation, DefinitionRelation, DescriptionRelation, MetadataRelation,
+    AssignmentRelation, DefinitionRelation, DescriptionRelation, MetadataRelation, StatusRelation,
     ToscaCompatibleEntity, ToscaCompatibleRelation, VersionRelation,
 };
 use toto_parser::{add_with_loc, mandatory, RelationParser};
@@ -36,6 +36,7 @@ where
     const SELF: fn() -> E = || crate::Entity::from(crate::RelationshipEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "derived_from" => TypeRef::<crate::RelationshipEntity, crate::DerivedFromRelation>::parse,
+        "status" => Field::<StatusRelation, crate::grammar::v2_0::StatusValue>::parse,
         "version" => Field::<VersionRelation, value::StringValue>::parse,
         "metadata" => Collection::<MetadataRelation, value::AnyValue>::parse,
         "description" => Field::<DescriptionRelation, value::StringValue>::parse,
@@ -59,8 +60,8 @@ where
         "type" => TypeRef::<crate::RelationshipEntity, crate::HasTypeRelation>::parse,
         "description" => Field::<DescriptionRelation, value::StringValue>::parse,
         "metadata" => Collection::<MetadataRelation, value::AnyValue>::parse,
-        "properties" => Collection::<AssignmentRelation, value::AnyValue>::parse,
-        "attributes" => Collection::<AssignmentRelation, value::AnyValue>::parse,
+        "properties" => Collection::<AssignmentRelation, value::PropertyAssignment>::parse,
+        "attributes" => Collection::<AssignmentRelation, value::AttributeAssignment>::parse,
         "interfaces" => Collection::<AssignmentRelation, V::InterfaceAssignment>::parse,
         "copy" => |_, _, _| {},
     };
@@ -80,8 +81,8 @@ where
         "type" => TypeRef::<crate::RelationshipEntity, crate::HasTypeRelation>::parse,
         "description" => Field::<DescriptionRelation, value::StringValue>::parse,
         "metadata" => Collection::<MetadataRelation, value::AnyValue>::parse,
-        "properties" => Collection::<AssignmentRelation, value::AnyValue>::parse,
-        "attributes" => Collection::<AssignmentRelation, value::AnyValue>::parse,
+        "properties" => Collection::<AssignmentRelation, value::PropertyAssignment>::parse,
+        "attributes" => Collection::<AssignmentRelation, value::AttributeAssignment>::parse,
         "interfaces" => Collection::<AssignmentRelation, V::InterfaceAssignment>::parse,
     };
 
@@ -98,8 +99,8 @@ where
     const SELF: fn() -> E = || crate::Entity::from(crate::RelationshipEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "type" => TypeRef::<crate::RelationshipEntity, crate::HasTypeRelation>::parse,
-        "properties" => Collection::<AssignmentRelation, value::AnyValue>::parse,
-        "attributes" => Collection::<AssignmentRelation, value::AnyValue>::parse,
+        "properties" => Collection::<AssignmentRelation, value::PropertyAssignment>::parse,
+        "attributes" => Collection::<AssignmentRelation, value::AttributeAssignment>::parse,
         "interfaces" => Collection::<AssignmentRelation, V::InterfaceAssignment>::parse,
     };
 }
@@ -8,8 +8,9 @@ use crate::{
         list::List,
         ToscaDefinitionsVersion,
     },
-    AssignmentRelation, DefinitionRelation, DescriptionRelation, DirectiveRelation,
-    MetadataRelation, TargetCapabilityRelation, ToscaCompatibleEntity, ToscaCompatibleRelation,
+    AssignmentRelation, CountRangeRelation, DefinitionRelation, DescriptionRelation,
+    DirectiveRelation, MetadataRelation, NodeFilterRelation, TargetCapabilityRelation,
+    ToscaCompatibleEntity, ToscaCompatibleRelation,
 };
 use toto_parser::{add_with_loc, mandatory, RelationParser};
 
@@ -34,8 +35,8 @@ where
         "relationship" => Field::<DefinitionRelation, V::RelationshipDefinition>::parse,
         "node" => TypeRef::<crate::NodeEntity, crate::ValidTargetNodeTypeRelation>::parse,
         "capability" => TypeRef::<crate::CapabilityEntity, crate::ValidCapabilityTypeRelation>::parse,
-        "node_filter" => |_, _, _| {},
-        "count_range" => |_, _, _| {},
+        "node_filter" => Field::<NodeFilterRelation, V::NodeFilterDefinition>::parse,
+        "count_range" => Field::<CountRangeRelation, value::RangeValue>::parse,
     };
 
     const VALIDATION: &'static [toto_parser::ValidationFieldFn] =
@@ -55,7 +56,7 @@ where
         "relationship" => Field::<AssignmentRelation, V::RelationshipAssignment>::parse,
         "allocation" => |_, _, _| {},
         "count" => |_, _, _| {},
-        "node_filter" => |_, _, _| {},
+        "node_filter" => Field::<NodeFilterRelation, V::NodeFilterDefinition>::parse,
         "directives" => List::<DirectiveRelation, value::StringValue>::parse,
         "optional" => |_, _, _| {},
     };
@@ -4,8 +4,9 @@ use toto_parser::RelationParser;
 
 use crate::{
     grammar::{collection::Collection, field::Field, list::KeyedList, ToscaDefinitionsVersion},
-    DefinitionRelation, DescriptionRelation, MetadataRelation, OrderedDefinitionRelation,
-    SubstitutionMappingRelation, ToscaCompatibleEntity, ToscaCompatibleRelation,
+    DefinitionRelation, DescriptionRelation, InputRelation, MetadataRelation,
+    OrderedDefinitionRelation, OutputRelation, SubstitutionMappingRelation, ToscaCompatibleEntity,
+    ToscaCompatibleRelation,
 };
 
 use super::value;
@@ -23,8 +24,8 @@ where
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "description" => Field::<DescriptionRelation, value::StringValue>::parse,
         "metadata" => Collection::<MetadataRelation, value::AnyValue>::parse,
-        "inputs" => Collection::<DefinitionRelation, V::ParameterDefinition>::parse,
-        "outputs" => Collection::<DefinitionRelation, V::ParameterDefinition>::parse,
+        "inputs" => Collection::<InputRelation, V::ParameterDefinition>::parse,
+        "outputs" => Collection::<OutputRelation, V::ParameterDefinition>::parse,
         "node_templates" => Collection::<DefinitionRelation, V::NodeTemplateDefinition>::parse,
         "relationship_templates" => Collection::<DefinitionRelation, V::RelationshipTemplateDefinition>::parse,
         "groups" => Collection::<DefinitionRelation, V::GroupDefinition>::parse,
@@ -1,12 +1,18 @@
 use std::{collections::HashSet, marker::PhantomData};
 
-use toto_parser::{mandatory, RelationParser};
+use toto_parser::{add_with_loc, mandatory, ParseError, RelationParser};
 
 use crate::{
     grammar::{
-        collection::Collection, field_ref::TypeRef, list::KeyedList, ToscaDefinitionsVersion,
+        collection::Collection,
+        field::Field,
+        field_ref::{DefRef, FieldRef, TypeRef},
+        list::KeyedList,
+        ToscaDefinitionsVersion,
     },
-    DefinitionRelation, OrderedDefinitionRelation, ToscaCompatibleEntity, ToscaCompatibleRelation,
+    semantic::SimpleLookuper,
+    DefinitionRelation, MappedNameRelation, OrderedDefinitionRelation, SubstitutionFilterRelation,
+    ToscaCompatibleEntity, ToscaCompatibleRelation,
 };
 
 use super::value;
@@ -23,12 +29,12 @@ where
     const SELF: fn() -> E = || crate::Entity::from(crate::SubstitutionMappingEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "node_type" => TypeRef::<crate::NodeEntity, crate::SubstitutesTypeRelation>::parse,
-        "substitution_filter" => |_, _, _| {},
-        "properties" => Collection::<DefinitionRelation, value::AnyValue>::parse,
-        "attributes" => Collection::<DefinitionRelation, value::AnyValue>::parse,
-        "capabilities" => Collection::<DefinitionRelation, value::AnyValue>::parse,
-        "requirements" => KeyedList::<OrderedDefinitionRelation, value::AnyValue>::parse,
-        "interfaces" => Collection::<DefinitionRelation, value::AnyValue>::parse,
+        "substitution_filter" => Field::<SubstitutionFilterRelation, V::NodeFilterDefinition>::parse,
+        "properties" => Collection::<DefinitionRelation, PropertyMapping>::parse,
+        "attributes" => Collection::<DefinitionRelation, AttributeMapping>::parse,
+        "capabilities" => Collection::<DefinitionRelation, CapabilityMapping>::parse,
+        "requirements" => KeyedList::<OrderedDefinitionRelation, RequirementMapping>::parse,
+        "interfaces" => Collection::<DefinitionRelation, InterfaceMapping>::parse,
     };
 
     const VALIDATION: &'static [toto_parser::ValidationFieldFn] =
@@ -48,3 +54,191 @@ where
         <Self as toto_parser::Schema<E, R>>::parse(n, ast)
     }
 }
+
+/// `[ <input_name> ]`, maps a property of the substituted node type to an
+/// input of the service template.
+pub struct PropertyMapping;
+
+/// `[ <output_name> ]`, maps an attribute of the substituted node type to an
+/// output of the service template.
+pub struct AttributeMapping;
+
+/// `[ <node_template_name>, <capability_name> ]`
+pub struct CapabilityMapping;
+
+/// `[ <node_template_name>, <requirement_name> ]`
+pub struct RequirementMapping;
+
+/// Maps the operations of an interface of the substituted node type to
+/// workflows of the service template, `<operation_name>: <workflow_name>`.
+pub struct InterfaceMapping;
+
+pub struct OperationMapping;
+
+impl<E, R> toto_parser::EntityParser<E, R> for PropertyMapping
+where
+    E: ToscaCompatibleEntity,
+    R: ToscaCompatibleRelation,
+{
+    fn parse(
+        n: toto_ast::GraphHandle,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle> {
+        let [input] = get_items(n, ast, "[ input_name ]")?;
+        let mapping = add_with_loc(crate::Entity::from(crate::MappingEntity::Property), n, ast);
+        parameter_ref(
+            |s| crate::Relation::from(crate::InputRelation(s)),
+            crate::Relation::from(crate::MappedInputRelation),
+        )
+        .parse(mapping, input, ast);
+        Some(mapping)
+    }
+}
+
+impl<E, R> toto_parser::EntityParser<E, R> for AttributeMapping
+where
+    E: ToscaCompatibleEntity,
+    R: ToscaCompatibleRelation,
+{
+    fn parse(
+        n: toto_ast::GraphHandle,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle> {
+        let [output] = get_items(n, ast, "[ output_name ]")?;
+        let mapping = add_with_loc(crate::Entity::from(crate::MappingEntity::Attribute), n, ast);
+        parameter_ref(
+            |s| crate::Relation::from(crate::OutputRelation(s)),
+            crate::Relation::from(crate::MappedOutputRelation),
+        )
+        .parse(mapping, output, ast);
+        Some(mapping)
+    }
+}
+
+impl<E, R> toto_parser::EntityParser<E, R> for CapabilityMapping
+where
+    E: ToscaCompatibleEntity,
+    R: ToscaCompatibleRelation,
+{
+    fn parse(
+        n: toto_ast::GraphHandle,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle> {
+        parse_node_mapping(
+            crate::MappingEntity::Capability,
+            "[ node_template_name, capability_name ]",
+            n,
+            ast,
+        )
+    }
+}
+
+impl<E, R> toto_parser::EntityParser<E, R> for RequirementMapping
+where
+    E: ToscaCompatibleEntity,
+    R: ToscaCompatibleRelation,
+{
+    fn parse(
+        n: toto_ast::GraphHandle,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle> {
+        parse_node_mapping(
+            crate::MappingEntity::Requirement,
+            "[ node_template_name, requirement_name ]",
+            n,
+            ast,
+        )
+    }
+}
+
+impl<E, R> toto_parser::EntityParser<E, R> for InterfaceMapping
+where
+    E: ToscaCompatibleEntity,
+    R: ToscaCompatibleRelation,
+{
+    fn parse(
+        n: toto_ast::GraphHandle,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle> {
+        let mapping = add_with_loc(crate::Entity::from(crate::MappingEntity::Interface), n, ast);
+        Collection::<DefinitionRelation, OperationMapping>::parse(mapping, n, ast);
+        Some(mapping)
+    }
+}
+
+impl<E, R> toto_parser::EntityParser<E, R> for OperationMapping
+where
+    E: ToscaCompatibleEntity,
+    R: ToscaCompatibleRelation,
+{
+    fn parse(
+        n: toto_ast::GraphHandle,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle> {
+        let mapping = add_with_loc(crate::Entity::from(crate::MappingEntity::Operation), n, ast);
+        DefRef::<crate::ServiceTemplateEntity, crate::WorkflowEntity, crate::WorkflowRelation>::parse(
+            mapping, n, ast,
+        );
+        Some(mapping)
+    }
+}
+
+/// Items of a mapping list of exactly `N` items.
+fn get_items<const N: usize, E, R>(
+    n: toto_ast::GraphHandle,
+    ast: &mut toto_ast::AST<E, R>,
+    expected: &'static str,
+) -> Option<[toto_ast::GraphHandle; N]>
+where
+    E: ToscaCompatibleEntity,
+    R: ToscaCompatibleRelation,
+{
+    toto_yaml::as_list(n, ast)
+        .and_then(|items| {
+            let mut items = items.collect::<Vec<_>>();
+            items.sort();
+            items
+                .into_iter()
+                .map(|(_, item)| item)
+                .collect::<Vec<_>>()
+                .try_into()
+                .ok()
+        })
+        .or_else(|| {
+            add_with_loc(ParseError::UnexpectedType(expected), n, ast);
+            None
+        })
+}
+
+fn parameter_ref(what_rel: fn(String) -> crate::Relation, then: crate::Relation) -> FieldRef {
+    FieldRef(SimpleLookuper {
+        root: (
+            crate::Relation::Root(crate::RootRelation),
+            crate::Entity::ServiceTemplate(crate::ServiceTemplateEntity),
+        ),
+        what: crate::Entity::from(crate::ParameterEntity),
+        what_rel,
+        then,
+    })
+}
+
+/// A capability or a requirement of a node template of the service
+/// template. The name is checked once the node template is known.
+fn parse_node_mapping<E, R>(
+    kind: crate::MappingEntity,
+    expected: &'static str,
+    n: toto_ast::GraphHandle,
+    ast: &mut toto_ast::AST<E, R>,
+) -> Option<toto_ast::GraphHandle>
+where
+    E: ToscaCompatibleEntity,
+    R: ToscaCompatibleRelation,
+{
+    let [node, name] = get_items(n, ast, expected)?;
+    let mapping = add_with_loc(crate::Entity::from(kind), n, ast);
+    DefRef::<crate::ServiceTemplateEntity, crate::NodeEntity, crate::TargetNodeRelation>::parse(
+        mapping, node, ast,
+    );
+    Field::<MappedNameRelation, value::StringValue>::parse(mapping, name, ast);
+    Some(mapping)
+}
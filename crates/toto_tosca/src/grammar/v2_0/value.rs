@@ -1,7 +1,14 @@
+use std::marker::PhantomData;
+
 use petgraph::data::DataMap;
-use toto_parser::add_with_loc;
+use toto_parser::{add_with_loc, RelationParser};
 
-use crate::{ToscaCompatibleEntity, ToscaCompatibleRelation};
+use crate::{
+    codes,
+    grammar::{field::Field, field_ref::FieldRef},
+    semantic::{Literal, SimpleLookuper},
+    ToscaCompatibleEntity, ToscaCompatibleRelation,
+};
 
 pub struct StringValue;
 impl<E, R> toto_parser::EntityParser<E, R> for StringValue
@@ -78,6 +85,36 @@ where {
     }
 }
 
+/// Integer range, e.g. `count_range`, parsed into a range entity.
+pub struct RangeValue;
+impl<E, R> toto_parser::EntityParser<E, R> for RangeValue
+where
+    E: ToscaCompatibleEntity,
+    R: ToscaCompatibleRelation,
+{
+    fn parse(
+        n: toto_ast::GraphHandle,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle>
+where {
+        let range = Literal::parse_range(n, ast).and_then(|range| match range.lower {
+            Some(lower) if lower < 0 => Err("lower bound must not be negative".to_string()),
+            _ => Ok(range),
+        });
+        match range {
+            Ok(range) => Some(add_with_loc(crate::Entity::from(range), n, ast)),
+            Err(err) => {
+                add_with_loc(
+                    codes::INVALID_LITERAL.with(format!("invalid range: {}", err)),
+                    n,
+                    ast,
+                );
+                None
+            }
+        }
+    }
+}
+
 pub struct AnyValue;
 impl<E, R> toto_parser::EntityParser<E, R> for AnyValue
 where
@@ -92,3 +129,119 @@ where {
         Some(n)
     }
 }
+
+/// Any value, possibly containing function calls. Calls are single-key maps
+/// with a `$`-prefixed key (`$$` escapes a literal key).
+pub struct Expression;
+impl<E, R> toto_parser::EntityParser<E, R> for Expression
+where
+    E: ToscaCompatibleEntity,
+    R: ToscaCompatibleRelation,
+{
+    fn parse(
+        n: toto_ast::GraphHandle,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle>
+where {
+        Self::parse_calls(n, n, ast);
+        Some(n)
+    }
+}
+
+impl Expression {
+    fn parse_calls<E, R>(
+        root: toto_ast::GraphHandle,
+        n: toto_ast::GraphHandle,
+        ast: &mut toto_ast::AST<E, R>,
+    ) where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        if let Some(call) = Self::parse_call(n, ast) {
+            ast.add_edge(
+                call,
+                root,
+                crate::Relation::Root(crate::RootRelation).into(),
+            );
+            return;
+        }
+
+        if let Some(items) = toto_yaml::as_list(n, ast) {
+            items.for_each(|(_, v)| Self::parse_calls(root, v, ast));
+        } else if let Some(items) = toto_yaml::as_map(n, ast) {
+            items.for_each(|(_, v)| Self::parse_calls(root, v, ast));
+        }
+    }
+
+    fn parse_call<E, R>(
+        n: toto_ast::GraphHandle,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mut items = toto_yaml::as_map(n, ast)?;
+        let (k, v) = items.next()?;
+        if items.next().is_some() {
+            return None;
+        }
+        toto_yaml::as_string(k, ast)
+            .filter(|name| name.0.starts_with('$') && !name.0.starts_with("$$"))?;
+
+        let call = add_with_loc(crate::Entity::from(crate::FunctionCallEntity), n, ast);
+        FieldRef(SimpleLookuper {
+            root: (
+                crate::Relation::Root(crate::RootRelation),
+                crate::Entity::File(crate::FileEntity),
+            ),
+            what: crate::Entity::Function(crate::FunctionEntity),
+            what_rel: |s| {
+                crate::Relation::Definition(crate::DefinitionRelation(
+                    s.trim_start_matches('$').to_string(),
+                ))
+            },
+            then: crate::Relation::Function(crate::FunctionRelation),
+        })
+        .link(call, k, ast);
+
+        let args = toto_yaml::as_list(v, ast)
+            .map(|items| items.collect::<Vec<_>>())
+            .unwrap_or_else(|| vec![(0, v)]);
+        for (i, arg) in args {
+            ast.add_edge(
+                call,
+                arg,
+                crate::Relation::FunctionArgument(crate::FunctionArgumentRelation(i)).into(),
+            );
+            Self::parse_calls(call, arg, ast);
+        }
+
+        Some(call)
+    }
+}
+
+/// Property, attribute or parameter assignment. The assigned value is linked
+/// with a `ValueRelation`.
+pub struct ValueAssignment<Ent>(PhantomData<Ent>);
+impl<Ent, E, R> toto_parser::EntityParser<E, R> for ValueAssignment<Ent>
+where
+    E: ToscaCompatibleEntity,
+    R: ToscaCompatibleRelation,
+    Ent: Default,
+    crate::Entity: From<Ent>,
+{
+    fn parse(
+        n: toto_ast::GraphHandle,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle>
+where {
+        let assignment = add_with_loc(crate::Entity::from(Ent::default()), n, ast);
+        Field::<crate::ValueRelation, Expression>::parse(assignment, n, ast);
+        Some(assignment)
+    }
+}
+
+pub type PropertyAssignment = ValueAssignment<crate::PropertyEntity>;
+pub type AttributeAssignment = ValueAssignment<crate::AttributeEntity>;
+pub type ParameterAssignment = ValueAssignment<crate::ParameterEntity>;
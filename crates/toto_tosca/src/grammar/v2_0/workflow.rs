@@ -3,12 +3,18 @@ use std::{collections::HashSet, marker::PhantomData};
 use toto_parser::{add_with_loc, mandatory, EntityParser, ParseError, RelationParser};
 
 use crate::{
+    codes,
     grammar::{
-        collection::Collection, field::Field, field_ref::DefRef, list::List,
+        collection::Collection,
+        field::Field,
+        field_ref::DefRef,
+        list::{List, ListRelator},
         ToscaDefinitionsVersion,
     },
-    DefinitionRelation, DescriptionRelation, MetadataRelation, ToscaCompatibleEntity,
-    ToscaCompatibleRelation, WorkflowActivityRelation,
+    AssignmentRelation, DefinitionRelation, DescriptionRelation, MetadataRelation,
+    OnFailureRelation, OnSuccessRelation, OperationRelation, PreconditionRelation,
+    StepFilterRelation, TargetRelationshipRelation, ToscaCompatibleEntity, ToscaCompatibleRelation,
+    WorkflowActivityRelation,
 };
 
 use super::value;
@@ -46,7 +52,7 @@ where
         "metadata" => Collection::<MetadataRelation, value::AnyValue>::parse,
         "inputs" => Collection::<DefinitionRelation, V::ParameterDefinition>::parse,
         "outputs" => Collection::<DefinitionRelation, V::ParameterDefinition>::parse,
-        "precondition" => |_, _, _| {},
+        "precondition" => Field::<PreconditionRelation, V::ConditionDefinition>::parse,
         "steps" => Collection::<DefinitionRelation, V::WorkflowStepDefinition>::parse,
         "implementation" => Field::<DefinitionRelation, V::ImplementationDefinition>::parse,
     };
@@ -54,9 +60,10 @@ where
     const VALIDATION: &'static [toto_parser::ValidationFieldFn] = &[
         |fields: &HashSet<String>| {
             if fields.contains("steps") && fields.contains("implementation") {
-                Some(toto_parser::ParseError::Custom(
-                    "steps and implementation are mutually exclusive".to_string(),
-                ))
+                Some(
+                    codes::CONFLICTING_FIELDS
+                        .with("steps and implementation are mutually exclusive"),
+                )
             } else {
                 None
             }
@@ -82,11 +89,11 @@ where
     const SELF: fn() -> E = || crate::Entity::from(crate::WorkflowStepEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "target" => DefRef::<crate::ServiceTemplateEntity, crate::NodeEntity, crate::TargetNodeRelation>::parse,
-        "target_relationship" => Field::<DefinitionRelation, value::StringValue>::parse,
-        "filter" => |_, _, _| {},
+        "target_relationship" => Field::<TargetRelationshipRelation, value::StringValue>::parse,
+        "filter" => Field::<StepFilterRelation, V::ConditionDefinition>::parse,
         "activities" => List::<WorkflowActivityRelation, V::WorkflowActivityDefinition>::parse,
-        "on_success" => |_, _, _| {},
-        "on_failure" => |_, _, _| {},
+        "on_success" => StepRefs::<OnSuccessRelation>::parse,
+        "on_failure" => StepRefs::<OnFailureRelation>::parse,
     };
 
     const VALIDATION: &'static [toto_parser::ValidationFieldFn] = &[
@@ -95,6 +102,28 @@ where
     ];
 }
 
+/// Steps to run next, `on_success` or `on_failure`: a step name or a list of
+/// them, looked up among the steps of the same workflow.
+pub struct StepRefs<Then>(PhantomData<Then>);
+
+impl<Then, E, R> RelationParser<E, R> for StepRefs<Then>
+where
+    E: ToscaCompatibleEntity,
+    R: ToscaCompatibleRelation,
+    Then: Default,
+    crate::Relation: From<Then>,
+{
+    fn parse(root: toto_ast::GraphHandle, n: toto_ast::GraphHandle, ast: &mut toto_ast::AST<E, R>) {
+        if toto_yaml::as_string(n, ast).is_some() {
+            DefRef::<crate::WorkflowEntity, crate::WorkflowStepEntity, Then>::parse(root, n, ast);
+        } else {
+            ListRelator::<DefRef<crate::WorkflowEntity, crate::WorkflowStepEntity, Then>>::parse(
+                root, n, ast,
+            );
+        }
+    }
+}
+
 impl<E, R, V> toto_parser::Schema<E, R> for WorkflowDelegateActivityDefinition<V>
 where
     E: ToscaCompatibleEntity,
@@ -104,7 +133,7 @@ where
     const SELF: fn() -> E = || crate::Entity::from(crate::WorkflowDelegateActivityEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "workflow" => DefRef::<crate::ServiceTemplateEntity, crate::WorkflowEntity, crate::WorkflowRelation>::parse,
-        "inputs" => Collection::<DefinitionRelation, value::AnyValue>::parse,
+        "inputs" => Collection::<AssignmentRelation, value::ParameterAssignment>::parse,
     };
 
     const VALIDATION: &'static [toto_parser::ValidationFieldFn] =
@@ -120,7 +149,7 @@ where
     const SELF: fn() -> E = || crate::Entity::from(crate::WorkflowInlineActivityEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
         "workflow" => DefRef::<crate::ServiceTemplateEntity, crate::WorkflowEntity, crate::WorkflowRelation>::parse,
-        "inputs" => Collection::<DefinitionRelation, value::AnyValue>::parse,
+        "inputs" => Collection::<AssignmentRelation, value::ParameterAssignment>::parse,
     };
 
     const VALIDATION: &'static [toto_parser::ValidationFieldFn] =
@@ -136,8 +165,8 @@ where
     const SELF: fn() -> E =
         || crate::Entity::from(crate::WorkflowCallOperationActivityEntity).into();
     const SCHEMA: toto_parser::StaticSchemaMap<E, R> = phf::phf_map! {
-        "operation" => Field::<DescriptionRelation, value::StringValue>::parse,
-        "inputs" => Collection::<DefinitionRelation, value::AnyValue>::parse,
+        "operation" => Field::<OperationRelation, value::StringValue>::parse,
+        "inputs" => Collection::<AssignmentRelation, value::ParameterAssignment>::parse,
     };
 
     const VALIDATION: &'static [toto_parser::ValidationFieldFn] =
@@ -304,7 +333,7 @@ where
                 ast.add_edge(
                     activity,
                     n,
-                    crate::Relation::from(crate::DefinitionRelation::default()).into(),
+                    crate::Relation::from(crate::NodeStateRelation).into(),
                 );
                 activity
             })
@@ -367,18 +396,18 @@ where
                     };
 
                     if activity.is_none() {
-                        add_with_loc(ParseError::Custom("unknown activity kind".to_string()), k, ast);
+                        add_with_loc(codes::UNKNOWN_ACTIVITY.with("unknown activity kind"), k, ast);
                     }
 
                     return activity;
 
                 } else {
-                    add_with_loc(ParseError::Custom("expected a key".to_string()), n, ast);
+                    add_with_loc(codes::SINGLE_KEY_MAP.with("expected a key"), n, ast);
                 }
 
                 if items.next().is_some() {
                     add_with_loc(
-                        ParseError::Custom("expected only one key".to_string()),
+                        codes::SINGLE_KEY_MAP.with("expected only one key"),
                         n,
                         ast,
                     );
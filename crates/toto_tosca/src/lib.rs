@@ -1,26 +1,60 @@
+pub mod codes;
+pub mod csar;
+pub mod fetch;
 pub mod grammar;
 pub mod models;
+pub mod profiles;
 pub mod semantic;
 
 use anyhow::Ok;
+use fetch::Fetcher;
 use grammar::{parser::ToscaGrammar, v1_3::Tosca1_3, v2_0::Tosca2_0, ToscaDefinitionsVersion};
 pub use models::*;
 use petgraph::{visit::EdgeRef, Direction};
-use semantic::{Derive, FileStorage, Importer, Lookup};
+use profiles::ProfileRegistry;
+use semantic::{
+    Artifacts, Derive, FileStorage, Filters, Functions, Importer, Lookup, Refinement, Requirements,
+    Status, Substitutions, Templates, Validate, Workflows,
+};
 use toto_parser::{add_with_loc, ParseError};
 
 #[derive(Default)]
 pub struct ToscaParser {
     files: FileStorage,
+    mirrors: Vec<(url::Url, url::Url)>,
+    profiles: ProfileRegistry,
 }
 
 impl ToscaParser {
     pub fn new() -> Self {
         Self {
             files: FileStorage::new(),
+            mirrors: vec![],
+            profiles: ProfileRegistry::default(),
         }
     }
 
+    pub fn with_fetcher(fetcher: impl Fetcher + 'static) -> Self {
+        Self {
+            files: FileStorage::with_fetcher(fetcher),
+            mirrors: vec![],
+            profiles: ProfileRegistry::default(),
+        }
+    }
+
+    /// Loads imports with urls starting with `prefix` from `mirror` instead,
+    /// e.g. a remote repository from its local copy.
+    pub fn mirror(mut self, prefix: url::Url, mirror: url::Url) -> Self {
+        self.mirrors.push((prefix, mirror));
+        self
+    }
+
+    /// Resolves `profile` imports with the given registry.
+    pub fn profiles(mut self, profiles: ProfileRegistry) -> Self {
+        self.profiles = profiles;
+        self
+    }
+
     pub fn get_files(&self) -> impl Iterator<Item = &url::Url> {
         self.files.get_files()
     }
@@ -30,6 +64,21 @@ impl ToscaParser {
         uri: &url::Url,
         ast: &mut toto_ast::AST<E, R>,
     ) -> anyhow::Result<toto_ast::GraphHandle>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        Ok(self.parse_all(std::slice::from_ref(uri), ast)?[0])
+    }
+
+    /// Parses several files into a single AST. Files imported by more than
+    /// one of them, or listed as well as imported, are loaded and checked
+    /// only once.
+    pub fn parse_all<E, R>(
+        &mut self,
+        uris: &[url::Url],
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> anyhow::Result<Vec<toto_ast::GraphHandle>>
     where
         E: ToscaCompatibleEntity,
         R: ToscaCompatibleRelation,
@@ -37,13 +86,31 @@ impl ToscaParser {
         ast.clear();
         self.files.clear();
 
-        let doc_root = self.parse_file(uri, ast)?;
+        let mut doc_roots = vec![];
+        for uri in uris {
+            let doc_root = match self.files.get_file(uri) {
+                Some(doc_root) => doc_root,
+                None => self.parse_file(uri, ast)?,
+            };
+            doc_roots.push(doc_root);
+        }
 
         Importer::import_all_types(ast);
         Lookup::lookup(ast);
+        Templates::copy_all(ast);
         Derive::inherit_all_definitions(ast);
+        Refinement::check_all(ast);
+        Status::check_all(ast);
+        Requirements::resolve_all(ast);
+        Filters::check_all(ast);
+        Substitutions::check_all(ast);
+        Workflows::check_all(ast);
+        Functions::evaluate_all(ast);
+        Validate::validate_all(ast);
+        Templates::check_all(ast);
+        Artifacts::check_all(&mut self.files, ast);
 
-        Ok(doc_root)
+        Ok(doc_roots)
     }
 
     pub fn parse_file<E, R>(
@@ -77,7 +144,7 @@ impl ToscaParser {
             }
             _ => {
                 add_with_loc(
-                    ParseError::Custom("unknown tosca version".to_string()),
+                    codes::UNKNOWN_TOSCA_VERSION.with("unknown tosca version"),
                     tosca_version.0,
                     ast,
                 );
@@ -99,6 +166,33 @@ impl ToscaParser {
         R: ToscaCompatibleRelation,
         V: ToscaDefinitionsVersion<Entity = E, Relation = R>,
     {
+        let (builtin_root, builtin_handle) = self.parse_builtins::<E, R, V>(ast);
+
+        self.parse_file_versioned::<E, R, V>(uri, yaml_root, builtin_root, builtin_handle, ast)
+    }
+
+    /// Adds the builtin types of the version, along with its bundled
+    /// normative types if there are any. Returns the document and the file
+    /// every file of this version imports implicitly.
+    fn parse_builtins<E, R, V>(
+        &mut self,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> (toto_ast::GraphHandle, toto_ast::GraphHandle)
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+        V: ToscaDefinitionsVersion<Entity = E, Relation = R>,
+    {
+        let normative_uri = V::NORMATIVE_TYPES.map(|uri| url::Url::parse(uri).unwrap());
+        if let Some(doc_root) = normative_uri
+            .as_ref()
+            .and_then(|uri| self.files.get_file(uri))
+        {
+            if let Some(file_handle) = Self::find_file(doc_root, ast) {
+                return (doc_root, file_handle);
+            }
+        }
+
         let doc = toto_yaml::FileEntity {
             url: url::Url::parse(format!("builtin://{}", V::NAME).as_str()).unwrap(),
             content: None,
@@ -113,7 +207,42 @@ impl ToscaParser {
 
         V::add_builtins(builtin_handle, ast);
 
-        self.parse_file_versioned::<E, R, V>(uri, yaml_root, builtin_root, builtin_handle, ast)
+        let Some(normative_uri) = normative_uri else {
+            return (builtin_root, builtin_handle);
+        };
+        let doc_root = self
+            .files
+            .add_file(&normative_uri, ast)
+            .expect("normative types are bundled");
+        let yaml_root =
+            toto_yaml::YamlParser::parse(doc_root, ast).expect("normative types are valid yaml");
+        let file_handle = self
+            .parse_file_versioned::<E, R, V>(
+                &normative_uri,
+                yaml_root,
+                builtin_root,
+                builtin_handle,
+                ast,
+            )
+            .expect("normative types are valid tosca");
+        (doc_root, file_handle)
+    }
+
+    /// Service template of a parsed file, if it has one.
+    pub fn get_service_template<E, R>(
+        doc_root: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let file = Self::find_file(doc_root, ast)?;
+        ast.edges_directed(file, Direction::Outgoing)
+            .find_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::ServiceTemplate(_)) => Some(e.target()),
+                _ => None,
+            })
     }
 
     fn find_file<E, R>(
@@ -173,7 +302,9 @@ impl ToscaParser {
             crate::Relation::from(crate::ImportTargetRelation).into(),
         );
 
-        for (import_uri, import_def) in Importer::iter_imports(uri, file_handle, ast) {
+        for (import_uri, import_def) in
+            Importer::iter_imports(uri, file_handle, &self.mirrors, &self.profiles, ast)
+        {
             if let Some(handle) = self.files.get_file(&import_uri) {
                 if let Some(parsed_handle) = Self::find_file(handle, ast) {
                     ast.add_edge(
@@ -193,8 +324,8 @@ impl ToscaParser {
             let doc_root = self.files.add_file(&import_uri, ast);
             if let Err(err) = doc_root {
                 toto_parser::add_with_loc(
-                    toto_parser::ParseError::Custom(err.to_string()),
-                    import_def,
+                    codes::UNRESOLVED_IMPORT.with(err.to_string()),
+                    Importer::get_import_url(import_def, ast).unwrap_or(import_def),
                     ast,
                 );
                 continue;
@@ -217,11 +348,32 @@ impl ToscaParser {
                 continue;
             };
 
+            // TOSCA 2.0 files may use definitions of TOSCA 1.3 files, which
+            // are parsed with their own grammar and builtins.
+            if V::NAME == Tosca2_0::<E, R>::NAME && tosca_version.1 == Tosca1_3::<E, R>::NAME {
+                let (builtin_root, builtin_handle) =
+                    self.parse_builtins::<E, R, Tosca1_3<E, R>>(ast);
+                if let Some(target_handle) = self.parse_file_versioned::<E, R, Tosca1_3<E, R>>(
+                    &import_uri,
+                    yaml_root,
+                    builtin_root,
+                    builtin_handle,
+                    ast,
+                ) {
+                    Tosca1_3::report_lossy_fields(target_handle, ast);
+                    ast.add_edge(
+                        file_handle,
+                        target_handle,
+                        crate::Relation::from(crate::ImportFileRelation).into(),
+                    );
+                }
+                continue;
+            }
+
             if tosca_version.1 != V::NAME {
                 toto_parser::add_with_loc(
-                    toto_parser::ParseError::Custom(
-                        "can't import file of different tosca version".to_string(),
-                    ),
+                    codes::TOSCA_VERSION_MISMATCH
+                        .with("can't import file of different tosca version"),
                     import_def,
                     ast,
                 );
@@ -258,8 +410,7 @@ impl ToscaParser {
 
         let err_lines = err.splitn(4, "\n").collect::<Vec<_>>();
         let err_pos = err_lines
-            .iter()
-            .nth(2)
+            .get(2)
             .and_then(|s| {
                 s.split_once(":")
                     .map(|s| (s.0.parse::<u32>().unwrap(), s.1.parse::<u32>().unwrap()))
@@ -270,13 +421,14 @@ impl ToscaParser {
             .unwrap_or_default();
 
         let err_handle = ast.add_node(
-            toto_parser::ParseError::Custom(
-                err_lines
-                    .first()
-                    .map(|e| e.strip_suffix(": ").unwrap_or(e).to_string())
-                    .unwrap_or(err),
-            )
-            .into(),
+            toto_parser::codes::YAML_SYNTAX
+                .with(
+                    err_lines
+                        .first()
+                        .map(|e| e.strip_suffix(": ").unwrap_or(e).to_string())
+                        .unwrap_or(err),
+                )
+                .into(),
         );
         ast.add_edge(
             err_handle,
@@ -4,16 +4,16 @@ use derive_more::{From, Into, TryInto};
 
 use crate::semantic::SimpleLookuper;
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct Version {
-    pub minor: u64,
     pub major: u64,
+    pub minor: u64,
     pub fix: u64,
     pub qualifier: String,
-    pub build: String,
+    pub build: u64,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum UnitSize {
     B,
     KB,
@@ -26,6 +26,31 @@ pub enum UnitSize {
     TiB,
 }
 
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum UnitTime {
+    D,
+    H,
+    M,
+    S,
+    Ms,
+    Us,
+    Ns,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum UnitFrequency {
+    Hz,
+    KHz,
+    MHz,
+    GHz,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum UnitBitrate {
+    Bps(UnitSize),
+    BytesPs(UnitSize),
+}
+
 #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct ImportEntity;
 
@@ -38,6 +63,17 @@ pub struct ServiceTemplateEntity;
 #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct SubstitutionMappingEntity;
 
+/// Entry of a substitution mapping, by what it maps.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum MappingEntity {
+    Property,
+    Attribute,
+    Capability,
+    Requirement,
+    Interface,
+    Operation,
+}
+
 #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct RepositoryEntity;
 
@@ -47,6 +83,15 @@ pub struct NodeEntity;
 #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct DataEntity;
 
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct PropertyEntity;
+
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct AttributeEntity;
+
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct ParameterEntity;
+
 #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct ArtifactEntity;
 
@@ -68,6 +113,9 @@ pub struct RelationshipEntity;
 #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct RequirementEntity;
 
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct FilterEntity;
+
 #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct GroupEntity;
 
@@ -104,6 +152,32 @@ pub struct FunctionEntity;
 #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct FunctionSignatureEntity;
 
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct FunctionCallEntity;
+
+/// Scalar with a unit, the value is normalized to the base unit.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ScalarUnitEntity {
+    Size(ordered_float::OrderedFloat<f64>, UnitSize),
+    Time(ordered_float::OrderedFloat<f64>, UnitTime),
+    Frequency(ordered_float::OrderedFloat<f64>, UnitFrequency),
+    Bitrate(ordered_float::OrderedFloat<f64>, UnitBitrate),
+}
+
+/// Point in time as seconds and nanoseconds since the Unix epoch in UTC.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct TimestampEntity {
+    pub seconds: i64,
+    pub nanos: u32,
+}
+
+/// Integer range, `None` stands for `UNBOUNDED`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct RangeEntity {
+    pub lower: Option<i64>,
+    pub upper: Option<i64>,
+}
+
 #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum StatusEntity {
     #[default]
@@ -113,16 +187,20 @@ pub enum StatusEntity {
     Deprecated,
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, From, TryInto)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, From, TryInto)]
 #[try_into(owned, ref, ref_mut)]
 pub enum Entity {
     Import(ImportEntity),
     File(FileEntity),
     ServiceTemplate(ServiceTemplateEntity),
     SubstitutionMapping(SubstitutionMappingEntity),
+    Mapping(MappingEntity),
     Repository(RepositoryEntity),
     Node(NodeEntity),
     Data(DataEntity),
+    Property(PropertyEntity),
+    Attribute(AttributeEntity),
+    Parameter(ParameterEntity),
     Status(StatusEntity),
     Artifact(ArtifactEntity),
     Capability(CapabilityEntity),
@@ -131,6 +209,7 @@ pub enum Entity {
     Notification(NotificationEntity),
     Relationship(RelationshipEntity),
     Requirement(RequirementEntity),
+    Filter(FilterEntity),
     Group(GroupEntity),
     Policy(PolicyEntity),
     PolicyTrigger(PolicyTriggerEntity),
@@ -145,6 +224,64 @@ pub enum Entity {
 
     Function(FunctionEntity),
     FunctionSignature(FunctionSignatureEntity),
+    FunctionCall(FunctionCallEntity),
+
+    ScalarUnit(ScalarUnitEntity),
+    Version(Version),
+    Timestamp(TimestampEntity),
+    Range(RangeEntity),
+}
+
+impl Entity {
+    /// Name of the kind of the entity as used by the specification.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Import(_) => "import",
+            Self::File(_) => "file",
+            Self::ServiceTemplate(_) => "service template",
+            Self::SubstitutionMapping(_) => "substitution mapping",
+            Self::Mapping(mapping) => match mapping {
+                MappingEntity::Property => "property mapping",
+                MappingEntity::Attribute => "attribute mapping",
+                MappingEntity::Capability => "capability mapping",
+                MappingEntity::Requirement => "requirement mapping",
+                MappingEntity::Interface => "interface mapping",
+                MappingEntity::Operation => "operation mapping",
+            },
+            Self::Repository(_) => "repository",
+            Self::Node(_) => "node",
+            Self::Data(_) => "data",
+            Self::Property(_) => "property",
+            Self::Attribute(_) => "attribute",
+            Self::Parameter(_) => "parameter",
+            Self::Status(_) => "status",
+            Self::Artifact(_) => "artifact",
+            Self::Capability(_) => "capability",
+            Self::Interface(_) => "interface",
+            Self::Operation(_) => "operation",
+            Self::Notification(_) => "notification",
+            Self::Relationship(_) => "relationship",
+            Self::Requirement(_) => "requirement",
+            Self::Filter(_) => "filter",
+            Self::Group(_) => "group",
+            Self::Policy(_) => "policy",
+            Self::PolicyTrigger(_) => "trigger",
+            Self::Implementation(_) => "implementation",
+            Self::Workflow(_) => "workflow",
+            Self::WorkflowStep(_) => "workflow step",
+            Self::WorkflowDelegateActivity(_)
+            | Self::WorkflowInlineActivity(_)
+            | Self::WorkflowSetStateActivity(_)
+            | Self::WorkflowCallOperationActivity(_) => "activity",
+            Self::Function(_) => "function",
+            Self::FunctionSignature(_) => "function signature",
+            Self::FunctionCall(_) => "function call",
+            Self::ScalarUnit(_) => "scalar-unit",
+            Self::Version(_) => "version",
+            Self::Timestamp(_) => "timestamp",
+            Self::Range(_) => "range",
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
@@ -215,6 +352,12 @@ pub struct AssignmentRelation(pub String);
 #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, From)]
 pub struct OrderedAssignmentRelation(pub (String, usize));
 
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, From)]
+pub struct InputRelation(pub String);
+
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, From)]
+pub struct OutputRelation(pub String);
+
 #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, From)]
 pub struct MetadataRelation(pub String);
 
@@ -362,6 +505,45 @@ impl From<usize> for TargetNodeRelation {
 #[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
 pub struct TargetCapabilityRelation;
 
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub struct ResolvedCapabilityRelation;
+
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub struct TargetRelationshipRelation;
+
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub struct MappedInputRelation;
+
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub struct MappedOutputRelation;
+
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub struct MappedNameRelation;
+
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub struct NodeFilterRelation;
+
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub struct CopyRelation;
+
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub struct CountRelation;
+
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub struct CountRangeRelation;
+
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub struct SubstitutionFilterRelation;
+
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, From)]
+pub struct ConditionRelation(pub usize);
+
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, From)]
+pub struct PropertyFilterRelation(pub String);
+
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, From)]
+pub struct CapabilityFilterRelation(pub (String, usize));
+
 #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, From)]
 pub struct WorkflowActivityRelation(pub usize);
 
@@ -383,6 +565,33 @@ impl From<usize> for OperationRelation {
     }
 }
 
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub struct OnSuccessRelation;
+
+impl From<usize> for OnSuccessRelation {
+    fn from(_: usize) -> Self {
+        Self
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub struct OnFailureRelation;
+
+impl From<usize> for OnFailureRelation {
+    fn from(_: usize) -> Self {
+        Self
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub struct PreconditionRelation;
+
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub struct StepFilterRelation;
+
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub struct NodeStateRelation;
+
 #[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
 pub struct PolicyTriggerEventRelation;
 
@@ -395,6 +604,12 @@ pub struct FunctionOptionalArgumentRelation(pub usize);
 #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, From)]
 pub struct FunctionSignatureRelation(pub usize);
 
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub struct FunctionRelation;
+
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub struct EvaluatesToRelation;
+
 #[derive(Debug, PartialEq, Eq, Hash, Clone, From, TryInto)]
 #[try_into(owned, ref, ref_mut)]
 pub enum Relation {
@@ -423,6 +638,8 @@ pub enum Relation {
     OrderedDefinition(OrderedDefinitionRelation),
     Assignment(AssignmentRelation),
     OrderedAssignment(OrderedAssignmentRelation),
+    Input(InputRelation),
+    Output(OutputRelation),
 
     Validation(ValidationRelation),
     Value(ValueRelation),
@@ -459,6 +676,9 @@ pub enum Relation {
     ValidRelationshipType(ValidRelationshipTypeRelation),
 
     Directive(DirectiveRelation),
+    Copy(CopyRelation),
+    Count(CountRelation),
+    CountRange(CountRangeRelation),
 
     MemberNodeTemplate(MemberNodeTemplateRelation),
     MemberNodeType(MemberNodeTypeRelation),
@@ -468,18 +688,36 @@ pub enum Relation {
 
     TargetNode(TargetNodeRelation),
     TargetCapability(TargetCapabilityRelation),
+    ResolvedCapability(ResolvedCapabilityRelation),
+    TargetRelationship(TargetRelationshipRelation),
 
     SubstitutionMapping(SubstitutionMappingRelation),
+    MappedInput(MappedInputRelation),
+    MappedOutput(MappedOutputRelation),
+    MappedName(MappedNameRelation),
+
+    NodeFilter(NodeFilterRelation),
+    SubstitutionFilter(SubstitutionFilterRelation),
+    Condition(ConditionRelation),
+    PropertyFilter(PropertyFilterRelation),
+    CapabilityFilter(CapabilityFilterRelation),
 
     WorkflowActivity(WorkflowActivityRelation),
     Workflow(WorkflowRelation),
     Operation(OperationRelation),
+    OnSuccess(OnSuccessRelation),
+    OnFailure(OnFailureRelation),
+    Precondition(PreconditionRelation),
+    StepFilter(StepFilterRelation),
+    NodeState(NodeStateRelation),
 
     PolicyTriggerEvent(PolicyTriggerEventRelation),
 
     FunctionArgument(FunctionArgumentRelation),
     FunctionOptionalArgument(FunctionOptionalArgumentRelation),
     FunctionSignature(FunctionSignatureRelation),
+    Function(FunctionRelation),
+    EvaluatesTo(EvaluatesToRelation),
 }
 
 pub trait AsToscaEntity {
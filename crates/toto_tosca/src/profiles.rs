@@ -0,0 +1,326 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::anyhow;
+
+use crate::fetch::user_cache_dir;
+
+/// An installed profile: the name declared with the `profile` keyname and
+/// the file declaring it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    pub name: String,
+    pub root: PathBuf,
+}
+
+/// Profiles installed in a search path of directories, used to resolve
+/// `profile` imports.
+///
+/// A profile is either a TOSCA file placed right in one of the directories,
+/// or a directory with the profile files, one of which declares the profile
+/// name. Profiles are installed into the first directory, the ones found
+/// earlier in the search path shadow the later ones of the same name.
+#[derive(Debug, Clone)]
+pub struct ProfileRegistry {
+    dirs: Vec<PathBuf>,
+}
+
+impl Default for ProfileRegistry {
+    /// Installs into `toto/profiles` in the user cache directory and also
+    /// searches the directories listed in `$TOTO_PROFILE_PATH`.
+    fn default() -> Self {
+        let mut registry = Self::new(Self::default_dir());
+        if let Some(path) = env::var_os("TOTO_PROFILE_PATH") {
+            for dir in env::split_paths(&path) {
+                registry = registry.search(dir);
+            }
+        }
+        registry
+    }
+}
+
+impl ProfileRegistry {
+    pub fn new(install_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dirs: vec![install_dir.into()],
+        }
+    }
+
+    /// Searches `dir` after the already added directories.
+    pub fn search(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dirs.push(dir.into());
+        self
+    }
+
+    pub fn default_dir() -> PathBuf {
+        user_cache_dir().join("toto").join("profiles")
+    }
+
+    pub fn install_dir(&self) -> &Path {
+        &self.dirs[0]
+    }
+
+    /// Installed profiles sorted by name.
+    pub fn list(&self) -> Vec<Profile> {
+        let mut profiles = Vec::<Profile>::new();
+        for profile in self.dirs.iter().flat_map(|dir| Self::scan(dir)) {
+            if !profiles.iter().any(|p| p.name == profile.name) {
+                profiles.push(profile);
+            }
+        }
+        profiles.sort_by(|a, b| a.name.cmp(&b.name));
+        profiles
+    }
+
+    pub fn find(&self, name: &str) -> Option<Profile> {
+        self.dirs
+            .iter()
+            .find_map(|dir| Self::scan(dir).into_iter().find(|p| p.name == name))
+    }
+
+    /// Copies a profile file or a directory with the profile files into the
+    /// install directory, replacing the installed profile of the same name.
+    pub fn add(&self, path: &Path) -> anyhow::Result<Profile> {
+        let declared = if path.is_dir() {
+            Self::scan_package(path)
+        } else {
+            Self::declared_profile(path).into_iter().collect()
+        };
+        let name = match declared.as_slice() {
+            [] => return Err(anyhow!("{} doesn't declare a profile", path.display())),
+            [profile] => profile.name.clone(),
+            _ => {
+                return Err(anyhow!(
+                    "{} declares several profiles: {}",
+                    path.display(),
+                    declared
+                        .iter()
+                        .map(|p| p.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ))
+            }
+        };
+
+        if self.installed(&name).is_some() {
+            self.remove(&name)?;
+        }
+
+        let dest = self.install_dir().join(Self::dir_name(&name)?);
+        if path.is_dir() {
+            copy_dir(path, &dest)?;
+        } else {
+            fs::create_dir_all(&dest)?;
+            fs::copy(path, dest.join(path.file_name().unwrap()))?;
+        }
+
+        Self::scan_package(&dest)
+            .into_iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| anyhow!("failed to install profile {}", name))
+    }
+
+    /// Deletes a profile from the install directory.
+    pub fn remove(&self, name: &str) -> anyhow::Result<Profile> {
+        let profile = self.installed(name).ok_or_else(|| {
+            anyhow!(
+                "profile {} is not installed in {}",
+                name,
+                self.install_dir().display()
+            )
+        })?;
+
+        let parent = profile.root.parent().unwrap();
+        if parent == self.install_dir() {
+            fs::remove_file(&profile.root)?;
+        } else {
+            fs::remove_dir_all(parent)?;
+        }
+        Ok(profile)
+    }
+
+    fn installed(&self, name: &str) -> Option<Profile> {
+        Self::scan(self.install_dir())
+            .into_iter()
+            .find(|p| p.name == name)
+    }
+
+    /// Profiles declared by the files of `dir` and of its subdirectories.
+    fn scan(dir: &Path) -> Vec<Profile> {
+        let mut profiles = Self::scan_package(dir);
+        profiles.extend(
+            sorted_entries(dir)
+                .into_iter()
+                .filter(|p| p.is_dir())
+                .flat_map(|p| Self::scan_package(&p)),
+        );
+        profiles
+    }
+
+    /// Profiles declared by the files right in `dir`.
+    fn scan_package(dir: &Path) -> Vec<Profile> {
+        sorted_entries(dir)
+            .into_iter()
+            .filter(|p| p.is_file())
+            .filter_map(|p| Self::declared_profile(&p))
+            .collect()
+    }
+
+    /// Reads the `profile` keyname of a TOSCA file without parsing it.
+    fn declared_profile(path: &Path) -> Option<Profile> {
+        if !path
+            .extension()
+            .is_some_and(|ext| ext == "yaml" || ext == "yml")
+        {
+            return None;
+        }
+        let content = fs::read_to_string(path).ok()?;
+        if !content
+            .lines()
+            .any(|line| line.starts_with("tosca_definitions_version:"))
+        {
+            return None;
+        }
+        content
+            .lines()
+            .find_map(|line| line.strip_prefix("profile:"))
+            .map(|name| name.split(" #").next().unwrap().trim())
+            .map(|name| name.trim_matches(|c| c == '"' || c == '\''))
+            .filter(|name| !name.is_empty())
+            .map(|name| Profile {
+                name: name.to_string(),
+                root: path.to_path_buf(),
+            })
+    }
+
+    /// Directory of the install directory the profile is copied to.
+    fn dir_name(name: &str) -> anyhow::Result<String> {
+        // `.` and `..` would point at the install directory or its parent.
+        if name.chars().all(|c| c == '.') {
+            return Err(anyhow!("{} is not a valid profile name", name));
+        }
+        Ok(name
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' | '_' => c,
+                _ => '_',
+            })
+            .collect())
+    }
+}
+
+fn sorted_entries(dir: &Path) -> Vec<PathBuf> {
+    let mut entries = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    entries.sort();
+    entries
+}
+
+fn copy_dir(from: &Path, to: &Path) -> anyhow::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in sorted_entries(from) {
+        let dest = to.join(entry.file_name().unwrap());
+        if entry.is_dir() {
+            copy_dir(&entry, &dest)?;
+        } else {
+            fs::copy(&entry, dest)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grammar::tests::errors;
+
+    fn fixtures() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/registry")
+    }
+
+    #[test]
+    fn install_profiles() {
+        let installed = tempfile::tempdir().unwrap();
+        let registry = ProfileRegistry::new(installed.path()).search(fixtures());
+
+        let names = |registry: &ProfileRegistry| {
+            registry
+                .list()
+                .into_iter()
+                .map(|p| (p.name, p.root.starts_with(installed.path())))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(&registry),
+            vec![
+                ("org.example.base:1.0".to_string(), false),
+                ("org.example.web:1.0".to_string(), false)
+            ]
+        );
+
+        let web = registry.add(&fixtures().join("web")).unwrap();
+        assert_eq!(
+            web.root,
+            installed.path().join("org.example.web_1.0/profile.yaml")
+        );
+        assert!(installed
+            .path()
+            .join("org.example.web_1.0/types.yaml")
+            .is_file());
+        registry.add(&fixtures().join("base.yaml")).unwrap();
+        assert_eq!(
+            names(&registry),
+            vec![
+                ("org.example.base:1.0".to_string(), true),
+                ("org.example.web:1.0".to_string(), true)
+            ]
+        );
+
+        registry.remove("org.example.web:1.0").unwrap();
+        assert!(registry.remove("org.example.web:1.0").is_err());
+        assert_eq!(
+            registry.find("org.example.web:1.0").unwrap().root,
+            fixtures().join("web/profile.yaml")
+        );
+        assert!(registry.add(&fixtures().join("web/types.yaml")).is_err());
+
+        let dots = tempfile::tempdir().unwrap();
+        let path = dots.path().join("profile.yaml");
+        fs::write(&path, "tosca_definitions_version: tosca_2_0\nprofile: ..\n").unwrap();
+        assert!(registry.add(&path).is_err());
+        assert!(!installed.path().join("..").join("profile.yaml").exists());
+    }
+
+    #[test]
+    fn profile_imports() {
+        let doc_path = url::Url::from_file_path(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../../tests/tosca_2_0_profiles.yaml"),
+        )
+        .unwrap();
+
+        let mut ast = toto_ast::AST::new();
+        crate::ToscaParser::new()
+            .profiles(ProfileRegistry::new(fixtures()))
+            .parse(&doc_path, &mut ast)
+            .unwrap();
+
+        assert_eq!(
+            errors(&ast),
+            vec![
+                (
+                    7,
+                    "profile org.example.missing:1.0 is not installed".to_string()
+                ),
+                (16, "unknown node type: Missing".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,267 @@
+use std::collections::HashSet;
+
+use petgraph::{visit::EdgeRef, Direction::Outgoing};
+use sha2::{Digest, Sha256, Sha512};
+use toto_parser::add_with_loc;
+
+use crate::{codes, ToscaCompatibleEntity, ToscaCompatibleRelation};
+
+use super::{graph, FileStorage, Requirements};
+
+/// Checksum algorithm assumed when an artifact doesn't name one.
+const DEFAULT_CHECKSUM_ALGORITHM: &str = "SHA-256";
+
+/// Mime types of the common artifact file extensions, used to check
+/// artifact types declaring a mime type but no file extensions.
+static MIME_TYPES: phf::Map<&'static str, &'static str> = phf::phf_map! {
+    "sh" => "application/x-sh",
+    "py" => "application/x-python",
+    "yaml" => "application/yaml",
+    "yml" => "application/yaml",
+    "json" => "application/json",
+    "xml" => "application/xml",
+    "txt" => "text/plain",
+    "zip" => "application/zip",
+    "jar" => "application/java-archive",
+    "tar" => "application/x-tar",
+    "gz" => "application/gzip",
+    "tgz" => "application/gzip",
+    "iso" => "application/x-iso9660-image",
+    "qcow2" => "application/x-qemu-disk",
+    "ova" => "application/x-virtualbox-ova",
+};
+
+/// Resolves files of the artifacts against the TOSCA files defining them
+/// and verifies them: local files and files of archives must exist and
+/// match the declared checksum, and every file should have an extension
+/// or a mime type allowed by the artifact type.
+pub struct Artifacts;
+
+impl Artifacts {
+    pub fn check_all<E, R>(files: &mut FileStorage, ast: &mut toto_ast::AST<E, R>)
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let artifacts = ast
+            .node_indices()
+            .filter(|n| {
+                matches!(
+                    ast.node_weight(*n).unwrap().as_tosca(),
+                    Some(crate::Entity::Artifact(_))
+                )
+            })
+            // Files of the artifacts from a repository are resolved against
+            // the repository url, which may be anywhere.
+            .filter(|n| {
+                !ast.edges_directed(*n, Outgoing).any(|e| match e.weight().as_tosca() {
+                    Some(crate::Relation::Ref(r)) => {
+                        matches!(r.lookuper.what, crate::Entity::Repository(_))
+                    }
+                    _ => false,
+                })
+            })
+            .filter_map(|n| {
+                let file =
+                    graph::get_target(n, ast, |rel| matches!(rel, crate::Relation::HasFile(_)))?;
+                Some((n, file))
+            })
+            .collect::<Vec<_>>();
+
+        let mut errors = vec![];
+        for (artifact, file) in artifacts {
+            let (Some(path), Some(url)) = (
+                toto_yaml::as_string(file, ast).map(|s| s.0.clone()),
+                Self::resolve(file, ast),
+            ) else {
+                continue;
+            };
+
+            if let Some(err) = Self::check_file_type(artifact, &path, ast) {
+                errors.push((err, file));
+            }
+
+            // Remote files are not downloaded.
+            if !matches!(url.scheme(), "file" | "csar") {
+                continue;
+            }
+            let Ok(content) = files.read_artifact(&url) else {
+                errors.push((
+                    codes::MISSING_ARTIFACT_FILE.with(format!("artifact file {} not found", path)),
+                    file,
+                ));
+                continue;
+            };
+            if let Some(err) = Self::check_checksum(artifact, &path, &content, ast) {
+                errors.push(err);
+            }
+        }
+
+        for (err, loc) in errors {
+            add_with_loc(err, loc, ast);
+        }
+    }
+
+    /// Url of an artifact file, given the yaml node with its path.
+    pub fn resolve<E, R>(file: toto_ast::GraphHandle, ast: &toto_ast::AST<E, R>) -> Option<url::Url>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let path = toto_yaml::as_string(file, ast)?;
+        let doc = ast
+            .edges(file)
+            .find_map(|e| e.weight().as_file().map(|_| e.target()))?;
+        let doc = ast.node_weight(doc).unwrap().as_file()?;
+        doc.url.join(&path.0).ok()
+    }
+
+    /// Hex digest of the content, if the algorithm is supported.
+    pub fn checksum(algorithm: &str, content: &[u8]) -> Option<String> {
+        match algorithm.to_ascii_uppercase().replace('-', "").as_str() {
+            "SHA256" => Some(format!("{:x}", Sha256::digest(content))),
+            "SHA512" => Some(format!("{:x}", Sha512::digest(content))),
+            "MD5" => Some(format!("{:x}", md5::compute(content))),
+            _ => None,
+        }
+    }
+
+    fn check_checksum<E, R>(
+        artifact: toto_ast::GraphHandle,
+        path: &str,
+        content: &[u8],
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<(toto_parser::ParseError, toto_ast::GraphHandle)>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let checksum = graph::get_target(artifact, ast, |rel| {
+            matches!(rel, crate::Relation::Checksum(_))
+        })?;
+        let expected = toto_yaml::as_string(checksum, ast)?.0.clone();
+
+        let algorithm = graph::get_target(artifact, ast, |rel| {
+            matches!(rel, crate::Relation::ChecksumAlgorithm(_))
+        });
+        let algorithm_name = algorithm
+            .and_then(|n| toto_yaml::as_string(n, ast))
+            .map_or(DEFAULT_CHECKSUM_ALGORITHM.to_string(), |s| s.0.clone());
+
+        let Some(actual) = Self::checksum(&algorithm_name, content) else {
+            return Some((
+                codes::UNKNOWN_CHECKSUM_ALGORITHM.with(format!(
+                    "unknown checksum algorithm {}, expected one of SHA-256, SHA-512, MD5",
+                    algorithm_name
+                )),
+                algorithm.unwrap_or(checksum),
+            ));
+        };
+        if actual.eq_ignore_ascii_case(expected.trim()) {
+            return None;
+        }
+        Some((
+            codes::CHECKSUM_MISMATCH.with(format!(
+                "{} checksum of {} is {}",
+                algorithm_name, path, actual
+            )),
+            checksum,
+        ))
+    }
+
+    /// Checks the file extension against the nearest `file_ext` of the
+    /// artifact type hierarchy, or, if there is none, against its nearest
+    /// `mime_type`.
+    fn check_file_type<E, R>(
+        artifact: toto_ast::GraphHandle,
+        path: &str,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<toto_parser::ParseError>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let t = graph::get_target(artifact, ast, |rel| {
+            matches!(rel, crate::Relation::HasType(_))
+        })?;
+        let name = path.rsplit('/').next().unwrap_or(path);
+        let ext = name.rsplit_once('.').map_or("", |(_, ext)| ext);
+        let type_name = Requirements::get_type_name(t, ast);
+
+        let mut visited = HashSet::new();
+        let mut curr = Some(t);
+        while let Some(t) = curr.filter(|t| visited.insert(*t)) {
+            let file_exts = ast
+                .edges_directed(t, Outgoing)
+                .filter_map(|e| match e.weight().as_tosca() {
+                    Some(crate::Relation::FileExt(_)) => toto_yaml::as_string(e.target(), ast),
+                    _ => None,
+                })
+                .map(|s| s.0.clone())
+                .collect::<Vec<_>>();
+            if !file_exts.is_empty() {
+                if file_exts.iter().any(|e| e.eq_ignore_ascii_case(ext)) {
+                    return None;
+                }
+                return Some(codes::ARTIFACT_FILE_TYPE_MISMATCH.with(format!(
+                    "file {} doesn't have an extension of {} artifacts, expected one of {}",
+                    path,
+                    type_name,
+                    file_exts.join(", ")
+                )));
+            }
+
+            let mime_type =
+                graph::get_target(t, ast, |rel| matches!(rel, crate::Relation::MimeType(_)))
+                    .and_then(|n| toto_yaml::as_string(n, ast));
+            if let Some(mime_type) = mime_type {
+                return match MIME_TYPES.get(ext.to_ascii_lowercase().as_str()) {
+                    Some(actual) if !actual.eq_ignore_ascii_case(&mime_type.0) => {
+                        Some(codes::ARTIFACT_FILE_TYPE_MISMATCH.with(format!(
+                            "file {} of type {} doesn't match {} artifacts of type {}",
+                            path, actual, type_name, mime_type.0
+                        )))
+                    }
+                    _ => None,
+                };
+            }
+
+            curr = graph::get_target(t, ast, |rel| matches!(rel, crate::Relation::DerivedFrom(_)));
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grammar::tests::{errors, parse};
+
+    #[test]
+    fn artifacts() {
+        let ast = parse("../tests/tosca_2_0_artifacts.yaml");
+
+        assert_eq!(
+            errors(&ast),
+            vec![
+                (
+                    34,
+                    "SHA-512 checksum of artifacts/install.sh is 4d9742302f4cab67bea274f128adf7034df9c04578acd1b5c069530e5eca7bc7d227ff7d5572d00be1d148bca57f732ce0ba58210386eb9777547e07f8cdd40e".to_string()
+                ),
+                (
+                    40,
+                    "unknown checksum algorithm CRC32, expected one of SHA-256, SHA-512, MD5"
+                        .to_string()
+                ),
+                (43, "artifact file artifacts/missing.sh not found".to_string()),
+                (
+                    46,
+                    "file artifacts/configure.py doesn't have an extension of Bash artifacts, expected one of sh".to_string()
+                ),
+                (
+                    49,
+                    "file artifacts/install.sh of type application/x-sh doesn't match Image artifacts of type application/x-qemu-disk".to_string()
+                ),
+            ]
+        );
+    }
+}
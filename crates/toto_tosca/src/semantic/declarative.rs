@@ -0,0 +1,680 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+use petgraph::{
+    algo::{
+        tarjan_scc,
+        tred::{dag_to_toposorted_adjacency_list, dag_transitive_reduction_closure},
+    },
+    visit::{EdgeRef, IntoNeighbors},
+    Direction::{Incoming, Outgoing},
+};
+
+use crate::{ToscaCompatibleEntity, ToscaCompatibleRelation};
+
+use super::{graph, Requirements, Templates};
+
+/// An activity of a step of a declarative workflow.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Activity {
+    SetState(&'static str),
+    /// `<interface_name>.<operation_name>`
+    CallOperation(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub name: String,
+    /// Node template the step runs on.
+    pub target: String,
+    /// Requirement of the target node template whose relationship the step
+    /// runs on.
+    pub target_relationship: Option<String>,
+    pub activities: Vec<Activity>,
+    /// Indices of the steps to run next, omitting the ones already implied by
+    /// another transition. A step with several predecessors waits for all of
+    /// them.
+    pub on_success: Vec<usize>,
+}
+
+/// Ordering a relationship imposes on the lifecycle of its nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dependency {
+    /// `HostedOn`: the source is created once the target is started.
+    Host,
+    /// `DependsOn` and `ConnectsTo`: the source is configured once the
+    /// target is started.
+    Depends,
+    None,
+}
+
+/// Normative relationship types of the bundled files, by the url of the
+/// file and their name there.
+const NORMATIVE_RELATIONSHIPS: &[(&str, &str, Dependency)] = &[
+    (
+        "builtin://tosca_2_0/simple_profile.yaml",
+        "HostedOn",
+        Dependency::Host,
+    ),
+    (
+        "builtin://tosca_2_0/simple_profile.yaml",
+        "DependsOn",
+        Dependency::Depends,
+    ),
+    (
+        "builtin://tosca_2_0/simple_profile.yaml",
+        "ConnectsTo",
+        Dependency::Depends,
+    ),
+    (
+        "builtin://tosca_simple_yaml_1_3/normative.yaml",
+        "tosca.relationships.HostedOn",
+        Dependency::Host,
+    ),
+    (
+        "builtin://tosca_simple_yaml_1_3/normative.yaml",
+        "tosca.relationships.DependsOn",
+        Dependency::Depends,
+    ),
+    (
+        "builtin://tosca_simple_yaml_1_3/normative.yaml",
+        "tosca.relationships.ConnectsTo",
+        Dependency::Depends,
+    ),
+];
+
+struct Relationship {
+    source: toto_ast::GraphHandle,
+    target: toto_ast::GraphHandle,
+    requirement: String,
+    /// The requirement in step names, requirements of the same name are told
+    /// apart by their index.
+    label: String,
+    relationship_type: Option<toto_ast::GraphHandle>,
+    dependency: Dependency,
+}
+
+/// The implicit `deploy` and `undeploy` workflows of a service template,
+/// synthesised from its node templates and their resolved requirements.
+///
+/// Each node template goes through `create`, `configure` and `start` on
+/// deploy, `stop` and `delete` on undeploy, calling the operations of its
+/// `Standard` interface and setting its state around them. The operations of
+/// the `Configure` interface of a relationship run around the configuration
+/// of the source node: `pre_configure_*` before it, `post_configure_*` after
+/// it and `add_*` once both nodes are started. `remove_*` run before the
+/// source node is stopped. Only the operations the types define are called.
+///
+/// Templates with the `select` or `substitute` directive aren't deployed by
+/// the service template and have no steps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeclarativeWorkflow {
+    pub name: String,
+    /// Steps in the order they may run, a step comes after all of its
+    /// predecessors.
+    pub steps: Vec<Step>,
+}
+
+impl DeclarativeWorkflow {
+    pub const NAMES: &'static [&'static str] = &["deploy", "undeploy"];
+
+    pub fn new<E, R>(
+        name: &str,
+        service_template: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> anyhow::Result<Self>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        match name {
+            "deploy" => Self::deploy(service_template, ast),
+            "undeploy" => Self::undeploy(service_template, ast),
+            _ => Err(anyhow::anyhow!(
+                "unknown declarative workflow: {}, expected one of {}",
+                name,
+                Self::NAMES.join(", ")
+            )),
+        }
+    }
+
+    pub fn deploy<E, R>(
+        service_template: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> anyhow::Result<Self>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mut builder = Builder::new(ast);
+
+        let templates = Self::get_templates(service_template, ast);
+        let mut phases = HashMap::new();
+        for (name, template) in templates.iter() {
+            let create = builder.add_node_step(name, *template, "create", "creating", "created");
+            let configure =
+                builder.add_node_step(name, *template, "configure", "configuring", "configured");
+            let start = builder.add_node_step(name, *template, "start", "starting", "started");
+            builder.link(create, configure);
+            builder.link(configure, start);
+            phases.insert(*template, (create, configure, start));
+        }
+
+        for relationship in Self::get_relationships(&templates, ast) {
+            let (source_create, source_configure, source_start) = phases[&relationship.source];
+            let (target_create, target_configure, target_start) = phases[&relationship.target];
+
+            for operation in ["pre_configure_source", "pre_configure_target"] {
+                if let Some(hook) = builder.add_relationship_step(&relationship, operation) {
+                    builder.link(source_create, hook);
+                    builder.link(target_create, hook);
+                    builder.link(hook, source_configure);
+                }
+            }
+            for operation in ["post_configure_source", "post_configure_target"] {
+                if let Some(hook) = builder.add_relationship_step(&relationship, operation) {
+                    builder.link(source_configure, hook);
+                    builder.link(target_configure, hook);
+                    builder.link(hook, source_start);
+                }
+            }
+            for operation in ["add_target", "add_source"] {
+                if let Some(hook) = builder.add_relationship_step(&relationship, operation) {
+                    builder.link(source_start, hook);
+                    builder.link(target_start, hook);
+                }
+            }
+
+            match relationship.dependency {
+                Dependency::Host => builder.link(target_start, source_create),
+                Dependency::Depends => builder.link(target_start, source_configure),
+                Dependency::None => {}
+            }
+        }
+
+        builder.build("deploy")
+    }
+
+    pub fn undeploy<E, R>(
+        service_template: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> anyhow::Result<Self>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mut builder = Builder::new(ast);
+
+        let templates = Self::get_templates(service_template, ast);
+        let mut phases = HashMap::new();
+        for (name, template) in templates.iter() {
+            let stop = builder.add_node_step(name, *template, "stop", "stopping", "configured");
+            let delete = builder.add_node_step(name, *template, "delete", "deleting", "deleted");
+            builder.link(stop, delete);
+            phases.insert(*template, (stop, delete));
+        }
+
+        for relationship in Self::get_relationships(&templates, ast) {
+            let (source_stop, source_delete) = phases[&relationship.source];
+            let (target_stop, _) = phases[&relationship.target];
+
+            for operation in ["remove_target", "remove_source"] {
+                if let Some(hook) = builder.add_relationship_step(&relationship, operation) {
+                    builder.link(hook, source_stop);
+                }
+            }
+
+            match relationship.dependency {
+                Dependency::Host => builder.link(source_delete, target_stop),
+                Dependency::Depends => builder.link(source_stop, target_stop),
+                Dependency::None => {}
+            }
+        }
+
+        builder.build("undeploy")
+    }
+
+    /// Node templates of the service template deployed by it, by name.
+    fn get_templates<E, R>(
+        service_template: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Vec<(String, toto_ast::GraphHandle)>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mut templates = ast
+            .edges_directed(service_template, Outgoing)
+            .filter_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::Definition(crate::DefinitionRelation(name)))
+                    if Requirements::is_node_template(e.target(), ast)
+                        && !Templates::is_abstract(e.target(), ast) =>
+                {
+                    Some((name.clone(), e.target()))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        templates.sort();
+        templates
+    }
+
+    /// Fulfilled requirements between the deployed node templates, in the
+    /// order of the templates and of their requirements.
+    fn get_relationships<E, R>(
+        templates: &[(String, toto_ast::GraphHandle)],
+        ast: &toto_ast::AST<E, R>,
+    ) -> Vec<Relationship>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let is_deployed = |n: toto_ast::GraphHandle| templates.iter().any(|(_, t)| *t == n);
+
+        let mut relationships = vec![];
+        for (_, source) in templates {
+            let mut requirements = ast
+                .edges_directed(*source, Outgoing)
+                .filter_map(|e| match e.weight().as_tosca() {
+                    Some(crate::Relation::OrderedAssignment(crate::OrderedAssignmentRelation(
+                        (name, i),
+                    ))) if matches!(
+                        ast.node_weight(e.target()).unwrap().as_tosca(),
+                        Some(crate::Entity::Requirement(_))
+                    ) =>
+                    {
+                        Some((*i, name.clone(), e.target()))
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            requirements.sort();
+
+            for (i, name, requirement) in requirements.iter() {
+                let Some(target) = graph::get_target(*requirement, ast, |rel| {
+                    matches!(rel, crate::Relation::TargetNode(_))
+                })
+                .filter(|t| is_deployed(*t)) else {
+                    continue;
+                };
+                let label = match requirements.iter().filter(|(_, n, _)| n == name).count() {
+                    1 => name.clone(),
+                    _ => format!("{}_{}", name, i),
+                };
+                let relationship_type = Self::get_relationship_type(*requirement, ast);
+                relationships.push(Relationship {
+                    source: *source,
+                    target,
+                    requirement: name.clone(),
+                    label,
+                    relationship_type,
+                    dependency: relationship_type
+                        .map(|t| Self::get_dependency(t, ast))
+                        .unwrap_or(Dependency::None),
+                });
+            }
+        }
+        relationships
+    }
+
+    /// Relationship type of the requirement assignment, or of the nearest
+    /// requirement definition declaring one.
+    fn get_relationship_type<E, R>(
+        requirement: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let relationship_type = |n: toto_ast::GraphHandle| {
+            ast.edges_directed(n, Outgoing)
+                .filter(|e| {
+                    matches!(
+                        ast.node_weight(e.target()).unwrap().as_tosca(),
+                        Some(crate::Entity::Relationship(_))
+                    )
+                })
+                .find_map(|e| {
+                    graph::get_target(e.target(), ast, |rel| {
+                        matches!(rel, crate::Relation::HasType(_))
+                    })
+                })
+        };
+
+        let mut visited = std::collections::HashSet::new();
+        let mut curr = Some(requirement);
+        while let Some(n) = curr.filter(|n| visited.insert(*n)) {
+            if let Some(t) = relationship_type(n) {
+                return Some(t);
+            }
+            curr = graph::get_target(n, ast, |rel| {
+                matches!(
+                    rel,
+                    crate::Relation::DefinedBy(_) | crate::Relation::RefinedFrom(_)
+                )
+            });
+        }
+        None
+    }
+
+    fn get_dependency<E, R>(
+        relationship_type: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Dependency
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        NORMATIVE_RELATIONSHIPS
+            .iter()
+            .find(|(url, name, _)| {
+                Self::get_normative_type(url, name, ast)
+                    .is_some_and(|t| Requirements::is_derived_from(relationship_type, t, ast))
+            })
+            .map(|(_, _, dependency)| *dependency)
+            .unwrap_or(Dependency::None)
+    }
+
+    /// Type of the given name defined by the bundled file, if it was loaded.
+    fn get_normative_type<E, R>(
+        url: &str,
+        name: &str,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let doc_root = ast
+            .node_indices()
+            .find(|n| ast[*n].as_file().is_some_and(|f| f.url.as_str() == url))?;
+        let file = crate::ToscaParser::find_file(doc_root, ast)?;
+        ast.edges_directed(file, Outgoing)
+            .find_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::Type(crate::TypeRelation(type_name)))
+                    if type_name == name =>
+                {
+                    Some(e.target())
+                }
+                _ => None,
+            })
+    }
+}
+
+struct Builder<'a, E, R> {
+    ast: &'a toto_ast::AST<E, R>,
+    graph: petgraph::Graph<Step, ()>,
+}
+
+impl<'a, E, R> Builder<'a, E, R>
+where
+    E: ToscaCompatibleEntity,
+    R: ToscaCompatibleRelation,
+{
+    fn new(ast: &'a toto_ast::AST<E, R>) -> Self {
+        Self {
+            ast,
+            graph: petgraph::Graph::new(),
+        }
+    }
+
+    fn add_node_step(
+        &mut self,
+        name: &str,
+        template: toto_ast::GraphHandle,
+        operation: &str,
+        before: &'static str,
+        after: &'static str,
+    ) -> petgraph::graph::NodeIndex {
+        let node_type = graph::get_target(template, self.ast, |rel| {
+            matches!(rel, crate::Relation::HasType(_))
+        });
+
+        let mut activities = vec![Activity::SetState(before)];
+        if node_type.is_some_and(|t| self.has_operation(t, "Standard", operation)) {
+            activities.push(Activity::CallOperation(format!("Standard.{}", operation)));
+        }
+        activities.push(Activity::SetState(after));
+
+        self.graph.add_node(Step {
+            name: format!("{}_{}", name, operation),
+            target: name.to_string(),
+            target_relationship: None,
+            activities,
+            on_success: vec![],
+        })
+    }
+
+    fn add_relationship_step(
+        &mut self,
+        relationship: &Relationship,
+        operation: &str,
+    ) -> Option<petgraph::graph::NodeIndex> {
+        let relationship_type = relationship.relationship_type?;
+        if !self.has_operation(relationship_type, "Configure", operation) {
+            return None;
+        }
+
+        let source = Requirements::get_name(relationship.source, self.ast);
+        Some(self.graph.add_node(Step {
+            name: format!("{}_{}_{}", source, relationship.label, operation),
+            target: source,
+            target_relationship: Some(relationship.requirement.clone()),
+            activities: vec![Activity::CallOperation(format!("Configure.{}", operation))],
+            on_success: vec![],
+        }))
+    }
+
+    fn has_operation(&self, t: toto_ast::GraphHandle, interface: &str, operation: &str) -> bool {
+        graph::find_definition(t, interface, self.ast, |e| {
+            matches!(e, crate::Entity::Interface(_))
+        })
+        .and_then(|interface| {
+            graph::find_definition(interface, operation, self.ast, |e| {
+                matches!(e, crate::Entity::Operation(_))
+            })
+        })
+        .is_some()
+    }
+
+    fn link(&mut self, from: petgraph::graph::NodeIndex, to: petgraph::graph::NodeIndex) {
+        if !self.graph.contains_edge(from, to) {
+            self.graph.add_edge(from, to, ());
+        }
+    }
+
+    /// Orders the steps, each one after its predecessors and otherwise in
+    /// the order they were added.
+    fn build(self, name: &str) -> anyhow::Result<DeclarativeWorkflow> {
+        let graph = self.graph;
+
+        let mut in_degree = graph
+            .node_indices()
+            .map(|n| graph.edges_directed(n, Incoming).count())
+            .collect::<Vec<_>>();
+        let mut ready = graph
+            .node_indices()
+            .filter(|n| in_degree[n.index()] == 0)
+            .map(Reverse)
+            .collect::<BinaryHeap<_>>();
+        let mut order = vec![];
+        while let Some(Reverse(n)) = ready.pop() {
+            order.push(n);
+            for next in graph.neighbors_directed(n, Outgoing) {
+                in_degree[next.index()] -= 1;
+                if in_degree[next.index()] == 0 {
+                    ready.push(Reverse(next));
+                }
+            }
+        }
+
+        if order.len() < graph.node_count() {
+            let mut templates = tarjan_scc(&graph)
+                .into_iter()
+                .filter(|scc| scc.len() > 1)
+                .flatten()
+                .map(|n| graph[n].target.clone())
+                .collect::<Vec<_>>();
+            templates.sort();
+            templates.dedup();
+            return Err(anyhow::anyhow!(
+                "circular dependency between node templates {}",
+                templates.join(", ")
+            ));
+        }
+
+        // Transitions implied by others are dropped, `on_success` only lists
+        // the steps that may run right after.
+        let (sorted, _) = dag_to_toposorted_adjacency_list::<_, u32>(&graph, &order);
+        let (reduced, _) = dag_transitive_reduction_closure(&sorted);
+        let steps = order
+            .iter()
+            .enumerate()
+            .map(|(i, n)| {
+                let mut on_success = (&reduced)
+                    .neighbors(i as u32)
+                    .map(|next| next as usize)
+                    .collect::<Vec<_>>();
+                on_success.sort();
+                Step {
+                    on_success,
+                    ..graph[*n].clone()
+                }
+            })
+            .collect();
+
+        Ok(DeclarativeWorkflow {
+            name: name.to_string(),
+            steps,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grammar::tests::{errors, parse, Entity, Relation};
+    use crate::AsToscaEntity;
+
+    use super::{Activity, DeclarativeWorkflow};
+
+    fn service_template(ast: &toto_ast::AST<Entity, Relation>) -> toto_ast::GraphHandle {
+        ast.node_indices()
+            .find(|n| matches!(ast[*n].as_tosca(), Some(crate::Entity::ServiceTemplate(_))))
+            .unwrap()
+    }
+
+    fn names(workflow: &DeclarativeWorkflow) -> Vec<&str> {
+        workflow.steps.iter().map(|s| s.name.as_str()).collect()
+    }
+
+    #[test]
+    fn deploy() {
+        let ast = parse("../tests/tosca_2_0_deploy.yaml");
+        assert_eq!(errors(&ast), vec![]);
+
+        let deploy = DeclarativeWorkflow::deploy(service_template(&ast), &ast).unwrap();
+        assert_eq!(
+            names(&deploy),
+            vec![
+                "probe_create",
+                "probe_configure",
+                "probe_start",
+                "server_create",
+                "server_configure",
+                "server_start",
+                "db_create",
+                "web_create",
+                "db_host_pre_configure_source",
+                "db_host_pre_configure_target",
+                "db_configure",
+                "db_host_post_configure_source",
+                "db_host_post_configure_target",
+                "db_start",
+                "db_host_add_target",
+                "db_host_add_source",
+                "probe_watch_1_add_target",
+                "web_host_pre_configure_source",
+                "web_host_pre_configure_target",
+                "web_database_pre_configure_source",
+                "web_database_pre_configure_target",
+                "web_configure",
+                "web_host_post_configure_source",
+                "web_host_post_configure_target",
+                "web_database_post_configure_source",
+                "web_database_post_configure_target",
+                "web_start",
+                "probe_watch_0_add_target",
+                "web_host_add_target",
+                "web_host_add_source",
+                "web_database_add_target",
+                "web_database_add_source",
+            ]
+        );
+        assert_eq!(
+            deploy.steps[6].activities,
+            vec![
+                Activity::SetState("creating"),
+                Activity::CallOperation("Standard.create".to_string()),
+                Activity::SetState("created"),
+            ]
+        );
+        // `web` is configured once the hooks ran, after it was created, and
+        // `db` is started.
+        let web_configure = deploy
+            .steps
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.on_success.contains(&21))
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        assert_eq!(web_configure, vec![13, 17, 18, 19, 20]);
+        assert_eq!(
+            deploy.steps[19].target_relationship.as_deref(),
+            Some("database")
+        );
+        // Repeated requirements keep their name, the index only tells the
+        // steps apart.
+        assert_eq!(
+            deploy.steps[16].target_relationship.as_deref(),
+            Some("watch")
+        );
+
+        let undeploy = DeclarativeWorkflow::undeploy(service_template(&ast), &ast).unwrap();
+        assert_eq!(
+            names(&undeploy),
+            vec![
+                "db_host_remove_target",
+                "db_host_remove_source",
+                "probe_watch_0_remove_target",
+                "probe_watch_1_remove_target",
+                "probe_stop",
+                "probe_delete",
+                "web_host_remove_target",
+                "web_host_remove_source",
+                "web_database_remove_target",
+                "web_database_remove_source",
+                "web_stop",
+                "db_stop",
+                "db_delete",
+                "web_delete",
+                "server_stop",
+                "server_delete",
+            ]
+        );
+    }
+
+    #[test]
+    fn circular_dependency() {
+        let ast = parse("../tests/tosca_2_0_deploy_cycle.yaml");
+
+        assert_eq!(
+            DeclarativeWorkflow::deploy(service_template(&ast), &ast)
+                .unwrap_err()
+                .to_string(),
+            "circular dependency between node templates api, worker"
+        );
+    }
+}
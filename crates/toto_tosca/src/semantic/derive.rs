@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use petgraph::{
     algo::toposort,
@@ -6,9 +6,9 @@ use petgraph::{
     visit::{EdgeFiltered, EdgeRef, NodeFiltered, NodeRef},
     Direction::Outgoing,
 };
-use toto_parser::{add_with_loc, ParseError};
+use toto_parser::add_with_loc;
 
-use crate::{DefinitionRelation, ToscaCompatibleEntity, ToscaCompatibleRelation};
+use crate::{codes, DefinitionRelation, ToscaCompatibleEntity, ToscaCompatibleRelation};
 
 pub struct Derive;
 
@@ -21,7 +21,7 @@ impl Derive {
         let _ = Self::topo_iter_definitions(ast)
             .map_err(|e| {
                 add_with_loc(
-                    ParseError::Custom("circular dependency detected".to_string()),
+                    codes::CIRCULAR_DERIVATION.with("circular dependency detected"),
                     e,
                     ast,
                 );
@@ -83,7 +83,11 @@ impl Derive {
                 Some(crate::Relation::Definition(_)) => Some((
                     (
                         e.weight().as_tosca().unwrap().clone(),
-                        *ast.node_weight(e.target()).unwrap().as_tosca().unwrap(),
+                        ast.node_weight(e.target())
+                            .unwrap()
+                            .as_tosca()
+                            .unwrap()
+                            .clone(),
                     ),
                     e.target(),
                 )),
@@ -100,7 +104,11 @@ impl Derive {
                     Some((
                         (
                             e.weight().as_tosca().unwrap().clone(),
-                            *ast.node_weight(e.target()).unwrap().as_tosca().unwrap(),
+                            ast.node_weight(e.target())
+                                .unwrap()
+                                .as_tosca()
+                                .unwrap()
+                                .clone(),
                         ),
                         e.target(),
                     ))
@@ -110,12 +118,22 @@ impl Derive {
             .collect::<HashMap<(crate::Relation, crate::Entity), toto_ast::GraphHandle>>();
 
         for ((rel, ent), child_def_handle) in child_definitions.iter() {
+            // Fields copied from another node template are shared with it,
+            // and already linked when it was.
+            if ast.edges_directed(*child_def_handle, Outgoing).any(|e| {
+                matches!(
+                    e.weight().as_tosca(),
+                    Some(crate::Relation::RefinedFrom(_) | crate::Relation::DefinedBy(_))
+                )
+            }) {
+                continue;
+            }
             match rel {
                 crate::Relation::Definition(_) => {
-                    let Some(refined_def) = parent_definitions.get(&(rel.clone(), *ent)) else {
-                        return;
+                    let Some(refined_def) = parent_definitions.get(&(rel.clone(), ent.clone()))
+                    else {
+                        continue;
                     };
-                    // TODO: check refinement
                     ast.add_edge(
                         *child_def_handle,
                         *refined_def,
@@ -125,14 +143,18 @@ impl Derive {
                 crate::Relation::Assignment(crate::AssignmentRelation(name)) => {
                     let Some(assigned_def) = parent_definitions.get(&(
                         crate::Relation::from(DefinitionRelation(name.clone())),
-                        *ent,
+                        ent.clone(),
                     )) else {
                         add_with_loc(
-                            toto_parser::ParseError::Custom(format!("unknown {:?}", ent)),
+                            codes::UNKNOWN_ASSIGNMENT.with(format!(
+                                "unknown {}: {}",
+                                ent.kind(),
+                                name
+                            )),
                             *child_def_handle,
                             ast,
                         );
-                        return;
+                        continue;
                     };
 
                     ast.add_edge(
@@ -145,13 +167,118 @@ impl Derive {
             }
         }
 
+        // Requirement definitions are ordered and never copied to the derived
+        // types, a definition refines the nearest one with the same name.
+        let requirements =
+            ast.edges_directed(def_handle, Outgoing)
+                .filter_map(|e| match e.weight().as_tosca() {
+                    Some(crate::Relation::OrderedDefinition(crate::OrderedDefinitionRelation(
+                        (name, _),
+                    ))) if matches!(
+                        ast.node_weight(e.target()).unwrap().as_tosca(),
+                        Some(crate::Entity::Requirement(_))
+                    ) =>
+                    {
+                        Some((name.clone(), e.target()))
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+        for (name, child_def_handle) in requirements {
+            if let Some(refined_def) = Self::find_requirement(parent_handle, &name, ast) {
+                ast.add_edge(
+                    child_def_handle,
+                    refined_def,
+                    crate::Relation::from(crate::RefinedFromRelation).into(),
+                );
+            }
+        }
+
         for ((rel, ent), parent_def_handle) in parent_definitions.iter() {
             if let crate::Relation::Definition(_) = rel {
-                let None = child_definitions.get(&(rel.clone(), *ent)) else {
-                    return;
+                let None = child_definitions.get(&(rel.clone(), ent.clone())) else {
+                    continue;
                 };
                 ast.add_edge(def_handle, *parent_def_handle, rel.clone().into());
             }
         }
     }
+
+    /// Finds the requirement definition in a node type or in the types it is
+    /// derived from.
+    pub(crate) fn find_requirement<E, R>(
+        node_type: toto_ast::GraphHandle,
+        name: &str,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mut visited = HashSet::new();
+        let mut curr = Some(node_type);
+        while let Some(node_type) = curr.filter(|t| visited.insert(*t)) {
+            let definition =
+                ast.edges_directed(node_type, Outgoing)
+                    .find_map(|e| match e.weight().as_tosca() {
+                        Some(crate::Relation::OrderedDefinition(
+                            crate::OrderedDefinitionRelation((def_name, _)),
+                        )) if def_name == name => Some(e.target()),
+                        _ => None,
+                    });
+            if definition.is_some() {
+                return definition;
+            }
+            curr =
+                ast.edges_directed(node_type, Outgoing)
+                    .find_map(|e| match e.weight().as_tosca() {
+                        Some(crate::Relation::DerivedFrom(_)) => Some(e.target()),
+                        _ => None,
+                    });
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::Direction::Incoming;
+
+    use crate::grammar::tests::{Entity, Relation};
+    use crate::{AsToscaRelation, ToscaParser};
+
+    #[test]
+    fn inherit_definitions() {
+        let mut ast = toto_ast::AST::<Entity, Relation>::new();
+
+        let doc_path = "file://".to_string() + env!("CARGO_MANIFEST_DIR");
+        let doc_path = url::Url::parse(&doc_path).unwrap();
+        let doc_path = doc_path.join("../tests/tosca_2_0_derive.yaml").unwrap();
+        ToscaParser::new().parse(&doc_path, &mut ast).unwrap();
+
+        let child = ast
+            .node_indices()
+            .find(|n| {
+                ast.edges_directed(*n, Incoming).any(|e| {
+                    matches!(
+                        e.weight().as_tosca(),
+                        Some(crate::Relation::Type(crate::TypeRelation(name))) if name == "Child"
+                    )
+                })
+            })
+            .unwrap();
+
+        // `Child` adds `d` and refines `b`, the rest is inherited.
+        let mut names = ast
+            .edges(child)
+            .filter_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::Definition(crate::DefinitionRelation(name))) => {
+                    Some(name.as_str())
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec!["a", "b", "c", "d"]);
+    }
 }
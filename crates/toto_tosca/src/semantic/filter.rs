@@ -0,0 +1,430 @@
+use std::collections::HashSet;
+
+use petgraph::{visit::EdgeRef, Direction::Outgoing};
+use toto_parser::{add_with_loc, ParseError};
+
+use crate::{codes, ToscaCompatibleEntity, ToscaCompatibleRelation};
+
+use super::{graph, DataType, Functions, Requirements, Validate, Validation};
+
+/// Node filters, substitution filters and the conditions of workflows. Their
+/// conditions and constraints must be well-formed and may only refer to the
+/// properties and the capabilities of the filtered node type.
+pub struct Filters;
+
+impl Filters {
+    pub fn check_all<E, R>(ast: &mut toto_ast::AST<E, R>)
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let filters = ast
+            .edge_references()
+            .filter(|e| {
+                matches!(
+                    e.weight().as_tosca(),
+                    Some(
+                        crate::Relation::NodeFilter(_)
+                            | crate::Relation::SubstitutionFilter(_)
+                            | crate::Relation::Precondition(_)
+                            | crate::Relation::StepFilter(_)
+                    )
+                )
+            })
+            .map(|e| (e.source(), e.target()))
+            .collect::<Vec<_>>();
+
+        let mut errors = vec![];
+        let mut checked = HashSet::new();
+        for (owner, filter) in filters {
+            // Filters copied to another node template are checked once.
+            if !checked.insert(filter) {
+                continue;
+            }
+            let node_type = Self::get_filtered_type(owner, ast);
+            Self::check(filter, node_type, ast, &mut errors);
+        }
+
+        for (err, loc) in errors {
+            add_with_loc(err, loc, ast);
+        }
+    }
+
+    /// Node type of the nodes a filter applies to: the target node type of a
+    /// requirement, the type of a node template or of a workflow step's
+    /// target, or the substituted node type.
+    pub fn get_filtered_type<E, R>(
+        owner: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        match ast.node_weight(owner).unwrap().as_tosca()? {
+            crate::Entity::Requirement(_) => {
+                let mut visited = HashSet::new();
+                let mut curr = Some(owner);
+                while let Some(requirement) = curr.filter(|r| visited.insert(*r)) {
+                    if let Some(node_type) = graph::get_target(requirement, ast, |rel| {
+                        matches!(rel, crate::Relation::ValidTargetNodeType(_))
+                    }) {
+                        return Some(node_type);
+                    }
+                    curr = graph::get_target(requirement, ast, |rel| {
+                        matches!(
+                            rel,
+                            crate::Relation::DefinedBy(_) | crate::Relation::RefinedFrom(_)
+                        )
+                    });
+                }
+                None
+            }
+            crate::Entity::Node(_) => {
+                graph::get_target(owner, ast, |rel| matches!(rel, crate::Relation::HasType(_)))
+            }
+            crate::Entity::WorkflowStep(_) => {
+                let template = graph::get_target(owner, ast, |rel| {
+                    matches!(rel, crate::Relation::TargetNode(_))
+                })?;
+                graph::get_target(template, ast, |rel| {
+                    matches!(rel, crate::Relation::HasType(_))
+                })
+            }
+            crate::Entity::SubstitutionMapping(_) => graph::get_target(owner, ast, |rel| {
+                matches!(rel, crate::Relation::SubstitutesType(_))
+            }),
+            _ => None,
+        }
+    }
+
+    fn check<E, R>(
+        filter: toto_ast::GraphHandle,
+        node_type: Option<toto_ast::GraphHandle>,
+        ast: &toto_ast::AST<E, R>,
+        errors: &mut Vec<(ParseError, toto_ast::GraphHandle)>,
+    ) where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let type_name = node_type.map(|t| Requirements::get_type_name(t, ast));
+        for e in ast.edges_directed(filter, Outgoing) {
+            match e.weight().as_tosca() {
+                Some(crate::Relation::Condition(_)) => {
+                    Validation::check_condition(e.target(), ast, errors);
+                    if let Some(node_type) = node_type {
+                        Self::check_references(e.target(), node_type, ast, errors);
+                    }
+                }
+                Some(crate::Relation::PropertyFilter(crate::PropertyFilterRelation(name))) => {
+                    let owner = node_type.zip(type_name.as_deref());
+                    Self::check_property(e.target(), name, owner, None, ast, errors);
+                }
+                Some(crate::Relation::CapabilityFilter(crate::CapabilityFilterRelation((
+                    name,
+                    _,
+                )))) => {
+                    let capability =
+                        node_type.and_then(|t| Self::find_capability(t, name, true, ast));
+                    if let (Some(type_name), None) = (&type_name, capability) {
+                        errors.push((
+                            codes::UNKNOWN_FILTER_REFERENCE
+                                .with(format!("{} has no capability {}", type_name, name)),
+                            e.target(),
+                        ));
+                    }
+                    let owner = capability.zip(type_name.as_deref());
+                    for property in ast.edges_directed(e.target(), Outgoing) {
+                        if let Some(crate::Relation::PropertyFilter(
+                            crate::PropertyFilterRelation(property_name),
+                        )) = property.weight().as_tosca()
+                        {
+                            Self::check_property(
+                                property.target(),
+                                property_name,
+                                owner,
+                                Some(name),
+                                ast,
+                                errors,
+                            );
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Checks a TOSCA 1.3 constraint on a property of `owner`, a node type or
+    /// one of its capabilities, given along with the node type name.
+    fn check_property<E, R>(
+        clause: toto_ast::GraphHandle,
+        name: &str,
+        owner: Option<(toto_ast::GraphHandle, &str)>,
+        capability: Option<&str>,
+        ast: &toto_ast::AST<E, R>,
+        errors: &mut Vec<(ParseError, toto_ast::GraphHandle)>,
+    ) where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let data_type = match owner {
+            Some((owner, type_name)) => match Self::find_value(owner, name, false, ast) {
+                Some(property) => Validate::get_data_type(property, ast),
+                None => {
+                    errors.push((
+                        codes::UNKNOWN_FILTER_REFERENCE.with(format!(
+                            "{} has no property {}{}",
+                            type_name,
+                            name,
+                            capability
+                                .map(|c| format!(" in capability {}", c))
+                                .unwrap_or_default()
+                        )),
+                        clause,
+                    ));
+                    DataType::default()
+                }
+            },
+            None => DataType::default(),
+        };
+        Validation::check(clause, &data_type, ast, errors);
+    }
+
+    /// Checks the `SELF` paths of the `$get_property` and `$get_attribute`
+    /// calls of a condition against the filtered node type.
+    fn check_references<E, R>(
+        n: toto_ast::GraphHandle,
+        node_type: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+        errors: &mut Vec<(ParseError, toto_ast::GraphHandle)>,
+    ) where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        if let Some(call) = Functions::get_call(n, ast) {
+            let name = Functions::get_name(call, ast);
+            let args = Functions::get_args(call, ast);
+            if name == "get_property" || name == "get_attribute" {
+                Self::check_path(&args, name == "get_attribute", node_type, ast, errors);
+            }
+            for arg in args {
+                Self::check_references(arg, node_type, ast, errors);
+            }
+        } else if let Some(items) = toto_yaml::as_list(n, ast) {
+            for (_, item) in items {
+                Self::check_references(item, node_type, ast, errors);
+            }
+        } else if let Some(items) = toto_yaml::as_map(n, ast) {
+            for (_, v) in items {
+                Self::check_references(v, node_type, ast, errors);
+            }
+        }
+    }
+
+    fn check_path<E, R>(
+        args: &[toto_ast::GraphHandle],
+        attribute: bool,
+        node_type: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+        errors: &mut Vec<(ParseError, toto_ast::GraphHandle)>,
+    ) where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let segments = args
+            .iter()
+            .map(|arg| toto_yaml::as_string(*arg, ast).map(|s| s.0.as_str()))
+            .collect::<Vec<_>>();
+        let kind = if attribute { "attribute" } else { "property" };
+        let type_name = Requirements::get_type_name(node_type, ast);
+
+        let mut unknown = |what: &str, loc: toto_ast::GraphHandle| {
+            errors.push((
+                codes::UNKNOWN_FILTER_REFERENCE.with(format!("{} has no {}", type_name, what)),
+                loc,
+            ));
+        };
+
+        match segments.as_slice() {
+            [Some("SELF"), Some("CAPABILITY"), Some(capability), rest @ ..] => {
+                let Some(cap) = Self::find_capability(node_type, capability, false, ast) else {
+                    unknown(&format!("capability {}", capability), args[2]);
+                    return;
+                };
+                if let Some(Some(name)) = rest.first() {
+                    if Self::find_value(cap, name, attribute, ast).is_none() {
+                        unknown(
+                            &format!("{} {} in capability {}", kind, name, capability),
+                            args[3],
+                        );
+                    }
+                }
+            }
+            [Some("SELF"), Some("RELATIONSHIP" | "SOURCE" | "TARGET"), ..] => {}
+            [Some("SELF"), Some(name), rest @ ..] => {
+                if Self::find_value(node_type, name, attribute, ast).is_some() {
+                    return;
+                }
+                // TOSCA 1.3 notation: [ SELF, <capability>, <property> ]
+                match (
+                    rest.first(),
+                    Self::find_capability(node_type, name, false, ast),
+                ) {
+                    (Some(Some(property)), Some(cap)) => {
+                        if Self::find_value(cap, property, attribute, ast).is_none() {
+                            unknown(
+                                &format!("{} {} in capability {}", kind, property, name),
+                                args[2],
+                            );
+                        }
+                    }
+                    _ => unknown(&format!("{} {}", kind, name), args[1]),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Finds a capability definition of a node type by its name or, if
+    /// `by_type`, by the name of its type.
+    fn find_capability<E, R>(
+        node_type: toto_ast::GraphHandle,
+        name: &str,
+        by_type: bool,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let capabilities = ast
+            .edges_directed(node_type, Outgoing)
+            .filter(|e| {
+                matches!(
+                    ast.node_weight(e.target()).unwrap().as_tosca(),
+                    Some(crate::Entity::Capability(_))
+                )
+            })
+            .filter_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::Definition(crate::DefinitionRelation(cap_name))) => {
+                    Some((cap_name.clone(), e.target()))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        capabilities
+            .iter()
+            .find(|(cap_name, _)| cap_name == name)
+            .or_else(|| {
+                capabilities.iter().find(|(_, cap)| {
+                    by_type
+                        && Requirements::get_capability_type(*cap, ast)
+                            .is_some_and(|t| Requirements::has_name(t, name, ast))
+                })
+            })
+            .map(|(_, cap)| *cap)
+    }
+
+    /// Finds a property (or an attribute) definition of a type or of a
+    /// capability definition.
+    fn find_value<E, R>(
+        owner: toto_ast::GraphHandle,
+        name: &str,
+        attribute: bool,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        ast.edges_directed(owner, Outgoing).find_map(|e| {
+            let is_value = match ast.node_weight(e.target()).unwrap().as_tosca() {
+                Some(crate::Entity::Property(_)) => true,
+                Some(crate::Entity::Attribute(_)) => attribute,
+                _ => false,
+            };
+            match e.weight().as_tosca() {
+                Some(crate::Relation::Definition(crate::DefinitionRelation(def_name)))
+                    if is_value && def_name == name =>
+                {
+                    Some(e.target())
+                }
+                _ => None,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grammar::tests::{errors, parse, Entity, Relation};
+    use crate::{AsToscaEntity, AsToscaRelation};
+
+    fn count_filters(ast: &toto_ast::AST<Entity, Relation>) -> usize {
+        ast.node_weights()
+            .filter(|n| matches!(n.as_tosca(), Some(crate::Entity::Filter(_))))
+            .count()
+    }
+
+    fn count_edges(
+        ast: &toto_ast::AST<Entity, Relation>,
+        pred: fn(&crate::Relation) -> bool,
+    ) -> usize {
+        ast.edge_references()
+            .filter(|e| e.weight().as_tosca().is_some_and(pred))
+            .count()
+    }
+
+    #[test]
+    fn conditions() {
+        let ast = parse("../tests/tosca_2_0_filters.yaml");
+
+        assert_eq!(
+            errors(&ast),
+            vec![
+                (40, "Compute has no property arch".to_string()),
+                (41, "condition must be a boolean function".to_string()),
+                (53, "Compute has no capability storage".to_string()),
+                (
+                    60,
+                    "Compute has no property num_cpu in capability host".to_string()
+                ),
+                (67, "$greater_than expects 2 arguments".to_string()),
+            ]
+        );
+        assert_eq!(count_filters(&ast), 5);
+        assert_eq!(
+            count_edges(&ast, |rel| matches!(rel, crate::Relation::Condition(_))),
+            6
+        );
+    }
+
+    #[test]
+    fn property_filters() {
+        let ast = parse("../tests/tosca_1_3_filters.yaml");
+
+        assert_eq!(
+            errors(&ast),
+            vec![
+                (18, "tosca.nodes.Compute has no property speed".to_string()),
+                (
+                    35,
+                    "max_length is not applicable to scalar-unit.size".to_string()
+                ),
+                (39, "unknown constraint: between".to_string()),
+                (40, "tosca.nodes.Compute has no capability gpu".to_string()),
+            ]
+        );
+        // The node filter, the substitution filter and 3 capability filters.
+        assert_eq!(count_filters(&ast), 5);
+        assert_eq!(
+            count_edges(&ast, |rel| matches!(
+                rel,
+                crate::Relation::PropertyFilter(_)
+            )),
+            7
+        );
+    }
+}
@@ -0,0 +1,1459 @@
+use petgraph::{
+    data::DataMap,
+    visit::EdgeRef,
+    Direction::{Incoming, Outgoing},
+};
+use toto_parser::{add_with_loc, ParseError};
+
+use crate::{codes, ToscaCompatibleEntity, ToscaCompatibleRelation};
+
+type Evaluated = Result<Option<toto_ast::GraphHandle>, (ParseError, toto_ast::GraphHandle)>;
+
+#[derive(Debug, Clone, Copy)]
+enum Number {
+    Int(i64),
+    Float(f64),
+}
+
+impl Number {
+    fn as_f64(self) -> f64 {
+        match self {
+            Self::Int(v) => v as f64,
+            Self::Float(v) => v,
+        }
+    }
+}
+
+pub struct Functions;
+
+impl Functions {
+    pub fn evaluate_all<E, R>(ast: &mut toto_ast::AST<E, R>)
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let calls = ast
+            .node_indices()
+            .filter(|n| {
+                matches!(
+                    ast.node_weight(*n).unwrap().as_tosca(),
+                    Some(crate::Entity::FunctionCall(_))
+                )
+            })
+            .collect::<Vec<_>>();
+
+        for call in calls {
+            // Calls outside of a service template (e.g. in type definitions)
+            // have no entity to be evaluated against, so they are left as is.
+            let Some(this) = Self::get_context(call, ast) else {
+                continue;
+            };
+            if let Err((err, loc)) = Self::evaluate_call(call, this, &mut vec![], ast) {
+                Self::report(err, loc, ast);
+            }
+        }
+    }
+
+    pub fn get_call<E, R>(
+        n: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        ast.edges_directed(n, Incoming)
+            .filter(|e| e.weight().as_parse_loc().is_some())
+            .map(|e| e.source())
+            .find(|source| {
+                matches!(
+                    ast.node_weight(*source).unwrap().as_tosca(),
+                    Some(crate::Entity::FunctionCall(_))
+                )
+            })
+    }
+
+    pub fn get_evaluated<E, R>(
+        call: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        ast.edges_directed(call, Outgoing)
+            .find_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::EvaluatesTo(_)) => Some(e.target()),
+                _ => None,
+            })
+    }
+
+    pub fn get_name<E, R>(call: toto_ast::GraphHandle, ast: &toto_ast::AST<E, R>) -> String
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        toto_yaml::as_map(Self::get_loc(call, ast), ast)
+            .and_then(|mut items| items.next())
+            .and_then(|(k, _)| toto_yaml::as_string(k, ast))
+            .map(|name| name.0.trim_start_matches('$').to_string())
+            .unwrap_or_default()
+    }
+
+    pub fn get_args<E, R>(
+        call: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Vec<toto_ast::GraphHandle>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mut args = ast
+            .edges_directed(call, Outgoing)
+            .filter_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::FunctionArgument(crate::FunctionArgumentRelation(i))) => {
+                    Some((*i, e.target()))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        args.sort_by_key(|(i, _)| *i);
+        args.into_iter().map(|(_, arg)| arg).collect()
+    }
+
+    pub(crate) fn get_loc<E, R>(
+        n: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> toto_ast::GraphHandle
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        ast.edges_directed(n, Outgoing)
+            .find_map(|e| e.weight().as_parse_loc().map(|_| e.target()))
+            .unwrap_or(n)
+    }
+
+    fn get_root<E, R>(
+        n: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        ast.edges_directed(n, Outgoing)
+            .find_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::Root(_)) => Some(e.target()),
+                _ => None,
+            })
+    }
+
+    pub(crate) fn find_ancestor<E, R>(
+        n: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+        pred: fn(&crate::Entity) -> bool,
+    ) -> Option<toto_ast::GraphHandle>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mut curr = n;
+        loop {
+            if ast.node_weight(curr).unwrap().as_tosca().is_some_and(pred) {
+                return Some(curr);
+            }
+            curr = Self::get_root(curr, ast)?;
+        }
+    }
+
+    fn is_template(e: &crate::Entity) -> bool {
+        matches!(
+            e,
+            crate::Entity::Node(_)
+                | crate::Entity::Relationship(_)
+                | crate::Entity::Group(_)
+                | crate::Entity::Policy(_)
+        )
+    }
+
+    /// Returns the template a call located in a service template refers to as
+    /// `SELF`, or `None` if the call is not located in a service template or
+    /// is a part of a filter.
+    fn get_context<E, R>(
+        call: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<Option<toto_ast::GraphHandle>>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mut this = None;
+        let mut curr = call;
+        loop {
+            curr = Self::get_root(curr, ast)?;
+            match ast.node_weight(curr).unwrap().as_tosca() {
+                Some(crate::Entity::ServiceTemplate(_)) => return Some(this),
+                Some(crate::Entity::File(_)) => return None,
+                // `SELF` of a filter is the node being filtered, not known
+                // until the filter is applied.
+                Some(crate::Entity::Filter(_)) => return None,
+                Some(e) if this.is_none() && Self::is_template(e) => this = Some(curr),
+                _ => {}
+            }
+        }
+    }
+
+    fn report<E, R>(err: ParseError, loc: toto_ast::GraphHandle, ast: &mut toto_ast::AST<E, R>)
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let msg = err.to_string();
+        if ast.edges_directed(loc, Incoming).any(|e| {
+            e.weight().as_parse_loc().is_some()
+                && ast
+                    .node_weight(e.source())
+                    .unwrap()
+                    .as_parse()
+                    .is_some_and(|existing| existing.to_string() == msg)
+        }) {
+            return;
+        }
+        add_with_loc(err, loc, ast);
+    }
+
+    fn evaluate_call<E, R>(
+        call: toto_ast::GraphHandle,
+        this: Option<toto_ast::GraphHandle>,
+        stack: &mut Vec<toto_ast::GraphHandle>,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Evaluated
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        if let Some(value) = Self::get_evaluated(call, ast) {
+            return Ok(Some(value));
+        }
+
+        let loc = Self::get_loc(call, ast);
+        if stack.contains(&call) {
+            return Err((
+                codes::CIRCULAR_FUNCTION_CALL.with("circular function call detected"),
+                loc,
+            ));
+        }
+
+        let own_context = Self::get_context(call, ast);
+        let this = own_context.unwrap_or(this);
+
+        stack.push(call);
+        let result = Self::dispatch(call, loc, this, stack, ast);
+        stack.pop();
+
+        if let (Ok(Some(value)), Some(_)) = (&result, own_context) {
+            ast.add_edge(
+                call,
+                *value,
+                crate::Relation::EvaluatesTo(crate::EvaluatesToRelation).into(),
+            );
+        }
+
+        result
+    }
+
+    fn dispatch<E, R>(
+        call: toto_ast::GraphHandle,
+        loc: toto_ast::GraphHandle,
+        this: Option<toto_ast::GraphHandle>,
+        stack: &mut Vec<toto_ast::GraphHandle>,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Evaluated
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let name = Self::get_name(call, ast);
+        let args = Self::get_args(call, ast);
+
+        match name.as_str() {
+            "get_input" => Self::get_input(call, loc, &args, this, stack, ast),
+            "get_property" => Self::get_property(call, loc, &args, false, this, stack, ast),
+            "get_attribute" => Self::get_property(call, loc, &args, true, this, stack, ast),
+            "get_artifact" => Self::get_artifact(call, loc, &args, this, stack, ast),
+            "concat" => Self::concat(loc, &args, this, stack, ast),
+            "join" => Self::join(loc, &args, this, stack, ast),
+            "token" => Self::token(loc, &args, this, stack, ast),
+            "length" => Self::length(loc, &args, this, stack, ast),
+            "union" | "intersection" => {
+                Self::set_operation(loc, &args, name == "union", this, stack, ast)
+            }
+            "sum" | "difference" | "product" | "quotient" | "remainder" | "round" | "floor"
+            | "ceil" => Self::arithmetic(&name, loc, &args, this, stack, ast),
+            // The rest is either evaluated at runtime or is a custom function.
+            _ => Ok(None),
+        }
+    }
+
+    pub(crate) fn resolve<E, R>(
+        n: toto_ast::GraphHandle,
+        this: Option<toto_ast::GraphHandle>,
+        stack: &mut Vec<toto_ast::GraphHandle>,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Evaluated
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        match Self::get_call(n, ast) {
+            Some(call) => Self::evaluate_call(call, this, stack, ast),
+            None => Ok(Some(n)),
+        }
+    }
+
+    fn resolve_all<E, R>(
+        args: &[toto_ast::GraphHandle],
+        this: Option<toto_ast::GraphHandle>,
+        stack: &mut Vec<toto_ast::GraphHandle>,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Result<Option<Vec<toto_ast::GraphHandle>>, (ParseError, toto_ast::GraphHandle)>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mut resolved = Vec::with_capacity(args.len());
+        for arg in args {
+            match Self::resolve(*arg, this, stack, ast)? {
+                Some(value) => resolved.push(value),
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(resolved))
+    }
+
+    fn resolve_string<E, R>(
+        n: toto_ast::GraphHandle,
+        this: Option<toto_ast::GraphHandle>,
+        stack: &mut Vec<toto_ast::GraphHandle>,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Result<Option<String>, (ParseError, toto_ast::GraphHandle)>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let Some(value) = Self::resolve(n, this, stack, ast)? else {
+            return Ok(None);
+        };
+        toto_yaml::as_string(value, ast)
+            .map(|s| Some(s.0.clone()))
+            .ok_or((ParseError::UnexpectedType("string"), n))
+    }
+
+    fn expect_args(
+        loc: toto_ast::GraphHandle,
+        args: &[toto_ast::GraphHandle],
+        min: usize,
+        max: Option<usize>,
+    ) -> Result<(), (ParseError, toto_ast::GraphHandle)> {
+        if args.len() < min || max.is_some_and(|max| args.len() > max) {
+            let expected = match max {
+                Some(max) if max == min => format!("{}", min),
+                Some(max) => format!("{} to {}", min, max),
+                None => format!("at least {}", min),
+            };
+            return Err((
+                codes::ARGUMENT_COUNT.with(format!(
+                    "expected {} arguments, got {}",
+                    expected,
+                    args.len()
+                )),
+                loc,
+            ));
+        }
+        Ok(())
+    }
+
+    fn get_input<E, R>(
+        call: toto_ast::GraphHandle,
+        loc: toto_ast::GraphHandle,
+        args: &[toto_ast::GraphHandle],
+        this: Option<toto_ast::GraphHandle>,
+        stack: &mut Vec<toto_ast::GraphHandle>,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Evaluated
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        Self::expect_args(loc, args, 1, None)?;
+        let Some(name) = Self::resolve_string(args[0], this, stack, ast)? else {
+            return Ok(None);
+        };
+
+        let is_service_template =
+            |e: &crate::Entity| matches!(e, crate::Entity::ServiceTemplate(_));
+        let Some(service_template) = Self::find_ancestor(call, ast, is_service_template)
+            .or_else(|| this.and_then(|this| Self::find_ancestor(this, ast, is_service_template)))
+        else {
+            return Err((
+                codes::UNAVAILABLE_CONTEXT.with("inputs are only available in a service template"),
+                loc,
+            ));
+        };
+
+        let input_rel = crate::Relation::Input(crate::InputRelation(name.clone()));
+        let Some(input) = ast
+            .edges_directed(service_template, Outgoing)
+            .find(|e| e.weight().as_tosca() == Some(&input_rel))
+            .map(|e| e.target())
+        else {
+            return Err((
+                codes::UNKNOWN_REFERENCE.with(format!("unknown input: {}", name)),
+                args[0],
+            ));
+        };
+
+        // Inputs without a value or default are provided at deployment time.
+        let Some(value) = Self::get_definition_value(input, ast) else {
+            return Ok(None);
+        };
+
+        Self::select(value, &args[1..], this, stack, ast)
+    }
+
+    fn get_property<E, R>(
+        call: toto_ast::GraphHandle,
+        loc: toto_ast::GraphHandle,
+        args: &[toto_ast::GraphHandle],
+        attribute: bool,
+        this: Option<toto_ast::GraphHandle>,
+        stack: &mut Vec<toto_ast::GraphHandle>,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Evaluated
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        Self::expect_args(loc, args, 2, None)?;
+        let Some((entity, pos)) = Self::resolve_path(call, args, attribute, this, stack, ast)?
+        else {
+            return Ok(None);
+        };
+
+        let Some(name_arg) = args.get(pos) else {
+            return Err((
+                codes::MISSING_REFERENCE_NAME.with(
+                    if attribute {
+                        "expected attribute name"
+                    } else {
+                        "expected property name"
+                    }
+                    .to_string(),
+                ),
+                loc,
+            ));
+        };
+        let Some(name) = Self::resolve_string(*name_arg, this, stack, ast)? else {
+            return Ok(None);
+        };
+
+        let Some(value) = Self::find_value(entity, &name, attribute, ast) else {
+            return Err((
+                codes::UNKNOWN_REFERENCE.with(format!(
+                    "unknown {}: {}",
+                    if attribute { "attribute" } else { "property" },
+                    name
+                )),
+                *name_arg,
+            ));
+        };
+        // Attributes without a default are only known at runtime.
+        let Some(value) = value else {
+            return Ok(None);
+        };
+
+        let this = Self::find_ancestor(entity, ast, Self::is_template).or(this);
+        Self::select(value, &args[pos + 1..], this, stack, ast)
+    }
+
+    fn get_artifact<E, R>(
+        call: toto_ast::GraphHandle,
+        loc: toto_ast::GraphHandle,
+        args: &[toto_ast::GraphHandle],
+        this: Option<toto_ast::GraphHandle>,
+        stack: &mut Vec<toto_ast::GraphHandle>,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Evaluated
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        Self::expect_args(loc, args, 2, None)?;
+        let Some((entity, pos)) = Self::resolve_path(call, args, false, this, stack, ast)? else {
+            return Ok(None);
+        };
+
+        let Some(name_arg) = args.get(pos) else {
+            return Err((
+                codes::MISSING_REFERENCE_NAME.with("expected artifact name"),
+                loc,
+            ));
+        };
+        let Some(name) = Self::resolve_string(*name_arg, this, stack, ast)? else {
+            return Ok(None);
+        };
+
+        let Some(artifact) = Self::find_member(
+            entity,
+            &name,
+            crate::Entity::Artifact(crate::ArtifactEntity),
+            ast,
+        ) else {
+            return Err((
+                codes::UNKNOWN_REFERENCE.with(format!("unknown artifact: {}", name)),
+                *name_arg,
+            ));
+        };
+
+        Ok(ast
+            .edges_directed(artifact, Outgoing)
+            .find_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::HasFile(_)) => Some(e.target()),
+                _ => None,
+            }))
+    }
+
+    /// Resolves the entity part of a TOSCA path, returning the entity and the
+    /// position of the first argument following it.
+    fn resolve_path<E, R>(
+        call: toto_ast::GraphHandle,
+        args: &[toto_ast::GraphHandle],
+        attribute: bool,
+        this: Option<toto_ast::GraphHandle>,
+        stack: &mut Vec<toto_ast::GraphHandle>,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Result<Option<(toto_ast::GraphHandle, usize)>, (ParseError, toto_ast::GraphHandle)>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let Some(first) = Self::resolve_string(args[0], this, stack, ast)? else {
+            return Ok(None);
+        };
+
+        let mut entity = match first.as_str() {
+            "SELF" => this.ok_or((
+                codes::UNAVAILABLE_CONTEXT.with("SELF is not available in this context"),
+                args[0],
+            ))?,
+            "SOURCE" | "TARGET" => {
+                let Some(rel) = this.filter(|this| {
+                    matches!(
+                        ast.node_weight(*this).unwrap().as_tosca(),
+                        Some(crate::Entity::Relationship(_))
+                    )
+                }) else {
+                    return Err((
+                        codes::UNAVAILABLE_CONTEXT.with(format!(
+                            "{} is only available in a relationship context",
+                            first
+                        )),
+                        args[0],
+                    ));
+                };
+                let Some(node) = Self::relationship_end(rel, first == "SOURCE", ast) else {
+                    return Ok(None);
+                };
+                node
+            }
+            node_name => {
+                let is_service_template =
+                    |e: &crate::Entity| matches!(e, crate::Entity::ServiceTemplate(_));
+                let Some(node) = Self::find_ancestor(call, ast, is_service_template)
+                    .or_else(|| {
+                        this.and_then(|this| Self::find_ancestor(this, ast, is_service_template))
+                    })
+                    .and_then(|service_template| {
+                        Self::find_member(
+                            service_template,
+                            node_name,
+                            crate::Entity::Node(crate::NodeEntity),
+                            ast,
+                        )
+                    })
+                else {
+                    return Err((
+                        codes::UNKNOWN_REFERENCE
+                            .with(format!("unknown node template: {}", node_name)),
+                        args[0],
+                    ));
+                };
+                node
+            }
+        };
+
+        let mut pos = 1;
+        while let Some(segment) = args.get(pos) {
+            let Some(segment_value) = Self::resolve(*segment, this, stack, ast)? else {
+                return Ok(None);
+            };
+            let Some(segment_str) = toto_yaml::as_string(segment_value, ast).map(|s| s.0.clone())
+            else {
+                break;
+            };
+
+            match segment_str.as_str() {
+                "CAPABILITY" => {
+                    let Some(name_arg) = args.get(pos + 1) else {
+                        return Err((
+                            codes::MISSING_REFERENCE_NAME.with("expected capability name"),
+                            *segment,
+                        ));
+                    };
+                    let Some(name) = Self::resolve_string(*name_arg, this, stack, ast)? else {
+                        return Ok(None);
+                    };
+                    entity = Self::find_member(
+                        entity,
+                        &name,
+                        crate::Entity::Capability(crate::CapabilityEntity),
+                        ast,
+                    )
+                    .ok_or((
+                        codes::UNKNOWN_REFERENCE.with(format!("unknown capability: {}", name)),
+                        *name_arg,
+                    ))?;
+                    pos += 2;
+                }
+                "RELATIONSHIP" => {
+                    let Some(name_arg) = args.get(pos + 1) else {
+                        return Err((
+                            codes::MISSING_REFERENCE_NAME.with("expected requirement name"),
+                            *segment,
+                        ));
+                    };
+                    let Some(name) = Self::resolve_string(*name_arg, this, stack, ast)? else {
+                        return Ok(None);
+                    };
+                    pos += 2;
+
+                    let mut idx = 0;
+                    if let Some(idx_arg) = args.get(pos) {
+                        if let Some(idx_value) = toto_yaml::as_int(*idx_arg, ast) {
+                            idx = idx_value.0.max(0) as usize;
+                            pos += 1;
+                        }
+                    }
+
+                    let Some(requirement) = Self::find_requirement(entity, &name, idx, ast) else {
+                        return Err((
+                            codes::UNKNOWN_REFERENCE.with(format!("unknown requirement: {}", name)),
+                            *name_arg,
+                        ));
+                    };
+                    entity = ast
+                        .edges_directed(requirement, Outgoing)
+                        .find_map(|e| match e.weight().as_tosca() {
+                            Some(crate::Relation::Assignment(_))
+                                if matches!(
+                                    ast.node_weight(e.target()).unwrap().as_tosca(),
+                                    Some(crate::Entity::Relationship(_))
+                                ) =>
+                            {
+                                Some(e.target())
+                            }
+                            _ => None,
+                        })
+                        .unwrap_or(requirement);
+                }
+                "SOURCE" | "TARGET"
+                    if matches!(
+                        ast.node_weight(entity).unwrap().as_tosca(),
+                        Some(crate::Entity::Relationship(_) | crate::Entity::Requirement(_))
+                    ) =>
+                {
+                    let Some(node) = Self::relationship_end(entity, segment_str == "SOURCE", ast)
+                    else {
+                        return Ok(None);
+                    };
+                    entity = node;
+                    pos += 1;
+                }
+                name if pos + 1 < args.len()
+                    && Self::find_value(entity, name, attribute, ast).is_none() =>
+                {
+                    // TOSCA 1.3 notation: [ SELF, <capability or requirement>, ... ]
+                    if let Some(capability) = Self::find_member(
+                        entity,
+                        name,
+                        crate::Entity::Capability(crate::CapabilityEntity),
+                        ast,
+                    ) {
+                        entity = capability;
+                    } else if let Some(requirement) = Self::find_requirement(entity, name, 0, ast) {
+                        let Some(node) = Self::relationship_end(requirement, false, ast) else {
+                            return Ok(None);
+                        };
+                        entity = node;
+                    } else {
+                        break;
+                    }
+                    pos += 1;
+                }
+                _ => break,
+            }
+        }
+
+        Ok(Some((entity, pos)))
+    }
+
+    fn find_member<E, R>(
+        entity: toto_ast::GraphHandle,
+        name: &str,
+        kind: crate::Entity,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let assignment = crate::Relation::Assignment(crate::AssignmentRelation(name.to_string()));
+        let definition = crate::Relation::Definition(crate::DefinitionRelation(name.to_string()));
+        ast.edges_directed(entity, Outgoing)
+            .find_map(|e| {
+                if e.weight().as_tosca() == Some(&assignment)
+                    && ast.node_weight(e.target()).unwrap().as_tosca() == Some(&kind)
+                {
+                    Some(e.target())
+                } else {
+                    None
+                }
+            })
+            .or_else(|| {
+                ast.edges_directed(entity, Outgoing).find_map(|e| {
+                    if e.weight().as_tosca() == Some(&definition)
+                        && ast.node_weight(e.target()).unwrap().as_tosca() == Some(&kind)
+                    {
+                        Some(e.target())
+                    } else {
+                        None
+                    }
+                })
+            })
+    }
+
+    fn find_requirement<E, R>(
+        node: toto_ast::GraphHandle,
+        name: &str,
+        idx: usize,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mut requirements =
+            ast.edges_directed(node, Outgoing)
+                .filter_map(|e| match e.weight().as_tosca() {
+                    Some(crate::Relation::OrderedAssignment(crate::OrderedAssignmentRelation(
+                        (req_name, i),
+                    ))) if req_name == name => Some((*i, e.target())),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+        requirements.sort_by_key(|(i, _)| *i);
+        requirements.get(idx).map(|(_, req)| *req)
+    }
+
+    /// Returns the source or the target node of a relationship declared in a
+    /// requirement assignment.
+    fn relationship_end<E, R>(
+        relationship: toto_ast::GraphHandle,
+        source: bool,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let is_requirement = |n: toto_ast::GraphHandle| {
+            matches!(
+                ast.node_weight(n).unwrap().as_tosca(),
+                Some(crate::Entity::Requirement(_))
+            )
+        };
+        let requirement = if is_requirement(relationship) {
+            relationship
+        } else {
+            Self::get_root(relationship, ast).filter(|n| is_requirement(*n))?
+        };
+
+        if source {
+            Self::get_root(requirement, ast)
+        } else {
+            ast.edges_directed(requirement, Outgoing)
+                .find_map(|e| match e.weight().as_tosca() {
+                    Some(crate::Relation::TargetNode(_)) => Some(e.target()),
+                    _ => None,
+                })
+        }
+    }
+
+    /// Looks up a property (or an attribute) value of an entity. Returns `None`
+    /// if there is no such property and `Some(None)` if it has no value.
+    fn find_value<E, R>(
+        entity: toto_ast::GraphHandle,
+        name: &str,
+        attribute: bool,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<Option<toto_ast::GraphHandle>>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let kinds: &[crate::Entity] = if attribute {
+            &[
+                crate::Entity::Attribute(crate::AttributeEntity),
+                crate::Entity::Property(crate::PropertyEntity),
+            ]
+        } else {
+            &[crate::Entity::Property(crate::PropertyEntity)]
+        };
+
+        let mut curr = Some(entity);
+        while let Some(entity) = curr {
+            for kind in kinds {
+                if let Some(member) = Self::find_member(entity, name, kind.clone(), ast) {
+                    return Some(Self::get_definition_value(member, ast));
+                }
+            }
+            curr = ast
+                .edges_directed(entity, Outgoing)
+                .find_map(|e| match e.weight().as_tosca() {
+                    Some(crate::Relation::DefinedBy(_)) => Some(e.target()),
+                    _ => None,
+                });
+        }
+        None
+    }
+
+    /// Value of an assignment or a definition, falling back to the default
+    /// value of the definition it refines.
+    pub(crate) fn get_definition_value<E, R>(
+        def: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let find = |rel: fn(&crate::Relation) -> bool| {
+            ast.edges_directed(def, Outgoing)
+                .find(|e| e.weight().as_tosca().is_some_and(rel))
+                .map(|e| e.target())
+        };
+
+        find(|rel| matches!(rel, crate::Relation::Value(_)))
+            .or_else(|| find(|rel| matches!(rel, crate::Relation::Default(_))))
+            .or_else(|| {
+                find(|rel| {
+                    matches!(
+                        rel,
+                        crate::Relation::RefinedFrom(_) | crate::Relation::DefinedBy(_)
+                    )
+                })
+                .and_then(|parent| Self::get_definition_value(parent, ast))
+            })
+    }
+
+    /// Selects a nested value by a path of map keys and list indices.
+    fn select<E, R>(
+        value: toto_ast::GraphHandle,
+        path: &[toto_ast::GraphHandle],
+        this: Option<toto_ast::GraphHandle>,
+        stack: &mut Vec<toto_ast::GraphHandle>,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Evaluated
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mut curr = value;
+        for segment in path {
+            let Some(value) = Self::resolve(curr, this, stack, ast)? else {
+                return Ok(None);
+            };
+            let Some(segment_value) = Self::resolve(*segment, this, stack, ast)? else {
+                return Ok(None);
+            };
+
+            let next = if let Some(idx) = toto_yaml::as_int(segment_value, ast) {
+                let idx = idx.0;
+                toto_yaml::as_list(value, ast)
+                    .and_then(|mut items| items.find(|(i, _)| *i as i64 == idx))
+                    .map(|(_, item)| item)
+            } else if let Some(key) = toto_yaml::as_string(segment_value, ast) {
+                toto_yaml::as_map(value, ast).and_then(|mut items| {
+                    items
+                        .find(|(k, _)| toto_yaml::as_string(*k, ast).is_some_and(|k| k.0 == key.0))
+                        .map(|(_, v)| v)
+                })
+            } else {
+                return Err((ParseError::UnexpectedType("string or integer"), *segment));
+            };
+
+            curr = next.ok_or((
+                codes::NO_SUCH_ELEMENT.with("no such key or index in the value"),
+                *segment,
+            ))?;
+        }
+
+        Self::resolve(curr, this, stack, ast)
+    }
+
+    fn to_string<E, R>(n: toto_ast::GraphHandle, ast: &toto_ast::AST<E, R>) -> Option<String>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        match ast.node_weight(n).unwrap().as_yaml() {
+            Some(toto_yaml::Entity::Str(v)) => Some(v.0.clone()),
+            Some(toto_yaml::Entity::Int(v)) => Some(v.0.to_string()),
+            Some(toto_yaml::Entity::Float(v)) => Some(v.0.to_string()),
+            Some(toto_yaml::Entity::Bool(v)) => Some(v.0.to_string()),
+            _ => None,
+        }
+    }
+
+    fn to_number<E, R>(n: toto_ast::GraphHandle, ast: &toto_ast::AST<E, R>) -> Option<Number>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        match ast.node_weight(n).unwrap().as_yaml() {
+            Some(toto_yaml::Entity::Int(v)) => Some(Number::Int(v.0)),
+            Some(toto_yaml::Entity::Float(v)) => Some(Number::Float(v.0)),
+            _ => None,
+        }
+    }
+
+    fn add_number<E, R>(
+        value: Number,
+        loc: toto_ast::GraphHandle,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> toto_ast::GraphHandle
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        match value {
+            Number::Int(v) => add_with_loc(toto_yaml::Entity::Int(toto_yaml::YamlInt(v)), loc, ast),
+            Number::Float(v) => {
+                add_with_loc(toto_yaml::Entity::Float(toto_yaml::YamlFloat(v)), loc, ast)
+            }
+        }
+    }
+
+    fn add_list<E, R>(
+        items: Vec<toto_ast::GraphHandle>,
+        loc: toto_ast::GraphHandle,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> toto_ast::GraphHandle
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let list = add_with_loc(toto_yaml::Entity::List(toto_yaml::YamlList), loc, ast);
+        for (i, item) in items.into_iter().enumerate() {
+            ast.add_edge(
+                list,
+                item,
+                toto_yaml::Relation::ListValue(toto_yaml::YamlListValue(i)).into(),
+            );
+        }
+        list
+    }
+
+    fn list_items<E, R>(
+        n: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<Vec<toto_ast::GraphHandle>>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        toto_yaml::as_list(n, ast).map(|items| {
+            let mut items = items.collect::<Vec<_>>();
+            items.sort_by_key(|(i, _)| *i);
+            items.into_iter().map(|(_, item)| item).collect()
+        })
+    }
+
+    /// Structural equality of two yaml values.
+    pub(crate) fn yaml_eq<E, R>(
+        a: toto_ast::GraphHandle,
+        b: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> bool
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        if let (Some(a), Some(b)) = (Self::to_number(a, ast), Self::to_number(b, ast)) {
+            return match (a, b) {
+                (Number::Int(a), Number::Int(b)) => a == b,
+                (a, b) => a.as_f64() == b.as_f64(),
+            };
+        }
+
+        match (
+            ast.node_weight(a).unwrap().as_yaml(),
+            ast.node_weight(b).unwrap().as_yaml(),
+        ) {
+            (Some(toto_yaml::Entity::Null(_)), Some(toto_yaml::Entity::Null(_))) => true,
+            (Some(toto_yaml::Entity::Bool(a)), Some(toto_yaml::Entity::Bool(b))) => a.0 == b.0,
+            (Some(toto_yaml::Entity::Str(a)), Some(toto_yaml::Entity::Str(b))) => a.0 == b.0,
+            (Some(toto_yaml::Entity::List(_)), Some(toto_yaml::Entity::List(_))) => {
+                let a = Self::list_items(a, ast).unwrap();
+                let b = Self::list_items(b, ast).unwrap();
+                a.len() == b.len()
+                    && a.iter()
+                        .zip(b.iter())
+                        .all(|(a, b)| Self::yaml_eq(*a, *b, ast))
+            }
+            (Some(toto_yaml::Entity::Map(_)), Some(toto_yaml::Entity::Map(_))) => {
+                let a = toto_yaml::as_map(a, ast).unwrap().collect::<Vec<_>>();
+                let b = toto_yaml::as_map(b, ast).unwrap().collect::<Vec<_>>();
+                a.len() == b.len()
+                    && a.iter().all(|(a_k, a_v)| {
+                        b.iter().any(|(b_k, b_v)| {
+                            Self::yaml_eq(*a_k, *b_k, ast) && Self::yaml_eq(*a_v, *b_v, ast)
+                        })
+                    })
+            }
+            _ => false,
+        }
+    }
+
+    fn concat<E, R>(
+        loc: toto_ast::GraphHandle,
+        args: &[toto_ast::GraphHandle],
+        this: Option<toto_ast::GraphHandle>,
+        stack: &mut Vec<toto_ast::GraphHandle>,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Evaluated
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        Self::expect_args(loc, args, 1, None)?;
+        let Some(values) = Self::resolve_all(args, this, stack, ast)? else {
+            return Ok(None);
+        };
+
+        let mut result = String::new();
+        for (arg, value) in args.iter().zip(values) {
+            result +=
+                &Self::to_string(value, ast).ok_or((ParseError::UnexpectedType("string"), *arg))?;
+        }
+
+        Ok(Some(add_with_loc(
+            toto_yaml::Entity::Str(toto_yaml::YamlString(result)),
+            loc,
+            ast,
+        )))
+    }
+
+    fn join<E, R>(
+        loc: toto_ast::GraphHandle,
+        args: &[toto_ast::GraphHandle],
+        this: Option<toto_ast::GraphHandle>,
+        stack: &mut Vec<toto_ast::GraphHandle>,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Evaluated
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        Self::expect_args(loc, args, 1, Some(2))?;
+        let Some(values) = Self::resolve_all(args, this, stack, ast)? else {
+            return Ok(None);
+        };
+
+        let items = Self::list_items(values[0], ast)
+            .ok_or((ParseError::UnexpectedType("list"), args[0]))?;
+        let delimiter = match values.get(1) {
+            Some(delimiter) => toto_yaml::as_string(*delimiter, ast)
+                .map(|d| d.0.clone())
+                .ok_or((ParseError::UnexpectedType("string"), args[1]))?,
+            None => String::new(),
+        };
+
+        let Some(items) = Self::resolve_all(&items, this, stack, ast)? else {
+            return Ok(None);
+        };
+        let items = items
+            .into_iter()
+            .map(|item| {
+                Self::to_string(item, ast)
+                    .ok_or((ParseError::UnexpectedType("list of strings"), args[0]))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Some(add_with_loc(
+            toto_yaml::Entity::Str(toto_yaml::YamlString(items.join(&delimiter))),
+            loc,
+            ast,
+        )))
+    }
+
+    fn token<E, R>(
+        loc: toto_ast::GraphHandle,
+        args: &[toto_ast::GraphHandle],
+        this: Option<toto_ast::GraphHandle>,
+        stack: &mut Vec<toto_ast::GraphHandle>,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Evaluated
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        Self::expect_args(loc, args, 3, Some(3))?;
+        let Some(values) = Self::resolve_all(args, this, stack, ast)? else {
+            return Ok(None);
+        };
+
+        let string = toto_yaml::as_string(values[0], ast)
+            .map(|s| s.0.clone())
+            .ok_or((ParseError::UnexpectedType("string"), args[0]))?;
+        let separators = toto_yaml::as_string(values[1], ast)
+            .map(|s| s.0.clone())
+            .ok_or((ParseError::UnexpectedType("string"), args[1]))?;
+        let idx = toto_yaml::as_int(values[2], ast)
+            .map(|i| i.0)
+            .ok_or((ParseError::UnexpectedType("integer"), args[2]))?;
+
+        let token = usize::try_from(idx)
+            .ok()
+            .and_then(|idx| string.split(|c| separators.contains(c)).nth(idx))
+            .ok_or((
+                codes::NO_SUCH_ELEMENT.with(format!("no token at index {}", idx)),
+                args[2],
+            ))?;
+
+        Ok(Some(add_with_loc(
+            toto_yaml::Entity::Str(toto_yaml::YamlString(token.to_string())),
+            loc,
+            ast,
+        )))
+    }
+
+    fn length<E, R>(
+        loc: toto_ast::GraphHandle,
+        args: &[toto_ast::GraphHandle],
+        this: Option<toto_ast::GraphHandle>,
+        stack: &mut Vec<toto_ast::GraphHandle>,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Evaluated
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        Self::expect_args(loc, args, 1, Some(1))?;
+        let Some(value) = Self::resolve(args[0], this, stack, ast)? else {
+            return Ok(None);
+        };
+
+        let len = match ast.node_weight(value).unwrap().as_yaml() {
+            Some(toto_yaml::Entity::Str(s)) => s.0.chars().count(),
+            Some(toto_yaml::Entity::List(_)) => toto_yaml::as_list(value, ast).unwrap().count(),
+            Some(toto_yaml::Entity::Map(_)) => toto_yaml::as_map(value, ast).unwrap().count(),
+            _ => return Err((ParseError::UnexpectedType("string, list or map"), args[0])),
+        };
+
+        Ok(Some(Self::add_number(Number::Int(len as i64), loc, ast)))
+    }
+
+    fn set_operation<E, R>(
+        loc: toto_ast::GraphHandle,
+        args: &[toto_ast::GraphHandle],
+        union: bool,
+        this: Option<toto_ast::GraphHandle>,
+        stack: &mut Vec<toto_ast::GraphHandle>,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Evaluated
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        Self::expect_args(loc, args, 1, None)?;
+        let Some(values) = Self::resolve_all(args, this, stack, ast)? else {
+            return Ok(None);
+        };
+
+        let mut lists = Vec::with_capacity(values.len());
+        for (arg, value) in args.iter().zip(values) {
+            let items =
+                Self::list_items(value, ast).ok_or((ParseError::UnexpectedType("list"), *arg))?;
+            let Some(items) = Self::resolve_all(&items, this, stack, ast)? else {
+                return Ok(None);
+            };
+            lists.push(items);
+        }
+
+        let mut result: Vec<toto_ast::GraphHandle> = vec![];
+        let candidates = if union {
+            lists.concat()
+        } else {
+            lists[0].clone()
+        };
+        for item in candidates {
+            if result
+                .iter()
+                .any(|existing| Self::yaml_eq(*existing, item, ast))
+            {
+                continue;
+            }
+            if !union
+                && !lists[1..]
+                    .iter()
+                    .all(|list| list.iter().any(|other| Self::yaml_eq(*other, item, ast)))
+            {
+                continue;
+            }
+            result.push(item);
+        }
+
+        Ok(Some(Self::add_list(result, loc, ast)))
+    }
+
+    fn arithmetic<E, R>(
+        name: &str,
+        loc: toto_ast::GraphHandle,
+        args: &[toto_ast::GraphHandle],
+        this: Option<toto_ast::GraphHandle>,
+        stack: &mut Vec<toto_ast::GraphHandle>,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Evaluated
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        match name {
+            "sum" | "product" => Self::expect_args(loc, args, 2, None)?,
+            "difference" | "quotient" | "remainder" => Self::expect_args(loc, args, 2, Some(2))?,
+            _ => Self::expect_args(loc, args, 1, Some(1))?,
+        }
+
+        let Some(values) = Self::resolve_all(args, this, stack, ast)? else {
+            return Ok(None);
+        };
+        let numbers = args
+            .iter()
+            .zip(values)
+            .map(|(arg, value)| {
+                Self::to_number(value, ast).ok_or((ParseError::UnexpectedType("number"), *arg))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Integer arithmetic reports overflow instead of losing precision.
+        let overflow = || (codes::INTEGER_OVERFLOW.with("integer overflow"), loc);
+        let int_op = |op: fn(i64, i64) -> Option<i64>, float_op: fn(f64, f64) -> f64| {
+            move |a: Number, b: Number| match (a, b) {
+                (Number::Int(a), Number::Int(b)) => op(a, b).map(Number::Int).ok_or_else(overflow),
+                (a, b) => Ok(Number::Float(float_op(a.as_f64(), b.as_f64()))),
+            }
+        };
+
+        let result = match name {
+            "sum" => numbers[1..].iter().try_fold(numbers[0], |a, b| {
+                int_op(i64::checked_add, |a, b| a + b)(a, *b)
+            })?,
+            "product" => numbers[1..].iter().try_fold(numbers[0], |a, b| {
+                int_op(i64::checked_mul, |a, b| a * b)(a, *b)
+            })?,
+            "difference" => int_op(i64::checked_sub, |a, b| a - b)(numbers[0], numbers[1])?,
+            "quotient" => {
+                if numbers[1].as_f64() == 0.0 {
+                    return Err((codes::DIVISION_BY_ZERO.with("division by zero"), args[1]));
+                }
+                Number::Float(numbers[0].as_f64() / numbers[1].as_f64())
+            }
+            "remainder" => match (numbers[0], numbers[1]) {
+                (Number::Int(_), Number::Int(0)) => {
+                    return Err((codes::DIVISION_BY_ZERO.with("division by zero"), args[1]));
+                }
+                // `i64::MIN % -1` is 0, only the intermediate quotient overflows.
+                (Number::Int(a), Number::Int(b)) => Number::Int(a.wrapping_rem(b)),
+                (Number::Float(_), _) => {
+                    return Err((ParseError::UnexpectedType("integer"), args[0]));
+                }
+                (_, Number::Float(_)) => {
+                    return Err((ParseError::UnexpectedType("integer"), args[1]));
+                }
+            },
+            _ => {
+                let value = numbers[0].as_f64();
+                let rounded = match name {
+                    "round" => value.round(),
+                    "floor" => value.floor(),
+                    _ => value.ceil(),
+                };
+                // `as` saturates, so only values inside the range are exact.
+                if !(-(2f64.powi(63))..2f64.powi(63)).contains(&rounded) {
+                    return Err(overflow());
+                }
+                Number::Int(rounded as i64)
+            }
+        };
+
+        Ok(Some(Self::add_number(result, loc, ast)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::{visit::EdgeRef, Direction::Outgoing};
+    use toto_parser::{get_errors, AsParseError};
+
+    use crate::grammar::tests::{parse, Entity, Relation};
+    use crate::{AsToscaEntity, AsToscaRelation};
+
+    use super::Functions;
+
+    fn child(
+        n: toto_ast::GraphHandle,
+        rel: crate::Relation,
+        ast: &toto_ast::AST<Entity, Relation>,
+    ) -> toto_ast::GraphHandle {
+        ast.edges_directed(n, Outgoing)
+            .find(|e| e.weight().as_tosca() == Some(&rel))
+            .map(|e| e.target())
+            .unwrap_or_else(|| panic!("no {:?}", rel))
+    }
+
+    fn render(n: toto_ast::GraphHandle, ast: &toto_ast::AST<Entity, Relation>) -> String {
+        let n = match Functions::get_call(n, ast) {
+            Some(call) => match Functions::get_evaluated(call, ast) {
+                Some(value) => value,
+                None => return "?".to_string(),
+            },
+            None => n,
+        };
+        match ast.node_weight(n).unwrap() {
+            Entity::Yaml(toto_yaml::Entity::Str(v)) => v.0.clone(),
+            Entity::Yaml(toto_yaml::Entity::Int(v)) => v.0.to_string(),
+            Entity::Yaml(toto_yaml::Entity::Float(v)) => v.0.to_string(),
+            Entity::Yaml(toto_yaml::Entity::List(_)) => format!(
+                "[{}]",
+                Functions::list_items(n, ast)
+                    .unwrap()
+                    .into_iter()
+                    .map(|item| render(item, ast))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            other => format!("{:?}", other),
+        }
+    }
+
+    fn service_template(ast: &toto_ast::AST<Entity, Relation>) -> toto_ast::GraphHandle {
+        ast.node_indices()
+            .find(|n| {
+                matches!(
+                    ast.node_weight(*n).unwrap().as_tosca(),
+                    Some(crate::Entity::ServiceTemplate(_))
+                )
+            })
+            .unwrap()
+    }
+
+    fn property(ast: &toto_ast::AST<Entity, Relation>, node: &str, name: &str) -> String {
+        let node = child(
+            service_template(ast),
+            crate::Relation::Definition(crate::DefinitionRelation(node.to_string())),
+            ast,
+        );
+        let assignment = child(
+            node,
+            crate::Relation::Assignment(crate::AssignmentRelation(name.to_string())),
+            ast,
+        );
+        render(
+            child(
+                assignment,
+                crate::Relation::Value(crate::ValueRelation),
+                ast,
+            ),
+            ast,
+        )
+    }
+
+    fn output(ast: &toto_ast::AST<Entity, Relation>, name: &str) -> String {
+        let output = child(
+            service_template(ast),
+            crate::Relation::Output(crate::OutputRelation(name.to_string())),
+            ast,
+        );
+        render(
+            child(output, crate::Relation::Value(crate::ValueRelation), ast),
+            ast,
+        )
+    }
+
+    #[test]
+    fn evaluate() {
+        let ast = parse("../tests/tosca_2_0_functions.yaml");
+
+        let errors = get_errors(&ast)
+            .map(|(err, _)| ast[err].as_parse().unwrap().to_string())
+            .collect::<Vec<_>>();
+        assert!(errors.is_empty(), "{:?}", errors);
+
+        assert_eq!(property(&ast, "front", "name"), "front");
+        assert_eq!(property(&ast, "front", "tags"), "[web, public, edge]");
+        assert_eq!(property(&ast, "front", "state"), "front-ready");
+        assert_eq!(property(&ast, "checks", "name"), "a-b-c");
+        assert_eq!(property(&ast, "checks", "port"), "4");
+        assert_eq!(property(&ast, "checks", "address"), "front:8080");
+        assert_eq!(
+            property(&ast, "checks", "tags"),
+            "[b, http, db:5432, bc, ?]"
+        );
+
+        assert_eq!(output(&ast, "address"), "front:8080");
+        assert_eq!(output(&ast, "remainder"), "0");
+    }
+
+    #[test]
+    fn report_errors() {
+        let ast = parse("../tests/tosca_2_0_functions_errors.yaml");
+
+        let mut errors = get_errors(&ast)
+            .map(|(err, _)| ast[err].as_parse().unwrap().to_string())
+            .collect::<Vec<_>>();
+        errors.sort();
+        assert_eq!(
+            errors
+                .iter()
+                .filter(|err| *err == "integer overflow")
+                .count(),
+            4
+        );
+        errors.dedup();
+
+        assert_eq!(
+            errors,
+            vec![
+                "SELF is not available in this context",
+                "circular function call detected",
+                "division by zero",
+                "integer overflow",
+                "unknown function: undefined",
+                "unknown input: missing",
+                "unknown property: unknown",
+            ]
+        );
+    }
+}
@@ -0,0 +1,50 @@
+//! Graph lookups shared by the semantic passes.
+
+use petgraph::{visit::EdgeRef, Direction::Outgoing};
+
+use crate::{ToscaCompatibleEntity, ToscaCompatibleRelation};
+
+/// Target of the first outgoing relation matching the predicate.
+pub(crate) fn get_target<E, R>(
+    n: toto_ast::GraphHandle,
+    ast: &toto_ast::AST<E, R>,
+    pred: fn(&crate::Relation) -> bool,
+) -> Option<toto_ast::GraphHandle>
+where
+    E: ToscaCompatibleEntity,
+    R: ToscaCompatibleRelation,
+{
+    ast.edges_directed(n, Outgoing)
+        .find_map(|e| match e.weight().as_tosca() {
+            Some(rel) if pred(rel) => Some(e.target()),
+            _ => None,
+        })
+}
+
+/// Definition of the given name and kind. Definitions of a type include the
+/// inherited ones.
+pub(crate) fn find_definition<E, R>(
+    n: toto_ast::GraphHandle,
+    name: &str,
+    ast: &toto_ast::AST<E, R>,
+    pred: fn(&crate::Entity) -> bool,
+) -> Option<toto_ast::GraphHandle>
+where
+    E: ToscaCompatibleEntity,
+    R: ToscaCompatibleRelation,
+{
+    ast.edges_directed(n, Outgoing)
+        .find_map(|e| match e.weight().as_tosca() {
+            Some(crate::Relation::Definition(crate::DefinitionRelation(def_name)))
+                if def_name == name
+                    && ast
+                        .node_weight(e.target())
+                        .unwrap()
+                        .as_tosca()
+                        .is_some_and(pred) =>
+            {
+                Some(e.target())
+            }
+            _ => None,
+        })
+}
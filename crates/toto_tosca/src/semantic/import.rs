@@ -1,24 +1,45 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, path::PathBuf};
 
 use petgraph::{
-    algo::toposort,
+    algo::tarjan_scc,
     data::DataMap,
     visit::{EdgeFiltered, EdgeRef, NodeFiltered, NodeRef},
     Direction::Outgoing,
 };
-use toto_parser::{add_with_loc, ParseError};
+use toto_parser::add_with_loc;
 
-use crate::{ToscaCompatibleEntity, ToscaCompatibleRelation};
+use crate::{
+    codes,
+    csar::Csar,
+    fetch::{DefaultFetcher, Fetcher},
+    profiles::ProfileRegistry,
+    ToscaCompatibleEntity, ToscaCompatibleRelation,
+};
+
+use super::Validate;
 
-#[derive(Default)]
 pub struct FileStorage {
     existing_urls: HashMap<url::Url, toto_ast::GraphHandle>,
+    archives: HashMap<PathBuf, Csar>,
+    fetcher: Box<dyn Fetcher>,
+}
+
+impl Default for FileStorage {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl FileStorage {
     pub fn new() -> Self {
+        Self::with_fetcher(DefaultFetcher::default())
+    }
+
+    pub fn with_fetcher(fetcher: impl Fetcher + 'static) -> Self {
         Self {
             existing_urls: HashMap::new(),
+            archives: HashMap::new(),
+            fetcher: Box::new(fetcher),
         }
     }
 
@@ -35,8 +56,21 @@ impl FileStorage {
             return Ok(file_handle);
         }
 
-        let mut doc = toto_yaml::FileEntity::from_url(uri.clone());
-        doc.fetch()?;
+        let content = match uri.scheme() {
+            "builtin" => crate::grammar::get_builtin_file(uri)
+                .map(String::from)
+                .ok_or_else(|| anyhow::anyhow!("unknown builtin file: {}", uri))?,
+            "csar" => {
+                let content = self.read_archive(uri)?;
+                String::from_utf8(content.to_vec())
+                    .map_err(|_| anyhow::anyhow!("{} is not a text file", uri))?
+            }
+            _ => self.fetcher.fetch(uri)?,
+        };
+        let doc = toto_yaml::FileEntity {
+            url: uri.clone(),
+            content: Some(content),
+        };
 
         let doc_handle = ast.add_node(doc.into());
         self.existing_urls.insert(uri.clone(), doc_handle);
@@ -55,8 +89,35 @@ impl FileStorage {
         self.existing_urls.get(uri).copied()
     }
 
+    /// Reads a local artifact file or a file of an archive, loading the
+    /// archive if needed.
+    pub fn read_artifact(&mut self, uri: &url::Url) -> anyhow::Result<Vec<u8>> {
+        match uri.scheme() {
+            "file" => {
+                let path = uri
+                    .to_file_path()
+                    .map_err(|_| anyhow::anyhow!("invalid file url: {}", uri))?;
+                Ok(std::fs::read(path)?)
+            }
+            "csar" => Ok(self.read_archive(uri)?.to_vec()),
+            scheme => Err(anyhow::anyhow!("unsupported url scheme: {}", scheme)),
+        }
+    }
+
     pub fn clear(&mut self) {
-        self.existing_urls.clear()
+        self.existing_urls.clear();
+        self.archives.clear();
+    }
+
+    fn read_archive(&mut self, uri: &url::Url) -> anyhow::Result<&[u8]> {
+        let (path, entry) = Csar::split_url(uri)
+            .ok_or_else(|| anyhow::anyhow!("{} is outside of the archive", uri))?;
+        if !self.archives.contains_key(&path) {
+            self.archives.insert(path.clone(), Csar::open(&path)?);
+        }
+        self.archives[&path]
+            .read(&entry)
+            .ok_or_else(|| anyhow::anyhow!("{} not found in the archive", entry))
     }
 }
 
@@ -68,52 +129,248 @@ impl Importer {
         E: ToscaCompatibleEntity,
         R: ToscaCompatibleRelation,
     {
-        let _ = Self::topo_iter_imports(ast)
-            .map_err(|e| {
-                add_with_loc(
-                    ParseError::Custom("circular import detected".to_string()),
-                    e,
-                    ast,
-                );
-            })
-            .map(|imports| {
-                imports.for_each(|file_handle| {
-                    Self::import_types(file_handle, ast);
-                });
-            });
+        for files in Self::topo_iter_imports(ast) {
+            if let [file_handle] = files.as_slice() {
+                if !Self::imports_itself(*file_handle, ast) {
+                    Self::import_types(*file_handle, ast);
+                    continue;
+                }
+            }
+            // Types of files importing each other can't be resolved, files
+            // outside of the cycle are still imported.
+            add_with_loc(
+                codes::CIRCULAR_IMPORT.with("circular import detected"),
+                files[0],
+                ast,
+            );
+        }
     }
 
+    /// Resolved urls of the file's imports. Imports from a repository are
+    /// resolved against the repository url, others against the file itself.
+    /// Urls starting with a mirrored prefix are redirected to the mirror.
+    /// Profiles are loaded from the registry.
     pub fn iter_imports<E, R>(
         uri: &url::Url,
         file_handle: toto_ast::GraphHandle,
+        mirrors: &[(url::Url, url::Url)],
+        profiles: &ProfileRegistry,
         ast: &mut toto_ast::AST<E, R>,
     ) -> impl Iterator<Item = (url::Url, toto_ast::GraphHandle)>
     where
         E: ToscaCompatibleEntity,
         R: ToscaCompatibleRelation,
     {
-        ast.edges(file_handle)
+        let repositories = Self::get_repository_urls(uri, file_handle, ast);
+
+        let import_defs = ast
+            .edges(file_handle)
             .filter_map(|e| match e.weight().as_tosca() {
                 Some(crate::Relation::Import(_)) => Some(e.target()),
                 _ => None,
             })
-            .filter_map(|import_def| {
-                ast.edges_directed(import_def, Outgoing)
+            .collect::<Vec<_>>();
+
+        let mut imports = vec![];
+        for import_def in import_defs {
+            if let Some(profile) = Self::get_import_profile(import_def, ast) {
+                imports.extend(Self::resolve_profile(profile, profiles, ast).zip(Some(import_def)));
+                continue;
+            }
+
+            let base = match Self::get_import_repository(import_def, ast) {
+                // Unknown repositories are reported by the lookup.
+                Some(name) => match repositories.get(&name) {
+                    Some(Some(url)) => url,
+                    _ => continue,
+                },
+                None => uri,
+            };
+            imports.extend(
+                Self::get_import_url(import_def, ast)
+                    .and_then(|u| toto_yaml::as_string(u, ast))
+                    .and_then(|u| url::Url::parse(&u.0).or(base.join(&u.0)).ok())
+                    .map(|u| Self::apply_mirrors(u, mirrors))
+                    .zip(Some(import_def)),
+            );
+        }
+        imports.into_iter()
+    }
+
+    /// The `url` value of an import definition.
+    pub fn get_import_url<E, R>(
+        import_def: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        ast.edges_directed(import_def, Outgoing)
+            .find_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::ImportUrl(_)) => Some(e.target()),
+                _ => None,
+            })
+    }
+
+    /// The `profile` value of an import definition.
+    fn get_import_profile<E, R>(
+        import_def: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        ast.edges_directed(import_def, Outgoing)
+            .find_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::ImportProfile(_)) => Some(e.target()),
+                _ => None,
+            })
+    }
+
+    /// Url of the root file of an installed or a bundled profile.
+    fn resolve_profile<E, R>(
+        profile: toto_ast::GraphHandle,
+        profiles: &ProfileRegistry,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> Option<url::Url>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let name = toto_yaml::as_string(profile, ast)?.0.clone();
+        let found = profiles
+            .find(&name)
+            .and_then(|p| std::path::absolute(p.root).ok())
+            .and_then(|root| url::Url::from_file_path(root).ok())
+            .or_else(|| {
+                crate::grammar::BUILTIN_PROFILES
+                    .iter()
+                    .find(|(builtin, _)| *builtin == name)
+                    .map(|(_, url)| url::Url::parse(url).unwrap())
+            });
+        if found.is_none() {
+            add_with_loc(
+                codes::UNKNOWN_PROFILE.with(format!("profile {} is not installed", name)),
+                profile,
+                ast,
+            );
+        }
+        found
+    }
+
+    /// The name of the repository an import definition refers to. Imports
+    /// are resolved before the lookup, so the reference is read directly.
+    fn get_import_repository<E, R>(
+        import_def: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<String>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        ast.edges_directed(import_def, Outgoing)
+            .find_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::Ref(r))
+                    if matches!(r.lookuper.what, crate::Entity::Repository(_)) =>
+                {
+                    Some(e.target())
+                }
+                _ => None,
+            })
+            .and_then(|n| toto_yaml::as_string(n, ast))
+            .map(|n| n.0.clone())
+    }
+
+    /// Urls of the repositories defined in the file by name, `None` for the
+    /// ones with an invalid url. Every url is treated as a directory, and
+    /// relative ones are resolved against the file.
+    fn get_repository_urls<E, R>(
+        uri: &url::Url,
+        file_handle: toto_ast::GraphHandle,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> HashMap<String, Option<url::Url>>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let repositories = ast
+            .edges_directed(file_handle, Outgoing)
+            .filter_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::Definition(crate::DefinitionRelation(name)))
+                    if matches!(
+                        ast.node_weight(e.target()).unwrap().as_tosca(),
+                        Some(crate::Entity::Repository(_))
+                    ) =>
+                {
+                    Some((name.clone(), e.target()))
+                }
+                _ => None,
+            })
+            .filter_map(|(name, repository)| {
+                ast.edges_directed(repository, Outgoing)
                     .find_map(|e| match e.weight().as_tosca() {
-                        Some(crate::Relation::ImportUrl(_)) => Some(e.target()),
+                        Some(crate::Relation::RepositoryUrl(_)) => Some(e.target()),
                         _ => None,
                     })
-                    .and_then(|u| toto_yaml::as_string(u, ast))
-                    .and_then(|u| url::Url::parse(&u.0).or(uri.join(&u.0)).ok())
-                    .zip(Some(import_def))
+                    .map(|url| (name, url))
             })
-            .collect::<Vec<_>>()
+            .collect::<Vec<_>>();
+
+        repositories
             .into_iter()
+            .map(|(name, url_handle)| {
+                let Some(url) = toto_yaml::as_string(url_handle, ast).map(|u| u.0.clone()) else {
+                    return (name, None);
+                };
+                let parsed = match url::Url::parse(&url) {
+                    Err(url::ParseError::RelativeUrlWithoutBase) => uri.join(&url),
+                    parsed => parsed,
+                };
+                match parsed {
+                    Ok(mut parsed) if !parsed.cannot_be_a_base() => {
+                        if !parsed.path().ends_with('/') {
+                            parsed.set_path(&format!("{}/", parsed.path()));
+                        }
+                        (name, Some(parsed))
+                    }
+                    Ok(_) => {
+                        add_with_loc(
+                            codes::INVALID_REPOSITORY_URL
+                                .with(format!("{} can't be used as a repository url", url)),
+                            url_handle,
+                            ast,
+                        );
+                        (name, None)
+                    }
+                    Err(err) => {
+                        add_with_loc(
+                            codes::INVALID_REPOSITORY_URL
+                                .with(format!("invalid repository url {}: {}", url, err)),
+                            url_handle,
+                            ast,
+                        );
+                        (name, None)
+                    }
+                }
+            })
+            .collect()
     }
 
-    fn topo_iter_imports<E, R>(
-        ast: &toto_ast::AST<E, R>,
-    ) -> Result<impl Iterator<Item = toto_ast::GraphHandle>, toto_ast::GraphHandle>
+    fn apply_mirrors(url: url::Url, mirrors: &[(url::Url, url::Url)]) -> url::Url {
+        mirrors
+            .iter()
+            .find_map(|(prefix, mirror)| {
+                url.as_str()
+                    .strip_prefix(prefix.as_str())
+                    .and_then(|rest| mirror.join(rest).ok())
+            })
+            .unwrap_or(url)
+    }
+
+    /// Groups of files importing each other, imported files go first.
+    fn topo_iter_imports<E, R>(ast: &toto_ast::AST<E, R>) -> Vec<Vec<toto_ast::GraphHandle>>
     where
         E: ToscaCompatibleEntity,
         R: ToscaCompatibleRelation,
@@ -128,9 +385,16 @@ impl Importer {
             )
         });
 
-        toposort(&file_graph, None)
-            .map_err(|err| err.node_id())
-            .map(|v| v.into_iter().rev())
+        tarjan_scc(&file_graph)
+    }
+
+    fn imports_itself<E, R>(file_handle: toto_ast::GraphHandle, ast: &toto_ast::AST<E, R>) -> bool
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        ast.edges_connecting(file_handle, file_handle)
+            .any(|e| matches!(e.weight().as_tosca(), Some(crate::Relation::ImportFile(_))))
     }
 
     fn import_types<E, R>(file_handle: toto_ast::GraphHandle, ast: &mut toto_ast::AST<E, R>)
@@ -138,6 +402,7 @@ impl Importer {
         E: ToscaCompatibleEntity,
         R: ToscaCompatibleRelation,
     {
+        let mut collisions = vec![];
         ast.edges(file_handle)
             .filter_map(|e| match e.weight().as_tosca() {
                 Some(crate::Relation::Import(_)) => Some(e.target()),
@@ -160,11 +425,11 @@ impl Importer {
                     .and_then(|u| toto_yaml::as_string(u, ast).cloned())
                     .map(|u| u.0);
 
-                (doc_root, ns)
+                (import_def, doc_root, ns)
             })
             .collect::<Vec<_>>()
             .into_iter()
-            .for_each(|(doc_root, ns)| {
+            .for_each(|(import_def, doc_root, ns)| {
                 let Some(target_file) = ast
                     .edges_directed(doc_root, petgraph::Direction::Incoming)
                     .filter_map(|e| {
@@ -191,7 +456,21 @@ impl Importer {
                             .and_then(|n| match n {
                                 crate::Relation::Type(type_name) => {
                                     Some(crate::Relation::Type(crate::TypeRelation(
-                                        [ns.as_slice(), &[type_name.0.clone()]].concat().join(":"),
+                                        [ns.as_slice(), std::slice::from_ref(&type_name.0)]
+                                            .concat()
+                                            .join(":"),
+                                    )))
+                                }
+                                crate::Relation::Definition(function_name)
+                                    if matches!(
+                                        ast.node_weight(e.target()).unwrap().as_tosca(),
+                                        Some(crate::Entity::Function(_))
+                                    ) =>
+                                {
+                                    Some(crate::Relation::Definition(crate::DefinitionRelation(
+                                        [ns.as_slice(), std::slice::from_ref(&function_name.0)]
+                                            .concat()
+                                            .join(":"),
                                     )))
                                 }
                                 _ => None,
@@ -207,8 +486,155 @@ impl Importer {
                         {
                             return;
                         }
+                        if let crate::Relation::Type(crate::TypeRelation(name)) = &rel {
+                            let kind = ast.node_weight(target_def).unwrap().as_tosca();
+                            let existing = ast
+                                .edges_directed(file_handle, Outgoing)
+                                .filter(|e| e.weight().as_tosca() == Some(&rel))
+                                .map(|e| e.target())
+                                .find(|t| ast.node_weight(*t).unwrap().as_tosca() == kind);
+                            match existing {
+                                // Every file has its own copy of the builtin types.
+                                Some(_) if Validate::get_builtin(target_def, ast).is_some() => {}
+                                Some(_) => {
+                                    collisions.push((
+                                        codes::TYPE_COLLISION.with(format!(
+                                            "imported type {} collides with another type of the same name",
+                                            name
+                                        )),
+                                        import_def,
+                                    ));
+                                    return;
+                                }
+                                None => {}
+                            }
+                        }
                         ast.add_edge(file_handle, target_def, rel.into());
                     });
             });
+
+        for (err, loc) in collisions {
+            add_with_loc(err, loc, ast);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fetch::{DefaultFetcher, HttpFetcher},
+        grammar::tests::{errors, parse, parse_all},
+    };
+
+    #[test]
+    fn circular_import_is_isolated() {
+        let ast = parse_all(&[
+            "../tests/tosca_1_3.yaml",
+            "../tests/tosca_2_0_refinement.yaml",
+            "../tests/tosca_1_3_b.yaml",
+        ]);
+        let refinement = errors(&parse("../tests/tosca_2_0_refinement.yaml"));
+
+        let all = errors(&ast);
+        assert_eq!(
+            all.iter()
+                .filter(|(_, err)| err == "circular import detected")
+                .count(),
+            1
+        );
+        for err in refinement.iter() {
+            assert!(all.contains(err), "{:?}", err);
+        }
+        // the circular import and an unsupported field of tosca_1_3_b.yaml
+        assert_eq!(all.len(), refinement.len() + 2);
+    }
+
+    #[test]
+    fn mixed_version_imports() {
+        let ast = parse("../tests/tosca_2_0_mixed.yaml");
+
+        // Lines 6, 24 and 29 are in the imported TOSCA 1.3 file.
+        assert_eq!(
+            errors(&ast),
+            vec![
+                (
+                    6,
+                    "can't import file of different tosca version".to_string()
+                ),
+                (19, "value does not satisfy in_range".to_string()),
+                (
+                    24,
+                    "occurrences of capabilities is not supported by tosca_2_0 and is ignored"
+                        .to_string()
+                ),
+                (
+                    29,
+                    "deploy_path of artifacts is not supported by tosca_2_0 and is ignored"
+                        .to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn repository_imports() {
+        let tests = url::Url::from_directory_path(env!("CARGO_MANIFEST_DIR"))
+            .unwrap()
+            .join("../../tests/")
+            .unwrap();
+        let cache = tempfile::tempdir().unwrap();
+        let parse = |mirror: Option<&str>| {
+            let mut parser = crate::ToscaParser::with_fetcher(DefaultFetcher::new(
+                HttpFetcher::new(cache.path()).offline(true),
+            ));
+            if let Some(mirror) = mirror {
+                parser = parser.mirror(
+                    url::Url::parse("https://example.com/profiles/v1/").unwrap(),
+                    tests.join(mirror).unwrap(),
+                );
+            }
+            let mut ast = toto_ast::AST::new();
+            parser
+                .parse(
+                    &tests.join("tosca_2_0_repositories.yaml").unwrap(),
+                    &mut ast,
+                )
+                .unwrap();
+            // The reason comes from the url crate and differs between its
+            // versions, keep only the url.
+            errors(&ast)
+                .into_iter()
+                .map(
+                    |(line, err)| match err.strip_prefix("invalid repository url ") {
+                        Some(rest) => (
+                            line,
+                            format!(
+                                "invalid repository url {}",
+                                rest.split(": ").next().unwrap()
+                            ),
+                        ),
+                        None => (line, err),
+                    },
+                )
+                .collect::<Vec<_>>()
+        };
+
+        let common = [
+            (9, "invalid repository url http://exa mple.com/".to_string()),
+            (19, "unknown repository: unknown".to_string()),
+        ];
+        assert_eq!(parse(Some("profiles/profile/")), common);
+
+        let mut unmirrored = common.to_vec();
+        unmirrored.extend([
+            (
+                15,
+                "https://example.com/profiles/v1/types_2.yaml is not available in the offline cache"
+                    .to_string(),
+            ),
+            (28, "unknown node type: remote:base".to_string()),
+        ]);
+        unmirrored.sort();
+        assert_eq!(parse(None), unmirrored);
     }
 }
@@ -0,0 +1,410 @@
+use once_cell::sync::Lazy;
+use ordered_float::OrderedFloat;
+use petgraph::{
+    visit::EdgeRef,
+    Direction::{Incoming, Outgoing},
+};
+use regex::Regex;
+use toto_parser::ParseError;
+
+use crate::{
+    codes, RangeEntity, ScalarUnitEntity, TimestampEntity, ToscaCompatibleEntity,
+    ToscaCompatibleRelation, UnitBitrate, UnitFrequency, UnitSize, UnitTime, Version,
+};
+
+const SIZE_UNITS: &[(&str, UnitSize, f64)] = &[
+    ("B", UnitSize::B, 1.0),
+    ("kB", UnitSize::KB, 1e3),
+    ("KiB", UnitSize::KiB, 1024.0),
+    ("MB", UnitSize::MB, 1e6),
+    ("MiB", UnitSize::MiB, 1048576.0),
+    ("GB", UnitSize::GB, 1e9),
+    ("GiB", UnitSize::GiB, 1073741824.0),
+    ("TB", UnitSize::TB, 1e12),
+    ("TiB", UnitSize::TiB, 1099511627776.0),
+];
+
+const TIME_UNITS: &[(&str, UnitTime, f64)] = &[
+    ("d", UnitTime::D, 86400.0),
+    ("h", UnitTime::H, 3600.0),
+    ("m", UnitTime::M, 60.0),
+    ("s", UnitTime::S, 1.0),
+    ("ms", UnitTime::Ms, 1e-3),
+    ("us", UnitTime::Us, 1e-6),
+    ("ns", UnitTime::Ns, 1e-9),
+];
+
+const FREQUENCY_UNITS: &[(&str, UnitFrequency, f64)] = &[
+    ("Hz", UnitFrequency::Hz, 1.0),
+    ("kHz", UnitFrequency::KHz, 1e3),
+    ("MHz", UnitFrequency::MHz, 1e6),
+    ("GHz", UnitFrequency::GHz, 1e9),
+];
+
+const BITRATE_UNITS: &[(&str, UnitBitrate, f64)] = &[
+    ("bps", UnitBitrate::Bps(UnitSize::B), 1.0),
+    ("Kbps", UnitBitrate::Bps(UnitSize::KB), 1e3),
+    ("Kibps", UnitBitrate::Bps(UnitSize::KiB), 1024.0),
+    ("Mbps", UnitBitrate::Bps(UnitSize::MB), 1e6),
+    ("Mibps", UnitBitrate::Bps(UnitSize::MiB), 1048576.0),
+    ("Gbps", UnitBitrate::Bps(UnitSize::GB), 1e9),
+    ("Gibps", UnitBitrate::Bps(UnitSize::GiB), 1073741824.0),
+    ("Tbps", UnitBitrate::Bps(UnitSize::TB), 1e12),
+    ("Tibps", UnitBitrate::Bps(UnitSize::TiB), 1099511627776.0),
+    ("Bps", UnitBitrate::BytesPs(UnitSize::B), 8.0),
+    ("KBps", UnitBitrate::BytesPs(UnitSize::KB), 8e3),
+    ("KiBps", UnitBitrate::BytesPs(UnitSize::KiB), 8192.0),
+    ("MBps", UnitBitrate::BytesPs(UnitSize::MB), 8e6),
+    ("MiBps", UnitBitrate::BytesPs(UnitSize::MiB), 8388608.0),
+    ("GBps", UnitBitrate::BytesPs(UnitSize::GB), 8e9),
+    ("GiBps", UnitBitrate::BytesPs(UnitSize::GiB), 8589934592.0),
+    ("TBps", UnitBitrate::BytesPs(UnitSize::TB), 8e12),
+    (
+        "TiBps",
+        UnitBitrate::BytesPs(UnitSize::TiB),
+        8796093022208.0,
+    ),
+];
+
+static SCALAR_UNIT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*([+-]?(?:[0-9]+\.?[0-9]*|\.[0-9]+)(?:[eE][+-]?[0-9]+)?)\s*([A-Za-z]+)\s*$")
+        .unwrap()
+});
+
+static VERSION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^([0-9]+)\.([0-9]+)(?:\.([0-9]+)(?:\.([A-Za-z0-9_]+)(?:-([0-9]+))?)?)?$").unwrap()
+});
+
+static TIMESTAMP: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(concat!(
+        r"^([0-9]{4})-([0-9]{1,2})-([0-9]{1,2})",
+        r"(?:(?:[Tt]|[ \t]+)([0-9]{1,2}):([0-9]{2}):([0-9]{2})(?:\.([0-9]*))?",
+        r"(?:[ \t]*(Z|[+-][0-9]{1,2}(?::?[0-9]{2})?))?)?$"
+    ))
+    .unwrap()
+});
+
+/// Parsers of the builtin types with a structured representation.
+pub struct Literal;
+
+impl Literal {
+    /// Parses a value of a builtin type. Returns `None` if values of the type
+    /// are kept as plain yaml.
+    pub fn parse<E, R>(
+        builtin: &str,
+        n: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<Result<crate::Entity, ParseError>>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let result = match builtin {
+            "scalar-unit.size"
+            | "scalar-unit.time"
+            | "scalar-unit.frequency"
+            | "scalar-unit.bitrate" => {
+                Self::parse_scalar_unit(builtin, &toto_yaml::as_string(n, ast)?.0)
+                    .map(crate::Entity::from)
+            }
+            "version" => match ast.node_weight(n).unwrap().as_yaml()? {
+                toto_yaml::Entity::Str(s) => Self::parse_version(&s.0),
+                // Parsed by yaml as a number, e.g. 1.10
+                toto_yaml::Entity::Int(_) | toto_yaml::Entity::Float(_) => {
+                    Self::parse_version(Self::get_source(n, ast)?)
+                }
+                _ => return None,
+            }
+            .map(crate::Entity::from),
+            "timestamp" => {
+                Self::parse_timestamp(&toto_yaml::as_string(n, ast)?.0).map(crate::Entity::from)
+            }
+            "range" => Self::parse_range(n, ast).map(crate::Entity::from),
+            _ => return None,
+        };
+
+        Some(
+            result.map_err(|err| {
+                codes::INVALID_LITERAL.with(format!("invalid {}: {}", builtin, err))
+            }),
+        )
+    }
+
+    /// Returns the structured value previously parsed from a yaml value.
+    pub fn get<E, R>(n: toto_ast::GraphHandle, ast: &toto_ast::AST<E, R>) -> Option<&crate::Entity>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        ast.edges_directed(n, Incoming)
+            .filter(|e| e.weight().as_parse_loc().is_some())
+            .find_map(|e| match ast.node_weight(e.source()).unwrap().as_tosca() {
+                Some(
+                    entity @ (crate::Entity::ScalarUnit(_)
+                    | crate::Entity::Version(_)
+                    | crate::Entity::Timestamp(_)
+                    | crate::Entity::Range(_)),
+                ) => Some(entity),
+                _ => None,
+            })
+    }
+
+    pub fn parse_scalar_unit(builtin: &str, s: &str) -> Result<ScalarUnitEntity, String> {
+        let captures = SCALAR_UNIT
+            .captures(s)
+            .ok_or("expected <number> <unit>".to_string())?;
+        let value = captures[1]
+            .parse::<f64>()
+            .map_err(|err| format!("{}: {}", err, &captures[1]))?;
+        let unit = &captures[2];
+
+        fn find<U: Copy>(
+            units: &[(&str, U, f64)],
+            unit: &str,
+            case_sensitive: bool,
+        ) -> Result<(U, f64), String> {
+            units
+                .iter()
+                .find(|(name, _, _)| {
+                    if case_sensitive {
+                        *name == unit
+                    } else {
+                        name.eq_ignore_ascii_case(unit)
+                    }
+                })
+                .map(|(_, unit, factor)| (*unit, *factor))
+                .ok_or(format!(
+                    "unknown unit {}, expected one of {}",
+                    unit,
+                    units
+                        .iter()
+                        .map(|(name, _, _)| *name)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ))
+        }
+
+        Ok(match builtin {
+            "scalar-unit.size" => {
+                let (unit, factor) = find(SIZE_UNITS, unit, false)?;
+                ScalarUnitEntity::Size(OrderedFloat(value * factor), unit)
+            }
+            "scalar-unit.time" => {
+                let (unit, factor) = find(TIME_UNITS, unit, false)?;
+                ScalarUnitEntity::Time(OrderedFloat(value * factor), unit)
+            }
+            "scalar-unit.frequency" => {
+                let (unit, factor) = find(FREQUENCY_UNITS, unit, false)?;
+                ScalarUnitEntity::Frequency(OrderedFloat(value * factor), unit)
+            }
+            _ => {
+                // Bits and bytes differ only in case.
+                let (unit, factor) = find(BITRATE_UNITS, unit, true)?;
+                ScalarUnitEntity::Bitrate(OrderedFloat(value * factor), unit)
+            }
+        })
+    }
+
+    pub fn parse_version(s: &str) -> Result<Version, String> {
+        let captures = VERSION
+            .captures(s.trim())
+            .ok_or("expected <major>.<minor>[.<fix>[.<qualifier>[-<build>]]]".to_string())?;
+        let number = |i: usize| -> Result<u64, String> {
+            captures
+                .get(i)
+                .map(|m| m.as_str().parse::<u64>().map_err(|err| err.to_string()))
+                .unwrap_or(Ok(0))
+        };
+
+        Ok(Version {
+            major: number(1)?,
+            minor: number(2)?,
+            fix: number(3)?,
+            qualifier: captures
+                .get(4)
+                .map(|m| m.as_str().to_string())
+                .unwrap_or_default(),
+            build: number(5)?,
+        })
+    }
+
+    pub fn parse_timestamp(s: &str) -> Result<TimestampEntity, String> {
+        let captures = TIMESTAMP
+            .captures(s.trim())
+            .ok_or("expected YYYY-MM-DD[Thh:mm:ss[.fraction][Z|+hh:mm]]".to_string())?;
+        let number = |i: usize| -> i64 {
+            captures
+                .get(i)
+                .map(|m| m.as_str().parse::<i64>().unwrap())
+                .unwrap_or(0)
+        };
+
+        let (year, month, day) = (number(1), number(2), number(3));
+        let (hour, minute, second) = (number(4), number(5), number(6));
+        if !(1..=12).contains(&month) {
+            return Err(format!("month out of range: {}", month));
+        }
+        let is_leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        let days_in_month = match month {
+            2 if is_leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        };
+        if !(1..=days_in_month).contains(&day) {
+            return Err(format!("day out of range: {}", day));
+        }
+        if hour > 23 {
+            return Err(format!("hour out of range: {}", hour));
+        }
+        if minute > 59 {
+            return Err(format!("minute out of range: {}", minute));
+        }
+        if second > 60 {
+            return Err(format!("second out of range: {}", second));
+        }
+
+        let nanos = captures
+            .get(7)
+            .map(|m| {
+                let digits = m.as_str().chars().take(9).collect::<String>();
+                format!("{:0<9}", digits).parse::<u32>().unwrap()
+            })
+            .unwrap_or(0);
+
+        let offset = match captures.get(8).map(|m| m.as_str()) {
+            None | Some("Z") => 0,
+            Some(tz) => {
+                let sign = if tz.starts_with('-') { -1 } else { 1 };
+                let tz = tz[1..].replace(':', "");
+                let (hours, minutes) = if tz.len() > 2 {
+                    tz.split_at(tz.len() - 2)
+                } else {
+                    (tz.as_str(), "0")
+                };
+                let (hours, minutes) = (
+                    hours.parse::<i64>().unwrap(),
+                    minutes.parse::<i64>().unwrap(),
+                );
+                if hours > 23 || minutes > 59 {
+                    return Err(format!("time zone out of range: {}", &captures[8]));
+                }
+                sign * (hours * 3600 + minutes * 60)
+            }
+        };
+
+        // Days since the epoch of the proleptic Gregorian calendar date.
+        let y = if month <= 2 { year - 1 } else { year };
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        let days = era * 146097 + doe - 719468;
+
+        Ok(TimestampEntity {
+            seconds: days * 86400 + hour * 3600 + minute * 60 + second - offset,
+            nanos,
+        })
+    }
+
+    pub(crate) fn parse_range<E, R>(
+        n: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Result<RangeEntity, String>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mut bounds = toto_yaml::as_list(n, ast)
+            .ok_or("expected [<lower>, <upper>]".to_string())?
+            .collect::<Vec<_>>();
+        bounds.sort_by_key(|(i, _)| *i);
+        let [(_, lower), (_, upper)] = bounds.as_slice() else {
+            return Err("expected [<lower>, <upper>]".to_string());
+        };
+
+        let lower = toto_yaml::as_int(*lower, ast)
+            .map(|lower| lower.0)
+            .ok_or("lower bound must be an integer".to_string())?;
+        let upper = match (
+            toto_yaml::as_int(*upper, ast),
+            toto_yaml::as_string(*upper, ast),
+        ) {
+            (Some(upper), _) => Some(upper.0),
+            (_, Some(upper)) if upper.0 == "UNBOUNDED" => None,
+            _ => return Err("upper bound must be an integer or UNBOUNDED".to_string()),
+        };
+        if upper.is_some_and(|upper| lower > upper) {
+            return Err("lower bound is greater than the upper bound".to_string());
+        }
+
+        Ok(RangeEntity {
+            lower: Some(lower),
+            upper,
+        })
+    }
+
+    /// Source text of a scalar yaml value.
+    fn get_source<E, R>(n: toto_ast::GraphHandle, ast: &toto_ast::AST<E, R>) -> Option<&str>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let (pos, file) = ast
+            .edges_directed(n, Outgoing)
+            .find_map(|e| e.weight().as_file().map(|pos| (pos.0, e.target())))?;
+        let content = ast.node_weight(file)?.as_file()?.content.as_ref()?;
+        let source = content.get(pos..)?;
+        let end = source
+            .find(|c: char| c.is_whitespace() || matches!(c, ',' | ']' | '}' | '#'))
+            .unwrap_or(source.len());
+        Some(&source[..end])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Literal;
+    use crate::grammar::tests::{errors, parse};
+
+    #[test]
+    fn literals() {
+        let ast = parse("../tests/tosca_1_3_scalars.yaml");
+
+        let errors = errors(&ast);
+        assert_eq!(
+            errors
+                .iter()
+                .map(|(line, err)| (*line, err.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (45, "invalid scalar-unit.size: unknown unit XB, expected one of B, kB, KiB, MB, MiB, GB, GiB, TB, TiB"),
+                (63, "value does not satisfy greater_or_equal"),
+                (64, "value does not satisfy in_range"),
+                (65, "value does not satisfy less_than"),
+                (66, "value does not satisfy greater_than"),
+                (67, "value does not satisfy greater_or_equal"),
+                (68, "value does not satisfy less_or_equal"),
+                (73, "invalid scalar-unit.size: expected <number> <unit>"),
+                (74, "invalid scalar-unit.size: unknown unit XB, expected one of B, kB, KiB, MB, MiB, GB, GiB, TB, TiB"),
+                (75, "expected scalar-unit.time"),
+                (77, "invalid scalar-unit.bitrate: unknown unit MBPS, expected one of bps, Kbps, Kibps, Mbps, Mibps, Gbps, Gibps, Tbps, Tibps, Bps, KBps, KiBps, MBps, MiBps, GBps, GiBps, TBps, TiBps"),
+                (78, "invalid version: expected <major>.<minor>[.<fix>[.<qualifier>[-<build>]]]"),
+                (79, "invalid timestamp: day out of range: 29"),
+                (80, "invalid range: lower bound is greater than the upper bound"),
+            ]
+        );
+    }
+
+    #[test]
+    fn timestamp() {
+        let utc = Literal::parse_timestamp("2001-12-15T02:59:43.1Z").unwrap();
+        let local = Literal::parse_timestamp("2001-12-14t21:59:43.10-05:00").unwrap();
+        assert_eq!(utc, local);
+        assert_eq!(utc.seconds, 1008385183);
+        assert_eq!(utc.nanos, 100000000);
+        assert_eq!(Literal::parse_timestamp("1970-01-01").unwrap().seconds, 0);
+        assert!(Literal::parse_timestamp("2001-02-29").is_err());
+        assert!(Literal::parse_timestamp("2001-12-14 25:00:00").is_err());
+    }
+}
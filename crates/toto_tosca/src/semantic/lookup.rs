@@ -1,9 +1,10 @@
 use petgraph::{data::DataMap, visit::EdgeRef};
 use toto_parser::add_with_loc;
 
-use crate::{ToscaCompatibleEntity, ToscaCompatibleRelation};
+use crate::{codes, ToscaCompatibleEntity, ToscaCompatibleRelation};
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[allow(unpredictable_function_pointer_comparisons)]
 pub struct SimpleLookuper {
     pub root: (crate::Relation, crate::Entity),
     pub what: crate::Entity,
@@ -78,14 +79,26 @@ impl SimpleLookuper {
 
             ast.add_edge(source, lookuped, self.then.clone().into());
         } else {
-            add_with_loc(
-                toto_parser::ParseError::Custom(format!(
-                    "unknown {:?} {:?}",
-                    self.what, target_rel
-                )),
-                target,
-                ast,
-            );
+            let err = match (&target_rel, &self.what) {
+                (crate::Relation::Type(crate::TypeRelation(name)), what) => {
+                    codes::UNKNOWN_TYPE.with(format!("unknown {} type: {}", what.kind(), name))
+                }
+                (crate::Relation::Definition(crate::DefinitionRelation(name)), what) => {
+                    let kind = match what {
+                        crate::Entity::Node(_) => "node template",
+                        what => what.kind(),
+                    };
+                    codes::UNKNOWN_DEFINITION.with(format!("unknown {}: {}", kind, name))
+                }
+                (crate::Relation::Input(crate::InputRelation(name)), _) => {
+                    codes::UNKNOWN_DEFINITION.with(format!("unknown input: {}", name))
+                }
+                (crate::Relation::Output(crate::OutputRelation(name)), _) => {
+                    codes::UNKNOWN_DEFINITION.with(format!("unknown output: {}", name))
+                }
+                (_, what) => codes::UNKNOWN_DEFINITION.with(format!("unknown {}", what.kind())),
+            };
+            add_with_loc(err, target, ast);
         }
     }
 
@@ -133,6 +146,8 @@ impl SimpleLookuper {
                         let name = match rel {
                             crate::Relation::Type(type_rel) => type_rel.0.clone(),
                             crate::Relation::Definition(def_rel) => def_rel.0.clone(),
+                            crate::Relation::Input(input_rel) => input_rel.0.clone(),
+                            crate::Relation::Output(output_rel) => output_rel.0.clone(),
                             _ => String::new(),
                         };
                         return Some((name, rel, e.target()));
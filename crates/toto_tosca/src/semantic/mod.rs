@@ -1,9 +1,36 @@
+pub mod artifact;
+pub mod declarative;
 pub mod derive;
+pub mod filter;
+pub mod functions;
+pub(crate) mod graph;
 pub mod hierarchy;
 pub mod import;
+pub mod literal;
 pub mod lookup;
+pub mod refinement;
+pub mod requirement;
+pub mod status;
+pub mod substitution;
+pub mod template;
+pub mod validate;
+pub mod validation;
+pub mod workflow;
 
+pub use artifact::*;
+pub use declarative::*;
 pub use derive::*;
+pub use filter::*;
+pub use functions::*;
 pub use hierarchy::*;
 pub use import::*;
+pub use literal::*;
 pub use lookup::*;
+pub use refinement::*;
+pub use requirement::*;
+pub use status::*;
+pub use substitution::*;
+pub use template::*;
+pub use validate::*;
+pub use validation::*;
+pub use workflow::*;
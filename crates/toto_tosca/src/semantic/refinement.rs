@@ -0,0 +1,268 @@
+use std::collections::HashSet;
+
+use petgraph::{visit::EdgeRef, Direction::Outgoing};
+use toto_parser::{add_with_loc, ParseError};
+
+use crate::{codes, ToscaCompatibleEntity, ToscaCompatibleRelation};
+
+use super::{graph, Requirements};
+
+/// Checks that refined definitions are compatible with the definitions they
+/// refine: the type is the same or derived, `required` is not relaxed, the
+/// `status` is kept and requirements only narrow their valid types.
+pub struct Refinement;
+
+impl Refinement {
+    pub fn check_all<E, R>(ast: &mut toto_ast::AST<E, R>)
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let refinements = ast
+            .edge_references()
+            .filter(|e| matches!(e.weight().as_tosca(), Some(crate::Relation::RefinedFrom(_))))
+            .map(|e| (e.source(), e.target()))
+            .collect::<Vec<_>>();
+
+        let mut errors = vec![];
+        for (def, refined) in refinements {
+            Self::check(def, refined, ast, &mut errors);
+        }
+
+        for (err, loc) in errors {
+            add_with_loc(err, loc, ast);
+        }
+    }
+
+    fn check<E, R>(
+        def: toto_ast::GraphHandle,
+        refined: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+        errors: &mut Vec<(ParseError, toto_ast::GraphHandle)>,
+    ) where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let Some(entity) = ast.node_weight(def).unwrap().as_tosca() else {
+            return;
+        };
+        let what = format!("{} {}", entity.kind(), Requirements::get_name(def, ast));
+
+        let mut check_type = |def: toto_ast::GraphHandle,
+                              refined: toto_ast::GraphHandle,
+                              is_type: fn(&crate::Relation) -> bool| {
+            let (Some(t), Some(refined_t)) = (
+                graph::get_target(def, ast, is_type),
+                Self::find_along_refinements(refined, ast, |n| graph::get_target(n, ast, is_type)),
+            ) else {
+                return;
+            };
+            if !Requirements::is_derived_from(t, refined_t, ast) {
+                errors.push((
+                    codes::INCOMPATIBLE_REFINEMENT.with(format!(
+                        "incompatible refinement of {}: {} is not derived from {}",
+                        what,
+                        Requirements::get_type_name(t, ast),
+                        Requirements::get_type_name(refined_t, ast)
+                    )),
+                    Self::get_type_loc(def, is_type, ast).unwrap_or(def),
+                ));
+            }
+        };
+
+        check_type(def, refined, |rel| {
+            matches!(rel, crate::Relation::HasType(_))
+        });
+        if matches!(entity, crate::Entity::Requirement(_)) {
+            check_type(def, refined, |rel| {
+                matches!(rel, crate::Relation::ValidTargetNodeType(_))
+            });
+            check_type(def, refined, |rel| {
+                matches!(rel, crate::Relation::ValidCapabilityType(_))
+            });
+            if let (Some(relationship), Some(refined_relationship)) = (
+                Self::get_relationship(def, ast),
+                Self::find_along_refinements(refined, ast, |n| Self::get_relationship(n, ast)),
+            ) {
+                check_type(relationship, refined_relationship, |rel| {
+                    matches!(rel, crate::Relation::HasType(_))
+                });
+            }
+        }
+
+        if let Some((required, loc)) = Self::get_required(def, ast) {
+            let refined_required = Self::find_along_refinements(refined, ast, |n| {
+                Self::get_required(n, ast).map(|(required, _)| required)
+            })
+            .unwrap_or(true);
+            if refined_required && !required {
+                errors.push((
+                    codes::OPTIONAL_REFINEMENT
+                        .with(format!("refinement of {} cannot make it optional", what)),
+                    loc,
+                ));
+            }
+        }
+
+        if let Some((status, loc)) = Self::get_status(def, ast) {
+            let refined_status = Self::find_along_refinements(refined, ast, |n| {
+                Self::get_status(n, ast).map(|(status, _)| status)
+            })
+            .unwrap_or_default();
+            if status != refined_status {
+                errors.push((
+                    codes::STATUS_REFINEMENT.with(format!(
+                        "refinement of {} cannot change its status from {} to {}",
+                        what,
+                        Self::get_status_name(refined_status),
+                        Self::get_status_name(status)
+                    )),
+                    loc,
+                ));
+            }
+        }
+    }
+
+    /// Returns the first value found in a definition or in the definitions it
+    /// refines.
+    fn find_along_refinements<E, R, T>(
+        def: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+        f: impl Fn(toto_ast::GraphHandle) -> Option<T>,
+    ) -> Option<T>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mut visited = HashSet::new();
+        let mut curr = Some(def);
+        while let Some(def) = curr.filter(|d| visited.insert(*d)) {
+            if let Some(value) = f(def) {
+                return Some(value);
+            }
+            curr = graph::get_target(def, ast, |rel| {
+                matches!(rel, crate::Relation::RefinedFrom(_))
+            });
+        }
+        None
+    }
+
+    fn get_relationship<E, R>(
+        requirement: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        ast.edges_directed(requirement, Outgoing)
+            .find(|e| {
+                matches!(e.weight().as_tosca(), Some(crate::Relation::Definition(_)))
+                    && matches!(
+                        ast.node_weight(e.target()).unwrap().as_tosca(),
+                        Some(crate::Entity::Relationship(_))
+                    )
+            })
+            .map(|e| e.target())
+    }
+
+    fn get_required<E, R>(
+        def: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<(bool, toto_ast::GraphHandle)>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        ast.edges_directed(def, Outgoing)
+            .find_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::Required(_)) => {
+                    toto_yaml::as_bool(e.target(), ast).map(|required| (required.0, e.target()))
+                }
+                _ => None,
+            })
+    }
+
+    pub(crate) fn get_status<E, R>(
+        def: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<(crate::StatusEntity, toto_ast::GraphHandle)>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        ast.edges_directed(def, Outgoing)
+            .find_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::Status(_)) => {
+                    match ast.node_weight(e.target()).unwrap().as_tosca() {
+                        Some(crate::Entity::Status(status)) => Some((*status, e.target())),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+    }
+
+    fn get_status_name(status: crate::StatusEntity) -> &'static str {
+        match status {
+            crate::StatusEntity::Supported => "supported",
+            crate::StatusEntity::Unsupported => "unsupported",
+            crate::StatusEntity::Experimental => "experimental",
+            crate::StatusEntity::Deprecated => "deprecated",
+        }
+    }
+
+    /// Location of the type reference of a definition.
+    pub(crate) fn get_type_loc<E, R>(
+        def: toto_ast::GraphHandle,
+        is_type: fn(&crate::Relation) -> bool,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        ast.edges_directed(def, Outgoing)
+            .find_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::Ref(r)) if is_type(&r.lookuper.then) => Some(e.target()),
+                _ => None,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grammar::tests::{errors, parse};
+
+    #[test]
+    fn refinement() {
+        let ast = parse("../tests/tosca_2_0_refinement.yaml");
+
+        let errors = errors(&ast);
+        assert_eq!(
+            errors
+                .iter()
+                .map(|(line, err)| (*line, err.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    57,
+                    "incompatible refinement of property port: string is not derived from integer"
+                ),
+                (60, "refinement of property name cannot make it optional"),
+                (
+                    63,
+                    "refinement of property legacy cannot change its status from deprecated to supported"
+                ),
+                (
+                    66,
+                    "incompatible refinement of requirement database: Endpoint is not derived from DatabaseEndpoint"
+                ),
+                (
+                    70,
+                    "incompatible refinement of requirement host: HostedOn is not derived from ConnectsTo"
+                ),
+            ]
+        );
+    }
+}
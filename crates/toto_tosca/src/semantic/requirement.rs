@@ -0,0 +1,474 @@
+use std::collections::HashSet;
+
+use petgraph::{
+    data::DataMap,
+    visit::EdgeRef,
+    Direction::{Incoming, Outgoing},
+};
+use toto_parser::{add_with_loc, ParseError};
+
+use crate::{codes, ToscaCompatibleEntity, ToscaCompatibleRelation};
+
+use super::{graph, Derive, Functions};
+
+/// Requirement fulfilment. Each requirement assignment of a node template gets
+/// a `TargetNode` edge to the node template fulfilling it and, when the
+/// requirement constrains the capability, a `ResolvedCapability` edge to the
+/// matching capability of that node template.
+pub struct Requirements;
+
+impl Requirements {
+    pub fn resolve_all<E, R>(ast: &mut toto_ast::AST<E, R>)
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let requirements =
+            ast.edge_references()
+                .filter_map(|e| match e.weight().as_tosca() {
+                    Some(crate::Relation::OrderedAssignment(crate::OrderedAssignmentRelation(
+                        (name, _),
+                    ))) if Self::is_node_template(e.source(), ast)
+                        && matches!(
+                            ast.node_weight(e.target()).unwrap().as_tosca(),
+                            Some(crate::Entity::Requirement(_))
+                        ) =>
+                    {
+                        Some((e.source(), name.clone(), e.target()))
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+
+        let mut edges = vec![];
+        let mut errors = vec![];
+        let mut resolved = HashSet::new();
+        for (node, name, requirement) in requirements {
+            // Requirements copied to another node template are resolved once.
+            if !resolved.insert(requirement) {
+                continue;
+            }
+            Self::resolve(node, &name, requirement, ast, &mut edges, &mut errors);
+        }
+
+        for (source, target, rel) in edges {
+            ast.add_edge(source, target, rel.into());
+        }
+        for (err, loc) in errors {
+            add_with_loc(err, loc, ast);
+        }
+    }
+
+    fn resolve<E, R>(
+        node: toto_ast::GraphHandle,
+        name: &str,
+        requirement: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+        edges: &mut Vec<(
+            toto_ast::GraphHandle,
+            toto_ast::GraphHandle,
+            crate::Relation,
+        )>,
+        errors: &mut Vec<(ParseError, toto_ast::GraphHandle)>,
+    ) where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let Some(definition) =
+            graph::get_target(node, ast, |rel| matches!(rel, crate::Relation::HasType(_)))
+                .and_then(|node_type| Derive::find_requirement(node_type, name, ast))
+        else {
+            errors.push((
+                codes::UNKNOWN_ASSIGNMENT.with(format!("unknown requirement: {}", name)),
+                requirement,
+            ));
+            return;
+        };
+        edges.push((
+            requirement,
+            definition,
+            crate::Relation::from(crate::DefinedByRelation),
+        ));
+
+        let node_type = graph::get_target(definition, ast, |rel| {
+            matches!(rel, crate::Relation::ValidTargetNodeType(_))
+        });
+        let capability_type = graph::get_target(definition, ast, |rel| {
+            matches!(rel, crate::Relation::ValidCapabilityType(_))
+        });
+        let capability = graph::get_target(requirement, ast, |rel| {
+            matches!(rel, crate::Relation::TargetCapability(_))
+        })
+        .and_then(|capability| toto_yaml::as_string(capability, ast))
+        .map(|capability| capability.0.clone());
+
+        let matching_capabilities = |target: toto_ast::GraphHandle| {
+            Self::get_capabilities(target, ast)
+                .into_iter()
+                .filter(|(cap_name, cap)| {
+                    let cap_type = Self::get_capability_type(*cap, ast);
+                    capability.as_ref().is_none_or(|capability| {
+                        cap_name == capability
+                            || cap_type.is_some_and(|t| Self::has_name(t, capability, ast))
+                    }) && capability_type.is_none_or(|capability_type| {
+                        cap_type.is_some_and(|t| Self::is_derived_from(t, capability_type, ast))
+                    })
+                })
+                .collect::<Vec<_>>()
+        };
+        let has_capability_filter = capability.is_some() || capability_type.is_some();
+        let is_of_type = |target: toto_ast::GraphHandle| {
+            node_type.is_none_or(|node_type| {
+                graph::get_target(target, ast, |rel| {
+                    matches!(rel, crate::Relation::HasType(_))
+                })
+                .is_some_and(|t| Self::is_derived_from(t, node_type, ast))
+            })
+        };
+
+        let target = match graph::get_target(requirement, ast, |rel| {
+            matches!(rel, crate::Relation::TargetNode(_))
+        }) {
+            Some(target) => {
+                if !is_of_type(target) {
+                    errors.push((
+                        codes::TARGET_TYPE_MISMATCH.with(format!(
+                            "requirement {}: node template {} is not of type {}",
+                            name,
+                            Self::get_name(target, ast),
+                            Self::get_type_name(node_type.unwrap(), ast)
+                        )),
+                        requirement,
+                    ));
+                    return;
+                }
+                if has_capability_filter && matching_capabilities(target).is_empty() {
+                    errors.push((
+                        codes::MISSING_TARGET_CAPABILITY.with(format!(
+                            "requirement {}: node template {} has no matching capability",
+                            name,
+                            Self::get_name(target, ast)
+                        )),
+                        requirement,
+                    ));
+                    return;
+                }
+                target
+            }
+            None => {
+                let service_template = Functions::find_ancestor(node, ast, |e| {
+                    matches!(e, crate::Entity::ServiceTemplate(_))
+                });
+                let candidates = ast
+                    .node_indices()
+                    .filter(|n| {
+                        *n != node
+                            && Self::is_node_template(*n, ast)
+                            && Functions::find_ancestor(*n, ast, |e| {
+                                matches!(e, crate::Entity::ServiceTemplate(_))
+                            }) == service_template
+                            && is_of_type(*n)
+                            && (!has_capability_filter || !matching_capabilities(*n).is_empty())
+                    })
+                    .collect::<Vec<_>>();
+
+                match candidates.as_slice() {
+                    [target] => {
+                        edges.push((
+                            requirement,
+                            *target,
+                            crate::Relation::from(crate::TargetNodeRelation),
+                        ));
+                        *target
+                    }
+                    [] => {
+                        errors.push((
+                            codes::UNFULFILLED_REQUIREMENT.with(format!(
+                                "requirement {} cannot be fulfilled: no matching node template",
+                                name
+                            )),
+                            requirement,
+                        ));
+                        return;
+                    }
+                    _ => {
+                        errors.push((
+                            codes::AMBIGUOUS_TARGET.with(format!(
+                                "requirement {} is ambiguous: matching node templates are {}",
+                                name,
+                                Self::join_names(&candidates, ast)
+                            )),
+                            requirement,
+                        ));
+                        return;
+                    }
+                }
+            }
+        };
+
+        if !has_capability_filter {
+            return;
+        }
+        match matching_capabilities(target).as_slice() {
+            [(_, cap)] => edges.push((
+                requirement,
+                *cap,
+                crate::Relation::from(crate::ResolvedCapabilityRelation),
+            )),
+            caps => errors.push((
+                codes::AMBIGUOUS_CAPABILITY.with(format!(
+                    "requirement {} is ambiguous: matching capabilities of {} are {}",
+                    name,
+                    Self::get_name(target, ast),
+                    caps.iter()
+                        .map(|(cap_name, _)| cap_name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )),
+                requirement,
+            )),
+        }
+    }
+
+    pub(crate) fn is_node_template<E, R>(
+        n: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> bool
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        matches!(
+            ast.node_weight(n).unwrap().as_tosca(),
+            Some(crate::Entity::Node(_))
+        ) && Functions::find_ancestor(n, ast, |e| matches!(e, crate::Entity::ServiceTemplate(_)))
+            .is_some()
+    }
+
+    /// Capabilities of a node template by name, assignments take precedence
+    /// over the definitions.
+    pub(crate) fn get_capabilities<E, R>(
+        node: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Vec<(String, toto_ast::GraphHandle)>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let members = ast
+            .edges_directed(node, Outgoing)
+            .filter(|e| {
+                matches!(
+                    ast.node_weight(e.target()).unwrap().as_tosca(),
+                    Some(crate::Entity::Capability(_))
+                )
+            })
+            .filter_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::Assignment(crate::AssignmentRelation(name))) => {
+                    Some((true, name.clone(), e.target()))
+                }
+                Some(crate::Relation::Definition(crate::DefinitionRelation(name))) => {
+                    Some((false, name.clone(), e.target()))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let mut capabilities = members
+            .iter()
+            .filter(|(assigned, name, _)| {
+                *assigned || !members.iter().any(|(a, n, _)| *a && n == name)
+            })
+            .map(|(_, name, cap)| (name.clone(), *cap))
+            .collect::<Vec<_>>();
+        capabilities.sort();
+        capabilities
+    }
+
+    pub(crate) fn get_capability_type<E, R>(
+        capability: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<toto_ast::GraphHandle>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mut visited = HashSet::new();
+        let mut curr = Some(capability);
+        while let Some(capability) = curr.filter(|c| visited.insert(*c)) {
+            if let Some(t) = graph::get_target(capability, ast, |rel| {
+                matches!(rel, crate::Relation::HasType(_))
+            }) {
+                return Some(t);
+            }
+            curr = graph::get_target(capability, ast, |rel| {
+                matches!(
+                    rel,
+                    crate::Relation::DefinedBy(_) | crate::Relation::RefinedFrom(_)
+                )
+            });
+        }
+        None
+    }
+
+    pub(crate) fn is_derived_from<E, R>(
+        t: toto_ast::GraphHandle,
+        base: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> bool
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mut visited = HashSet::new();
+        let mut curr = Some(t);
+        while let Some(t) = curr.filter(|t| visited.insert(*t)) {
+            if t == base {
+                return true;
+            }
+            curr = graph::get_target(t, ast, |rel| matches!(rel, crate::Relation::DerivedFrom(_)));
+        }
+        false
+    }
+
+    /// Whether a type or one of its parents is known under the name.
+    pub(crate) fn has_name<E, R>(
+        t: toto_ast::GraphHandle,
+        name: &str,
+        ast: &toto_ast::AST<E, R>,
+    ) -> bool
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mut visited = HashSet::new();
+        let mut curr = Some(t);
+        while let Some(t) = curr.filter(|t| visited.insert(*t)) {
+            if ast.edges_directed(t, Incoming).any(|e| {
+                matches!(e.weight().as_tosca(), Some(crate::Relation::Type(crate::TypeRelation(n))) if n == name)
+            }) {
+                return true;
+            }
+            curr = graph::get_target(t, ast, |rel| matches!(rel, crate::Relation::DerivedFrom(_)));
+        }
+        false
+    }
+
+    pub(crate) fn get_name<E, R>(n: toto_ast::GraphHandle, ast: &toto_ast::AST<E, R>) -> String
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        ast.edges_directed(n, Incoming)
+            .find_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::Definition(crate::DefinitionRelation(name)))
+                | Some(crate::Relation::OrderedDefinition(crate::OrderedDefinitionRelation((
+                    name,
+                    _,
+                )))) => Some(name.clone()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn get_type_name<E, R>(t: toto_ast::GraphHandle, ast: &toto_ast::AST<E, R>) -> String
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        ast.edges_directed(t, Incoming)
+            .find_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::Type(crate::TypeRelation(name))) => Some(name.clone()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    fn join_names<E, R>(nodes: &[toto_ast::GraphHandle], ast: &toto_ast::AST<E, R>) -> String
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mut names = nodes
+            .iter()
+            .map(|n| Self::get_name(*n, ast))
+            .collect::<Vec<_>>();
+        names.sort();
+        names.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::{visit::EdgeRef, Direction::Incoming};
+
+    use crate::grammar::tests::{errors, parse};
+    use crate::AsToscaRelation;
+
+    #[test]
+    fn requirements() {
+        let ast = parse("../tests/tosca_2_0_requirements.yaml");
+
+        let errors = errors(&ast);
+        assert_eq!(
+            errors
+                .iter()
+                .map(|(line, err)| (*line, err.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (73, "requirement database: node template cache is not of type Database"),
+                (
+                    74,
+                    "requirement host is ambiguous: matching capabilities of cache are admin, endpoint"
+                ),
+                (75, "requirement storage cannot be fulfilled: no matching node template"),
+                (
+                    76,
+                    "requirement database is ambiguous: matching node templates are db, replica"
+                ),
+                (77, "unknown requirement: monitor"),
+            ]
+        );
+
+        let collector = ast
+            .edge_references()
+            .find(|e| {
+                e.weight().as_tosca()
+                    == Some(&crate::Relation::from(crate::DefinitionRelation(
+                        "collector".to_string(),
+                    )))
+            })
+            .unwrap()
+            .target();
+        assert_eq!(
+            ast.edges_directed(collector, Incoming)
+                .filter(|e| matches!(e.weight().as_tosca(), Some(crate::Relation::TargetNode(_))))
+                .count(),
+            1
+        );
+
+        let admin = ast
+            .edge_references()
+            .find(|e| {
+                e.weight().as_tosca()
+                    == Some(&crate::Relation::from(crate::AssignmentRelation(
+                        "admin".to_string(),
+                    )))
+                    || e.weight().as_tosca()
+                        == Some(&crate::Relation::from(crate::DefinitionRelation(
+                            "admin".to_string(),
+                        )))
+            })
+            .unwrap()
+            .target();
+        assert_eq!(
+            ast.edges_directed(admin, Incoming)
+                .filter(|e| matches!(
+                    e.weight().as_tosca(),
+                    Some(crate::Relation::ResolvedCapability(_))
+                ))
+                .count(),
+            1
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use petgraph::visit::EdgeRef;
+use toto_parser::add_with_loc;
+
+use crate::{codes, ToscaCompatibleEntity, ToscaCompatibleRelation};
+
+use super::{Refinement, Requirements};
+
+/// Reports types which are derived from or used as a type of a definition or
+/// a template while not being supported. Usage of deprecated and experimental
+/// types is reported as a warning, usage of unsupported types is an error.
+pub struct Status;
+
+impl Status {
+    pub fn check_all<E, R>(ast: &mut toto_ast::AST<E, R>)
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let usages = ast
+            .edge_references()
+            .filter(|e| {
+                matches!(
+                    e.weight().as_tosca(),
+                    Some(crate::Relation::HasType(_) | crate::Relation::DerivedFrom(_))
+                )
+            })
+            .filter_map(|e| {
+                let (status, _) = Refinement::get_status(e.target(), ast)?;
+                Some((e.source(), e.target(), status))
+            })
+            .collect::<Vec<_>>();
+
+        let mut errors = vec![];
+        for (source, t, status) in usages {
+            let (status, code) = match status {
+                crate::StatusEntity::Supported => continue,
+                crate::StatusEntity::Unsupported => ("an unsupported", &codes::UNSUPPORTED_TYPE),
+                crate::StatusEntity::Experimental => ("an experimental", &codes::EXPERIMENTAL_TYPE),
+                crate::StatusEntity::Deprecated => ("a deprecated", &codes::DEPRECATED_TYPE),
+            };
+            let loc = Refinement::get_type_loc(
+                source,
+                |rel| {
+                    matches!(
+                        rel,
+                        crate::Relation::HasType(_) | crate::Relation::DerivedFrom(_)
+                    )
+                },
+                ast,
+            )
+            .unwrap_or(source);
+            errors.push((
+                code.with(format!(
+                    "{} is {} type",
+                    Requirements::get_type_name(t, ast),
+                    status
+                )),
+                loc,
+            ));
+        }
+
+        for (err, loc) in errors {
+            add_with_loc(err, loc, ast);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use toto_parser::{AsParseError, Severity};
+
+    use crate::grammar::tests::{errors, parse};
+
+    #[test]
+    fn status() {
+        let ast = parse("../tests/tosca_2_0_status.yaml");
+
+        let errors = errors(&ast);
+        assert_eq!(
+            errors
+                .iter()
+                .map(|(line, err)| (*line, err.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    7,
+                    "imported type Local collides with another type of the same name"
+                ),
+                (
+                    7,
+                    "imported type Shared collides with another type of the same name"
+                ),
+                (17, "Legacy is a deprecated type"),
+                (29, "Beta is an experimental type"),
+                (32, "Legacy is a deprecated type"),
+                (41, "Gone is an unsupported type"),
+            ]
+        );
+
+        let severities = toto_parser::get_errors(&ast)
+            .map(|(err, _)| {
+                (
+                    ast[err].as_parse().unwrap().to_string(),
+                    ast[err].as_parse().unwrap().severity(),
+                )
+            })
+            .filter(|(err, _)| err.ends_with(" type"))
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(
+            severities,
+            [
+                ("Legacy is a deprecated type".to_string(), Severity::Warning),
+                (
+                    "Beta is an experimental type".to_string(),
+                    Severity::Warning
+                ),
+                ("Gone is an unsupported type".to_string(), Severity::Error),
+            ]
+            .into_iter()
+            .collect()
+        );
+    }
+}
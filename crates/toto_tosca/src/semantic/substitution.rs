@@ -0,0 +1,382 @@
+use std::collections::HashSet;
+
+use petgraph::{data::DataMap, visit::EdgeRef, Direction::Outgoing};
+use toto_parser::{add_with_loc, ParseError};
+
+use crate::{codes, MappingEntity, ToscaCompatibleEntity, ToscaCompatibleRelation};
+
+use super::{graph, Derive, Functions, Requirements, Templates, Validate};
+
+/// Substitution mappings. Every mapped name must be defined by the
+/// substituted node type and mapped to what the service template has, and
+/// the required properties and mandatory requirements of the node type must
+/// be mapped.
+pub struct Substitutions;
+
+impl Substitutions {
+    pub fn check_all<E, R>(ast: &mut toto_ast::AST<E, R>)
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mappings = ast
+            .node_indices()
+            .filter_map(|n| {
+                match ast.node_weight(n).unwrap().as_tosca() {
+                    Some(crate::Entity::SubstitutionMapping(_)) => {}
+                    _ => return None,
+                }
+                graph::get_target(n, ast, |rel| {
+                    matches!(rel, crate::Relation::SubstitutesType(_))
+                })
+                .map(|node_type| (n, node_type))
+            })
+            .collect::<Vec<_>>();
+
+        let mut errors = vec![];
+        for (mapping, node_type) in mappings {
+            Self::check(mapping, node_type, ast, &mut errors);
+        }
+
+        for (err, loc) in errors {
+            add_with_loc(err, loc, ast);
+        }
+    }
+
+    fn check<E, R>(
+        mapping: toto_ast::GraphHandle,
+        node_type: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+        errors: &mut Vec<(ParseError, toto_ast::GraphHandle)>,
+    ) where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let type_name = Requirements::get_type_name(node_type, ast);
+        let entries = ast
+            .edges_directed(mapping, Outgoing)
+            .filter_map(|e| {
+                let name = match e.weight().as_tosca()? {
+                    crate::Relation::Definition(crate::DefinitionRelation(name))
+                    | crate::Relation::OrderedDefinition(crate::OrderedDefinitionRelation((
+                        name,
+                        _,
+                    ))) => name.clone(),
+                    _ => return None,
+                };
+                match ast.node_weight(e.target()).unwrap().as_tosca()? {
+                    crate::Entity::Mapping(kind) => Some((name, *kind, e.target())),
+                    _ => None,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut mapped = HashSet::new();
+        for (name, kind, entry) in entries {
+            mapped.insert((kind, name.clone()));
+
+            let definition = match kind {
+                MappingEntity::Property => graph::find_definition(node_type, &name, ast, |e| {
+                    matches!(e, crate::Entity::Property(_))
+                }),
+                MappingEntity::Attribute => graph::find_definition(node_type, &name, ast, |e| {
+                    matches!(e, crate::Entity::Attribute(_))
+                }),
+                MappingEntity::Capability => graph::find_definition(node_type, &name, ast, |e| {
+                    matches!(e, crate::Entity::Capability(_))
+                }),
+                MappingEntity::Requirement => Derive::find_requirement(node_type, &name, ast),
+                MappingEntity::Interface => graph::find_definition(node_type, &name, ast, |e| {
+                    matches!(e, crate::Entity::Interface(_))
+                }),
+                MappingEntity::Operation => continue,
+            };
+            let Some(definition) = definition else {
+                errors.push((
+                    codes::UNKNOWN_MAPPING.with(format!(
+                        "{} has no {} {}",
+                        type_name,
+                        Self::get_kind(kind),
+                        name
+                    )),
+                    entry,
+                ));
+                continue;
+            };
+
+            match kind {
+                MappingEntity::Capability | MappingEntity::Requirement => {
+                    Self::check_node_mapping(entry, kind, ast, errors)
+                }
+                MappingEntity::Interface => {
+                    Self::check_interface_mapping(entry, definition, &name, ast, errors)
+                }
+                _ => {}
+            }
+        }
+
+        let loc = Templates::get_field_loc(mapping, "node_type", ast);
+        let service_template = Functions::find_ancestor(mapping, ast, |e| {
+            matches!(e, crate::Entity::ServiceTemplate(_))
+        });
+        let has_input = |name: &str| {
+            service_template.is_some_and(|st| {
+                ast.edges_directed(st, Outgoing).any(|e| {
+                    matches!(e.weight().as_tosca(), Some(crate::Relation::Input(crate::InputRelation(n))) if n == name)
+                })
+            })
+        };
+
+        let mut properties = ast
+            .edges_directed(node_type, Outgoing)
+            .filter_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::Definition(crate::DefinitionRelation(name)))
+                    if matches!(
+                        ast.node_weight(e.target()).unwrap().as_tosca(),
+                        Some(crate::Entity::Property(_))
+                    ) =>
+                {
+                    Some((name.clone(), e.target()))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        properties.sort();
+        for (name, property) in properties {
+            if Validate::is_required(property, ast)
+                && !mapped.contains(&(MappingEntity::Property, name.clone()))
+                && !has_input(&name)
+            {
+                errors.push((
+                    codes::UNMAPPED_DEFINITION.with(format!(
+                        "required property {} of {} is not mapped",
+                        name, type_name
+                    )),
+                    loc,
+                ));
+            }
+        }
+
+        for (name, requirement) in Self::get_requirements(node_type, ast) {
+            if Self::is_mandatory(requirement, ast)
+                && !mapped.contains(&(MappingEntity::Requirement, name.clone()))
+            {
+                errors.push((
+                    codes::UNMAPPED_DEFINITION.with(format!(
+                        "mandatory requirement {} of {} is not mapped",
+                        name, type_name
+                    )),
+                    loc,
+                ));
+            }
+        }
+    }
+
+    /// The node template of a capability or a requirement mapping must have
+    /// the mapped capability or requirement.
+    fn check_node_mapping<E, R>(
+        entry: toto_ast::GraphHandle,
+        kind: MappingEntity,
+        ast: &toto_ast::AST<E, R>,
+        errors: &mut Vec<(ParseError, toto_ast::GraphHandle)>,
+    ) where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        // An unknown node template is reported by the lookup.
+        let Some(template) = graph::get_target(entry, ast, |rel| {
+            matches!(rel, crate::Relation::TargetNode(_))
+        }) else {
+            return;
+        };
+        let Some(name_handle) = graph::get_target(entry, ast, |rel| {
+            matches!(rel, crate::Relation::MappedName(_))
+        }) else {
+            return;
+        };
+        let Some(name) = toto_yaml::as_string(name_handle, ast).map(|name| name.0.clone()) else {
+            return;
+        };
+
+        let found = match kind {
+            MappingEntity::Capability => Requirements::get_capabilities(template, ast)
+                .iter()
+                .any(|(cap_name, _)| *cap_name == name),
+            _ => graph::get_target(template, ast, |rel| {
+                matches!(rel, crate::Relation::HasType(_))
+            })
+            .and_then(|node_type| Derive::find_requirement(node_type, &name, ast))
+            .is_some(),
+        };
+        if !found {
+            errors.push((
+                codes::UNKNOWN_MAPPING.with(format!(
+                    "node template {} has no {} {}",
+                    Requirements::get_name(template, ast),
+                    Self::get_kind(kind),
+                    name
+                )),
+                name_handle,
+            ));
+        }
+    }
+
+    /// Operations mapped to workflows must be defined by the interface.
+    fn check_interface_mapping<E, R>(
+        entry: toto_ast::GraphHandle,
+        interface: toto_ast::GraphHandle,
+        name: &str,
+        ast: &toto_ast::AST<E, R>,
+        errors: &mut Vec<(ParseError, toto_ast::GraphHandle)>,
+    ) where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        for e in ast.edges_directed(entry, Outgoing) {
+            let Some(crate::Relation::Definition(crate::DefinitionRelation(operation))) =
+                e.weight().as_tosca()
+            else {
+                continue;
+            };
+            if graph::find_definition(interface, operation, ast, |e| {
+                matches!(e, crate::Entity::Operation(_))
+            })
+            .is_none()
+            {
+                errors.push((
+                    codes::UNKNOWN_MAPPING
+                        .with(format!("interface {} has no operation {}", name, operation)),
+                    e.target(),
+                ));
+            }
+        }
+    }
+
+    fn get_kind(kind: MappingEntity) -> &'static str {
+        match kind {
+            MappingEntity::Property => "property",
+            MappingEntity::Attribute => "attribute",
+            MappingEntity::Capability => "capability",
+            MappingEntity::Requirement => "requirement",
+            MappingEntity::Interface => "interface",
+            MappingEntity::Operation => "operation",
+        }
+    }
+
+    /// Requirement definitions of a node type and of the types it is derived
+    /// from, the nearest one of each name.
+    fn get_requirements<E, R>(
+        node_type: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Vec<(String, toto_ast::GraphHandle)>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mut requirements = Vec::<(String, toto_ast::GraphHandle)>::new();
+        let mut visited = HashSet::new();
+        let mut curr = Some(node_type);
+        while let Some(node_type) = curr.filter(|t| visited.insert(*t)) {
+            let mut own = ast
+                .edges_directed(node_type, Outgoing)
+                .filter_map(|e| match e.weight().as_tosca() {
+                    Some(crate::Relation::OrderedDefinition(crate::OrderedDefinitionRelation(
+                        (name, i),
+                    ))) if matches!(
+                        ast.node_weight(e.target()).unwrap().as_tosca(),
+                        Some(crate::Entity::Requirement(_))
+                    ) =>
+                    {
+                        Some((*i, name.clone(), e.target()))
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            own.sort();
+            for (_, name, requirement) in own {
+                if !requirements.iter().any(|(n, _)| *n == name) {
+                    requirements.push((name, requirement));
+                }
+            }
+            curr = graph::get_target(node_type, ast, |rel| {
+                matches!(rel, crate::Relation::DerivedFrom(_))
+            });
+        }
+        requirements
+    }
+
+    /// Whether a requirement needs at least one relationship. `count_range`
+    /// defaults to `[0, UNBOUNDED]`, the grammar fills in the default
+    /// `occurrences` of TOSCA 1.3.
+    fn is_mandatory<E, R>(requirement: toto_ast::GraphHandle, ast: &toto_ast::AST<E, R>) -> bool
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mut visited = HashSet::new();
+        let mut curr = Some(requirement);
+        while let Some(n) = curr.filter(|n| visited.insert(*n)) {
+            let range =
+                graph::get_target(n, ast, |rel| matches!(rel, crate::Relation::CountRange(_)));
+            if let Some(range) = range {
+                return matches!(
+                    ast.node_weight(range).unwrap().as_tosca(),
+                    Some(crate::Entity::Range(crate::RangeEntity { lower: Some(lower), .. })) if *lower > 0
+                );
+            }
+            curr = graph::get_target(n, ast, |rel| matches!(rel, crate::Relation::RefinedFrom(_)));
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grammar::tests::{errors, parse};
+
+    #[test]
+    fn substitution_mappings() {
+        let ast = parse("../tests/tosca_2_0_substitution.yaml");
+
+        assert_eq!(
+            errors(&ast),
+            vec![
+                (
+                    75,
+                    "mandatory requirement network of Database is not mapped".to_string()
+                ),
+                (78, "unknown input: user_name".to_string()),
+                (79, "expected [ input_name ]".to_string()),
+                (80, "Database has no property version".to_string()),
+                (85, "Database has no capability admin".to_string()),
+                (
+                    87,
+                    "node template server has no requirement hots".to_string()
+                ),
+                (
+                    91,
+                    "interface Standard has no operation upgrade".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn substitution_mappings_1_3() {
+        let ast = parse("../tests/tosca_1_3_substitution.yaml");
+
+        assert_eq!(
+            errors(&ast),
+            vec![
+                (
+                    18,
+                    "invalid range: lower bound is greater than the upper bound".to_string()
+                ),
+                (
+                    22,
+                    "mandatory requirement host of App is not mapped".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,574 @@
+use std::collections::{HashMap, HashSet};
+
+use petgraph::{
+    algo::tarjan_scc,
+    data::DataMap,
+    visit::{EdgeFiltered, EdgeRef},
+    Direction::{Incoming, Outgoing},
+};
+use toto_parser::{add_with_loc, ParseError, ParseLoc};
+
+use crate::{codes, ToscaCompatibleEntity, ToscaCompatibleRelation};
+
+use super::{graph, Functions, Requirements};
+
+/// Keynames a node template with the `select` directive can't have, the
+/// selected node comes with its own.
+const SELECTED_FIELDS: &[&str] = &[
+    "properties",
+    "attributes",
+    "capabilities",
+    "requirements",
+    "interfaces",
+    "artifacts",
+];
+
+/// Node template `copy`, `count` and `directives`.
+pub struct Templates;
+
+impl Templates {
+    /// Copies the fields of the node template named by `copy` into the
+    /// copying node template, its own fields take precedence. Copied fields
+    /// are cloned, so that their functions and requirements are resolved for
+    /// the copy, and located at its `copy` keyname.
+    pub fn copy_all<E, R>(ast: &mut toto_ast::AST<E, R>)
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let copy_graph = EdgeFiltered::from_fn(&*ast, |e| {
+            matches!(e.weight().as_tosca(), Some(crate::Relation::Copy(_)))
+        });
+
+        // Components come copied templates first, a copy may be copied again.
+        // Templates of a cycle are reported and left as they are.
+        let mut templates = vec![];
+        let mut errors = vec![];
+        for scc in tarjan_scc(&copy_graph) {
+            let is_cycle = match scc.as_slice() {
+                [template] => ast
+                    .edges_connecting(*template, *template)
+                    .any(|e| matches!(e.weight().as_tosca(), Some(crate::Relation::Copy(_)))),
+                scc => scc.len() > 1,
+            };
+            if !is_cycle {
+                templates.extend(scc);
+                continue;
+            }
+
+            let mut names = scc
+                .iter()
+                .map(|template| (Requirements::get_name(*template, ast), *template))
+                .collect::<Vec<_>>();
+            names.sort();
+            errors.push((
+                codes::CIRCULAR_COPY.with(format!(
+                    "circular copy between node templates {}",
+                    names
+                        .iter()
+                        .map(|(name, _)| name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )),
+                Functions::get_loc(names[0].1, ast),
+            ));
+        }
+
+        for template in templates {
+            if let Some(source) =
+                graph::get_target(template, ast, |rel| matches!(rel, crate::Relation::Copy(_)))
+            {
+                Self::copy(template, source, ast);
+            }
+        }
+
+        for (err, loc) in errors {
+            add_with_loc(err, loc, ast);
+        }
+    }
+
+    fn copy<E, R>(
+        template: toto_ast::GraphHandle,
+        source: toto_ast::GraphHandle,
+        ast: &mut toto_ast::AST<E, R>,
+    ) where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let get_fields = |n: toto_ast::GraphHandle| {
+            ast.edges_directed(n, Outgoing)
+                .filter_map(|e| {
+                    let rel = e.weight().as_tosca()?;
+                    let key = Self::get_key(rel, ast.node_weight(e.target()).unwrap().as_tosca())?;
+                    Some((key, rel.clone(), e.target()))
+                })
+                .collect::<Vec<_>>()
+        };
+        let own = get_fields(template)
+            .into_iter()
+            .map(|(key, _, _)| key)
+            .collect::<HashSet<_>>();
+        let copied = get_fields(source)
+            .into_iter()
+            .filter(|(key, _, _)| !own.contains(key))
+            .collect::<Vec<_>>();
+
+        let copy_loc = Self::get_field_loc(template, "copy", ast);
+        let pos = ast
+            .edges(copy_loc)
+            .find_map(|e| e.weight().as_file().map(|pos| (pos.0, e.target())));
+        for (_, rel, target) in copied {
+            // Fields referring to something else, e.g. the type, are shared.
+            let is_owned =
+                graph::get_target(target, ast, |rel| matches!(rel, crate::Relation::Root(_)))
+                    == Some(source);
+            let target = if is_owned {
+                Self::clone_subtree(target, source, template, pos, ast)
+            } else {
+                target
+            };
+            ast.add_edge(template, target, rel.into());
+        }
+    }
+
+    /// Clones a field along with its children and yaml nodes, the clone
+    /// belongs to `new_parent` and is located at `pos`. Edges leaving the
+    /// field keep their targets.
+    fn clone_subtree<E, R>(
+        root: toto_ast::GraphHandle,
+        old_parent: toto_ast::GraphHandle,
+        new_parent: toto_ast::GraphHandle,
+        pos: Option<(usize, toto_ast::GraphHandle)>,
+        ast: &mut toto_ast::AST<E, R>,
+    ) -> toto_ast::GraphHandle
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mut nodes = vec![root];
+        let mut seen = HashSet::from([root, old_parent]);
+        let mut i = 0;
+        while let Some(n) = nodes.get(i).copied() {
+            i += 1;
+            let children = ast
+                .edges_directed(n, Incoming)
+                .filter(|e| matches!(e.weight().as_tosca(), Some(crate::Relation::Root(_))))
+                .map(|e| e.source())
+                .chain(
+                    ast.edges_directed(n, Outgoing)
+                        .filter(|e| {
+                            e.weight().as_yaml().is_some() || e.weight().as_parse_loc().is_some()
+                        })
+                        .map(|e| e.target()),
+                )
+                .collect::<Vec<_>>();
+            nodes.extend(children.into_iter().filter(|c| seen.insert(*c)));
+        }
+
+        let mut clones = HashMap::new();
+        for n in nodes.iter() {
+            let weight = ast.node_weight(*n).unwrap();
+            let clone = match (weight.as_tosca(), weight.as_yaml()) {
+                (Some(entity), _) => E::from(entity.clone()),
+                (_, Some(entity)) => E::from(entity.clone()),
+                _ => continue,
+            };
+            clones.insert(*n, ast.add_node(clone));
+        }
+
+        for (n, clone) in clones.iter() {
+            let edges = ast
+                .edges_directed(*n, Outgoing)
+                .map(|e| (e.id(), e.target()))
+                .collect::<Vec<_>>();
+            // Edges are listed newest first, keep their order in the clone.
+            for (edge, target) in edges.into_iter().rev() {
+                let weight = ast.edge_weight(edge).unwrap();
+                let (target, rel) = if let Some(rel) = weight.as_tosca() {
+                    let target = match rel {
+                        crate::Relation::Root(_) if target == old_parent => new_parent,
+                        _ => clones.get(&target).copied().unwrap_or(target),
+                    };
+                    (target, R::from(rel.clone()))
+                } else if let Some(rel) = weight.as_yaml() {
+                    (clones[&target], R::from(rel.clone()))
+                } else if weight.as_parse_loc().is_some() {
+                    (
+                        clones.get(&target).copied().unwrap_or(target),
+                        R::from(ParseLoc),
+                    )
+                } else if let Some(file_pos) = weight.as_file() {
+                    let (offset, file) = pos.unwrap_or((file_pos.0, target));
+                    (file, R::from(toto_yaml::FileRelation(offset)))
+                } else {
+                    continue;
+                };
+                ast.add_edge(*clone, target, rel);
+            }
+        }
+
+        clones.get(&root).copied().unwrap_or(root)
+    }
+
+    /// Fields with the same key override each other. Lists, i.e. directives
+    /// and requirements, are overridden as a whole.
+    fn get_key(
+        rel: &crate::Relation,
+        target: Option<&crate::Entity>,
+    ) -> Option<(crate::Relation, Option<crate::Entity>)> {
+        match rel {
+            crate::Relation::Assignment(_)
+            | crate::Relation::Definition(_)
+            | crate::Relation::Metadata(_) => Some((rel.clone(), target.cloned())),
+            crate::Relation::HasType(_)
+            | crate::Relation::Description(_)
+            | crate::Relation::NodeFilter(_)
+            | crate::Relation::Count(_) => Some((rel.clone(), None)),
+            crate::Relation::Directive(_) => {
+                Some((crate::Relation::from(crate::DirectiveRelation(0)), None))
+            }
+            crate::Relation::OrderedAssignment(_) => Some((
+                crate::Relation::from(crate::OrderedAssignmentRelation::default()),
+                None,
+            )),
+            _ => None,
+        }
+    }
+
+    pub fn check_all<E, R>(ast: &mut toto_ast::AST<E, R>)
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mut errors = vec![];
+
+        let counts = ast
+            .edge_references()
+            .filter(|e| matches!(e.weight().as_tosca(), Some(crate::Relation::Count(_))))
+            .map(|e| e.target())
+            .collect::<Vec<_>>();
+        for count in counts {
+            Self::check_count(count, ast, &mut errors);
+        }
+
+        let templates = ast
+            .node_indices()
+            .filter(|n| Requirements::is_node_template(*n, ast))
+            .collect::<Vec<_>>();
+        for template in templates {
+            let directives = Self::get_directives(template, ast);
+            if directives.iter().any(|d| d == "select") {
+                Self::check_select(template, ast, &mut errors);
+            }
+            if directives.iter().any(|d| d == "substitute") {
+                Self::check_substitute(template, ast, &mut errors);
+            }
+        }
+
+        for (err, loc) in errors {
+            add_with_loc(err, loc, ast);
+        }
+    }
+
+    /// Directives of a node template, e.g. `select` or `substitute`.
+    pub fn get_directives<E, R>(
+        template: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Vec<String>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mut directives = ast
+            .edges_directed(template, Outgoing)
+            .filter_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::Directive(crate::DirectiveRelation(i))) => {
+                    toto_yaml::as_string(e.target(), ast).map(|d| (*i, d.0.clone()))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        directives.sort();
+        directives.into_iter().map(|(_, d)| d).collect()
+    }
+
+    /// Whether the nodes of a template are selected or substituted instead
+    /// of created from the template.
+    pub fn is_abstract<E, R>(template: toto_ast::GraphHandle, ast: &toto_ast::AST<E, R>) -> bool
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        Self::get_directives(template, ast)
+            .iter()
+            .any(|d| d == "select" || d == "substitute")
+    }
+
+    fn check_count<E, R>(
+        count: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+        errors: &mut Vec<(ParseError, toto_ast::GraphHandle)>,
+    ) where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let value = match Functions::get_call(count, ast) {
+            Some(call) => match Functions::get_evaluated(call, ast) {
+                Some(value) => value,
+                // Not known until runtime.
+                None => return,
+            },
+            None => count,
+        };
+        if toto_yaml::as_int(value, ast).is_none_or(|count| count.0 < 0) {
+            errors.push((
+                codes::INVALID_COUNT.with("count must be a non-negative integer"),
+                count,
+            ));
+        }
+    }
+
+    /// Only the keynames written in the template are checked, fields copied
+    /// from another template are left alone.
+    fn check_select<E, R>(
+        template: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+        errors: &mut Vec<(ParseError, toto_ast::GraphHandle)>,
+    ) where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let Some(items) = toto_yaml::as_map(Functions::get_loc(template, ast), ast) else {
+            return;
+        };
+        for (k, _) in items {
+            let Some(key) = toto_yaml::as_string(k, ast) else {
+                continue;
+            };
+            if SELECTED_FIELDS.contains(&key.0.as_str()) {
+                errors.push((
+                    codes::SELECT_ASSIGNMENT.with(format!(
+                        "{} can't be assigned to a template with the select directive",
+                        key.0
+                    )),
+                    k,
+                ));
+            }
+        }
+    }
+
+    fn check_substitute<E, R>(
+        template: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+        errors: &mut Vec<(ParseError, toto_ast::GraphHandle)>,
+    ) where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let Some(node_type) = graph::get_target(template, ast, |rel| {
+            matches!(rel, crate::Relation::HasType(_))
+        }) else {
+            return;
+        };
+
+        let substitutes = ast
+            .edge_references()
+            .filter(|e| {
+                matches!(
+                    e.weight().as_tosca(),
+                    Some(crate::Relation::SubstitutesType(_))
+                )
+            })
+            .any(|e| Requirements::is_derived_from(e.target(), node_type, ast));
+        if substitutes {
+            return;
+        }
+
+        errors.push((
+            codes::MISSING_SUBSTITUTION.with(format!(
+                "no substitution mappings for {}",
+                Requirements::get_type_name(node_type, ast)
+            )),
+            Self::get_field_loc(template, "type", ast),
+        ));
+    }
+
+    /// Location of a keyname of the template, or of the template if it
+    /// doesn't have it.
+    pub(crate) fn get_field_loc<E, R>(
+        template: toto_ast::GraphHandle,
+        name: &str,
+        ast: &toto_ast::AST<E, R>,
+    ) -> toto_ast::GraphHandle
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let loc = Functions::get_loc(template, ast);
+        toto_yaml::as_map(loc, ast)
+            .and_then(|mut items| {
+                items.find_map(|(k, _)| {
+                    toto_yaml::as_string(k, ast)
+                        .is_some_and(|k| k.0 == name)
+                        .then_some(k)
+                })
+            })
+            .unwrap_or(loc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::visit::EdgeRef;
+
+    use crate::grammar::tests::{errors, parse, Entity, Relation};
+    use crate::AsToscaRelation;
+
+    use super::super::{graph, Functions};
+
+    fn template(ast: &toto_ast::AST<Entity, Relation>, name: &str) -> toto_ast::GraphHandle {
+        ast.edge_references()
+            .find_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::Definition(crate::DefinitionRelation(n))) if n == name => {
+                    Some(e.target())
+                }
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    fn field(
+        ast: &toto_ast::AST<Entity, Relation>,
+        template: toto_ast::GraphHandle,
+        pred: impl Fn(&crate::Relation) -> bool,
+    ) -> toto_ast::GraphHandle {
+        ast.edges(template)
+            .find(|e| e.weight().as_tosca().is_some_and(&pred))
+            .unwrap()
+            .target()
+    }
+
+    fn address(ast: &toto_ast::AST<Entity, Relation>, name: &str) -> String {
+        let assignment = field(ast, template(ast, name), |rel| {
+            rel == &crate::Relation::from(crate::AssignmentRelation("address".to_string()))
+        });
+        let value = field(ast, assignment, |rel| {
+            matches!(rel, crate::Relation::Value(_))
+        });
+        let call = Functions::get_call(value, ast).unwrap();
+        let evaluated = Functions::get_evaluated(call, ast).unwrap();
+        toto_yaml::as_string(evaluated, ast).unwrap().0.clone()
+    }
+
+    #[test]
+    fn templates() {
+        let ast = parse("../tests/tosca_2_0_templates.yaml");
+
+        assert_eq!(
+            errors(&ast),
+            vec![
+                (66, "count must be a non-negative integer".to_string()),
+                (70, "count must be a non-negative integer".to_string()),
+                (
+                    75,
+                    "properties can't be assigned to a template with the select directive"
+                        .to_string()
+                ),
+                (85, "no substitution mappings for Queue".to_string()),
+            ]
+        );
+
+        // `web_spare` copies `web` through `web_broken`.
+        let web_spare = ast
+            .edge_references()
+            .find_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::Definition(crate::DefinitionRelation(name)))
+                    if name == "web_spare" =>
+                {
+                    Some(e.target())
+                }
+                _ => None,
+            })
+            .unwrap();
+        let copied = ast
+            .edges(web_spare)
+            .filter_map(|e| e.weight().as_tosca())
+            .collect::<Vec<_>>();
+        assert!(copied
+            .iter()
+            .any(|rel| matches!(rel, crate::Relation::HasType(_))));
+        assert!(copied.iter().any(|rel| matches!(
+            rel,
+            crate::Relation::OrderedAssignment(crate::OrderedAssignmentRelation((name, 0))) if name == "database"
+        )));
+    }
+
+    #[test]
+    fn circular_copy() {
+        let ast = parse("../tests/tosca_2_0_copy_cycle.yaml");
+
+        assert_eq!(
+            errors(&ast),
+            vec![(
+                13,
+                "circular copy between node templates backup, web".to_string()
+            )]
+        );
+
+        // Templates outside of the cycle are still copied.
+        let db_replica = ast
+            .edge_references()
+            .find_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::Definition(crate::DefinitionRelation(name)))
+                    if name == "db_replica" =>
+                {
+                    Some(e.target())
+                }
+                _ => None,
+            })
+            .unwrap();
+        let copied = ast
+            .edges(db_replica)
+            .filter_map(|e| e.weight().as_tosca())
+            .collect::<Vec<_>>();
+        assert!(copied
+            .iter()
+            .any(|rel| matches!(rel, crate::Relation::HasType(_))));
+        assert!(copied
+            .iter()
+            .any(|rel| matches!(rel, crate::Relation::Description(_))));
+    }
+
+    #[test]
+    fn copy_in_context() {
+        let ast = parse("../tests/tosca_2_0_copy.yaml");
+
+        // `Static` has no `database` requirement, the copied one is reported
+        // at the copy.
+        assert_eq!(
+            errors(&ast),
+            vec![(68, "unknown requirement: database".to_string())]
+        );
+
+        // `SELF` is the copy, with its own and the copied properties.
+        assert_eq!(address(&ast, "web"), "web:80");
+        assert_eq!(address(&ast, "api"), "api:80");
+        assert_eq!(address(&ast, "assets"), "web:8080");
+
+        // The copied requirement is defined by the type of the copy.
+        let defined_by = |name: &str| {
+            let requirement = field(&ast, template(&ast, name), |rel| {
+                matches!(rel, crate::Relation::OrderedAssignment(_))
+            });
+            graph::get_target(requirement, &ast, |rel| {
+                matches!(rel, crate::Relation::DefinedBy(_))
+            })
+            .unwrap()
+        };
+        let api_type = field(&ast, template(&ast, "api"), |rel| {
+            matches!(rel, crate::Relation::HasType(_))
+        });
+        assert_ne!(defined_by("web"), defined_by("api"));
+        assert!(ast.edges(api_type).any(|e| e.target() == defined_by("api")));
+    }
+}
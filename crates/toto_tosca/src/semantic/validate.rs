@@ -0,0 +1,552 @@
+use std::collections::{BTreeMap, HashSet};
+
+use petgraph::{
+    data::DataMap,
+    visit::EdgeRef,
+    Direction::{Incoming, Outgoing},
+};
+use toto_parser::{add_with_loc, ParseError};
+
+use crate::{codes, ToscaCompatibleEntity, ToscaCompatibleRelation};
+
+use super::{Functions, Literal, Templates, Validation};
+
+const BUILTIN_TYPES: &[&str] = &[
+    "string",
+    "integer",
+    "float",
+    "boolean",
+    "bytes",
+    "nil",
+    "null",
+    "timestamp",
+    "scalar-unit",
+    "scalar-unit.time",
+    "scalar-unit.size",
+    "scalar-unit.frequency",
+    "scalar-unit.bitrate",
+    "version",
+    "range",
+    "list",
+    "map",
+];
+
+/// Data type of a definition, collected along its type derivation chain.
+#[derive(Debug, Default)]
+pub struct DataType {
+    pub builtin: Option<&'static str>,
+    pub key_schema: Option<toto_ast::GraphHandle>,
+    pub entry_schema: Option<toto_ast::GraphHandle>,
+    pub properties: Vec<(String, toto_ast::GraphHandle)>,
+    pub validations: Vec<toto_ast::GraphHandle>,
+}
+
+pub struct Validate;
+
+impl Validate {
+    pub fn validate_all<E, R>(ast: &mut toto_ast::AST<E, R>)
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let assignments = ast
+            .edge_references()
+            .filter(|e| {
+                matches!(e.weight().as_tosca(), Some(crate::Relation::DefinedBy(_)))
+                    && Self::is_value_definition(ast.node_weight(e.source()).unwrap())
+                    && Self::is_value_definition(ast.node_weight(e.target()).unwrap())
+            })
+            .map(|e| (e.source(), e.target()))
+            .collect::<Vec<_>>();
+
+        let mut errors = vec![];
+        let mut literals = vec![];
+        for (assignment, definition) in assignments {
+            let Some(value) = ast
+                .edges_directed(assignment, Outgoing)
+                .find(|e| matches!(e.weight().as_tosca(), Some(crate::Relation::Value(_))))
+                .map(|e| e.target())
+            else {
+                continue;
+            };
+            Self::check_value(value, definition, None, ast, &mut errors, &mut literals);
+        }
+
+        let definitions = ast
+            .edge_references()
+            .filter(|e| {
+                matches!(
+                    e.weight().as_tosca(),
+                    Some(crate::Relation::Default(_) | crate::Relation::Validation(_))
+                ) && (Self::is_value_definition(ast.node_weight(e.source()).unwrap())
+                    || matches!(
+                        ast.node_weight(e.source()).unwrap().as_tosca(),
+                        Some(crate::Entity::Data(_))
+                    ))
+            })
+            .map(|e| {
+                (
+                    e.source(),
+                    e.target(),
+                    e.weight().as_tosca().unwrap().clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        for (definition, target, rel) in definitions {
+            match rel {
+                crate::Relation::Default(_) => {
+                    Self::check_value(target, definition, None, ast, &mut errors, &mut literals);
+                }
+                _ => {
+                    let data_type = Self::get_data_type(definition, ast);
+                    Validation::check(target, &data_type, ast, &mut errors);
+                }
+            }
+        }
+
+        let templates = ast
+            .node_indices()
+            .filter(|n| {
+                Self::is_template(ast.node_weight(*n).unwrap())
+                    && Functions::find_ancestor(*n, ast, |e| {
+                        matches!(e, crate::Entity::ServiceTemplate(_))
+                    })
+                    .is_some()
+            })
+            .collect::<Vec<_>>();
+        for template in templates {
+            // Selected and substituted nodes have their own property values.
+            if Templates::is_abstract(template, ast) {
+                continue;
+            }
+            Self::check_required(template, ast, &mut errors);
+        }
+
+        for (err, loc) in errors {
+            add_with_loc(err, loc, ast);
+        }
+        for (literal, n) in literals {
+            // The same value may be checked against several definitions.
+            if Literal::get(n, ast).is_none() {
+                add_with_loc(literal, n, ast);
+            }
+        }
+    }
+
+    fn is_value_definition<E>(e: &E) -> bool
+    where
+        E: ToscaCompatibleEntity,
+    {
+        matches!(
+            e.as_tosca(),
+            Some(
+                crate::Entity::Property(_)
+                    | crate::Entity::Attribute(_)
+                    | crate::Entity::Parameter(_)
+            )
+        )
+    }
+
+    fn is_template<E>(e: &E) -> bool
+    where
+        E: ToscaCompatibleEntity,
+    {
+        matches!(
+            e.as_tosca(),
+            Some(
+                crate::Entity::Node(_)
+                    | crate::Entity::Relationship(_)
+                    | crate::Entity::Capability(_)
+                    | crate::Entity::Group(_)
+                    | crate::Entity::Policy(_)
+            )
+        )
+    }
+
+    /// Returns the name of the builtin data type `n` is, if any.
+    pub fn get_builtin<E, R>(
+        n: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<&'static str>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        ast.edges_directed(n, Incoming)
+            .filter(|e| {
+                ast.edges_directed(e.source(), Outgoing)
+                    .filter(|f| f.weight().as_file().is_some())
+                    .any(|f| {
+                        ast.node_weight(f.target())
+                            .unwrap()
+                            .as_file()
+                            .is_some_and(|file| file.url.scheme() == "builtin")
+                    })
+            })
+            .find_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::Type(crate::TypeRelation(name))) => {
+                    BUILTIN_TYPES.iter().find(|b| **b == name).copied()
+                }
+                _ => None,
+            })
+    }
+
+    /// Resolves the data type of a definition, a schema or a data type by
+    /// following its `type`, `derived_from` and refinement chain.
+    pub fn get_data_type<E, R>(n: toto_ast::GraphHandle, ast: &toto_ast::AST<E, R>) -> DataType
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mut data_type = DataType::default();
+        let mut visited = HashSet::new();
+        let mut curr = Some(n);
+
+        while let Some(n) = curr {
+            if !visited.insert(n) {
+                break;
+            }
+
+            let is_data = matches!(
+                ast.node_weight(n).unwrap().as_tosca(),
+                Some(crate::Entity::Data(_))
+            );
+            let mut parent = None;
+            let mut refined = None;
+            for e in ast.edges_directed(n, Outgoing) {
+                match e.weight().as_tosca() {
+                    Some(crate::Relation::KeySchema(_)) => {
+                        data_type.key_schema.get_or_insert(e.target());
+                    }
+                    Some(crate::Relation::EntrySchema(_)) => {
+                        data_type.entry_schema.get_or_insert(e.target());
+                    }
+                    Some(crate::Relation::Validation(_)) => {
+                        data_type.validations.push(e.target());
+                    }
+                    Some(crate::Relation::Definition(crate::DefinitionRelation(name)))
+                        if is_data
+                            && matches!(
+                                ast.node_weight(e.target()).unwrap().as_tosca(),
+                                Some(crate::Entity::Property(_))
+                            )
+                            && !data_type.properties.iter().any(|(p, _)| p == name) =>
+                    {
+                        data_type.properties.push((name.clone(), e.target()));
+                    }
+                    Some(crate::Relation::HasType(_) | crate::Relation::DerivedFrom(_)) => {
+                        parent = Some(e.target());
+                    }
+                    Some(crate::Relation::RefinedFrom(_)) => {
+                        refined = Some(e.target());
+                    }
+                    _ => {}
+                }
+            }
+
+            if let Some(builtin) = Self::get_builtin(n, ast) {
+                data_type.builtin = Some(builtin);
+                break;
+            }
+            curr = parent.or(refined);
+        }
+
+        data_type
+    }
+
+    /// Whether a property must be assigned a value, i.e. it is required and
+    /// has neither a default nor a fixed value.
+    pub fn is_required<E, R>(def: toto_ast::GraphHandle, ast: &toto_ast::AST<E, R>) -> bool
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        if Functions::get_definition_value(def, ast).is_some() {
+            return false;
+        }
+
+        let mut curr = Some(def);
+        while let Some(n) = curr {
+            if let Some(required) =
+                ast.edges_directed(n, Outgoing)
+                    .find_map(|e| match e.weight().as_tosca() {
+                        Some(crate::Relation::Required(_)) => toto_yaml::as_bool(e.target(), ast),
+                        _ => None,
+                    })
+            {
+                return required.0;
+            }
+            curr = ast
+                .edges_directed(n, Outgoing)
+                .find(|e| matches!(e.weight().as_tosca(), Some(crate::Relation::RefinedFrom(_))))
+                .map(|e| e.target());
+        }
+        true
+    }
+
+    /// Checks that a template assigns every required property of its type.
+    /// Errors are reported at the `type` key of the template.
+    fn check_required<E, R>(
+        template: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+        errors: &mut Vec<(ParseError, toto_ast::GraphHandle)>,
+    ) where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        // Capability assignments have no type of their own, the properties
+        // come from the capability definition they are assigned to.
+        let sources = std::iter::once(template)
+            .chain(
+                ast.edges_directed(template, Outgoing)
+                    .filter(|e| {
+                        matches!(e.weight().as_tosca(), Some(crate::Relation::DefinedBy(_)))
+                    })
+                    .map(|e| e.target()),
+            )
+            .collect::<Vec<_>>();
+
+        let mut definitions = BTreeMap::new();
+        let mut assigned = HashSet::new();
+        for source in sources {
+            for e in ast.edges_directed(source, Outgoing) {
+                if !matches!(
+                    ast.node_weight(e.target()).unwrap().as_tosca(),
+                    Some(crate::Entity::Property(_))
+                ) {
+                    continue;
+                }
+                match e.weight().as_tosca() {
+                    Some(crate::Relation::Definition(crate::DefinitionRelation(name))) => {
+                        definitions.entry(name.clone()).or_insert(e.target());
+                    }
+                    Some(crate::Relation::Assignment(crate::AssignmentRelation(name)))
+                        if source == template =>
+                    {
+                        assigned.insert(name.clone());
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let loc = Functions::get_loc(template, ast);
+        let loc = toto_yaml::as_map(loc, ast)
+            .and_then(|mut items| {
+                items.find_map(|(k, _)| {
+                    toto_yaml::as_string(k, ast)
+                        .is_some_and(|k| k.0 == "type")
+                        .then_some(k)
+                })
+            })
+            .unwrap_or(loc);
+
+        for (name, def) in definitions {
+            if !assigned.contains(&name) && Self::is_required(def, ast) {
+                errors.push((
+                    codes::MISSING_REQUIRED_PROPERTY
+                        .with(format!("missing required property: {}", name)),
+                    loc,
+                ));
+            }
+        }
+    }
+
+    /// Whether a yaml value has the shape of the data type, or `None` if the
+    /// data type is unknown. Nested values are not checked.
+    pub fn is_instance<E, R>(
+        n: toto_ast::GraphHandle,
+        data_type: &DataType,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<bool>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let value = ast.node_weight(n).unwrap().as_yaml();
+        let is_instance = match data_type.builtin {
+            Some("integer") => matches!(value, Some(toto_yaml::Entity::Int(_))),
+            Some("float") => matches!(
+                value,
+                Some(toto_yaml::Entity::Int(_) | toto_yaml::Entity::Float(_))
+            ),
+            Some("boolean") => matches!(value, Some(toto_yaml::Entity::Bool(_))),
+            Some("nil" | "null") => matches!(value, Some(toto_yaml::Entity::Null(_))),
+            Some("version") => matches!(
+                value,
+                Some(
+                    toto_yaml::Entity::Str(_)
+                        | toto_yaml::Entity::Int(_)
+                        | toto_yaml::Entity::Float(_)
+                )
+            ),
+            Some("range" | "list") => matches!(value, Some(toto_yaml::Entity::List(_))),
+            Some("map") => matches!(value, Some(toto_yaml::Entity::Map(_))),
+            Some(_) => matches!(value, Some(toto_yaml::Entity::Str(_))),
+            None if !data_type.properties.is_empty() => {
+                matches!(value, Some(toto_yaml::Entity::Map(_)))
+            }
+            None => return None,
+        };
+        Some(is_instance)
+    }
+
+    /// Checks a value against the data type of `def`. Errors found in values
+    /// produced by function calls are reported at the call.
+    fn check_value<E, R>(
+        n: toto_ast::GraphHandle,
+        def: toto_ast::GraphHandle,
+        loc: Option<toto_ast::GraphHandle>,
+        ast: &toto_ast::AST<E, R>,
+        errors: &mut Vec<(ParseError, toto_ast::GraphHandle)>,
+        literals: &mut Vec<(crate::Entity, toto_ast::GraphHandle)>,
+    ) where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let (n, loc) = match Functions::get_call(n, ast) {
+            Some(call) => match Functions::get_evaluated(call, ast) {
+                Some(value) => (value, Some(loc.unwrap_or(n))),
+                // Not known until runtime.
+                None => return,
+            },
+            None => (n, loc),
+        };
+        let at = loc.unwrap_or(n);
+        let data_type = Self::get_data_type(def, ast);
+
+        match Self::is_instance(n, &data_type, ast) {
+            Some(true) => {}
+            Some(false) => {
+                errors.push((
+                    ParseError::UnexpectedType(data_type.builtin.unwrap_or("map")),
+                    at,
+                ));
+                return;
+            }
+            // Unknown type, already reported by the lookup.
+            None => return,
+        }
+
+        match data_type
+            .builtin
+            .and_then(|builtin| Literal::parse(builtin, n, ast))
+        {
+            Some(Ok(literal)) => literals.push((literal, n)),
+            Some(Err(err)) => {
+                errors.push((err, at));
+                return;
+            }
+            None => {}
+        }
+
+        match data_type.builtin {
+            Some("list") => {
+                if let Some(entry_schema) = data_type.entry_schema {
+                    for (_, item) in toto_yaml::as_list(n, ast).unwrap() {
+                        Self::check_value(item, entry_schema, loc, ast, errors, literals);
+                    }
+                }
+            }
+            Some("map") => {
+                for (k, v) in toto_yaml::as_map(n, ast).unwrap() {
+                    if let Some(key_schema) = data_type.key_schema {
+                        Self::check_value(k, key_schema, loc, ast, errors, literals);
+                    }
+                    if let Some(entry_schema) = data_type.entry_schema {
+                        Self::check_value(v, entry_schema, loc, ast, errors, literals);
+                    }
+                }
+            }
+            None => {
+                let mut assigned = HashSet::new();
+                for (k, v) in toto_yaml::as_map(n, ast).unwrap() {
+                    let name = toto_yaml::as_string(k, ast)
+                        .map(|name| name.0.clone())
+                        .unwrap_or_default();
+                    let Some((_, property)) = data_type.properties.iter().find(|(p, _)| *p == name)
+                    else {
+                        errors.push((ParseError::UnknownField(name), loc.unwrap_or(k)));
+                        continue;
+                    };
+                    assigned.insert(name);
+                    Self::check_value(v, *property, loc, ast, errors, literals);
+                }
+
+                for (name, property) in data_type.properties.iter() {
+                    if !assigned.contains(name) && Self::is_required(*property, ast) {
+                        errors.push((
+                            codes::MISSING_REQUIRED_PROPERTY
+                                .with(format!("missing required property: {}", name)),
+                            at,
+                        ));
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        for clause in data_type.validations.iter() {
+            if Validation::evaluate(*clause, n, data_type.builtin, ast) == Some(false) {
+                errors.push((
+                    codes::CONSTRAINT_VIOLATION.with(format!(
+                        "value does not satisfy {}",
+                        Validation::get_name(*clause, ast)
+                    )),
+                    at,
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grammar::tests::{errors, parse};
+
+    #[test]
+    fn validate() {
+        let ast = parse("../tests/tosca_2_0_values.yaml");
+
+        let errors = errors(&ast);
+        assert_eq!(
+            errors
+                .iter()
+                .map(|(line, err)| (*line, err.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (71, "expected string"),
+                (72, "expected integer"),
+                (73, "expected float"),
+                (74, "expected boolean"),
+                (75, "expected string"),
+                (76, "expected integer"),
+                (77, "expected string"),
+                (78, "missing required property: user"),
+                (78, "unsupported field"),
+                (79, "expected map"),
+                (81, "expected string"),
+            ]
+        );
+    }
+
+    #[test]
+    fn required() {
+        let ast = parse("../tests/tosca_2_0_required.yaml");
+
+        let errors = errors(&ast);
+        assert_eq!(
+            errors
+                .iter()
+                .map(|(line, err)| (*line, err.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (58, "missing required property: legacy"),
+                (58, "missing required property: name"),
+                (60, "missing required property: port"),
+                (65, "missing required property: timeout"),
+                (69, "missing required property: zone"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,685 @@
+use std::cmp::Ordering;
+
+use toto_parser::ParseError;
+
+use crate::{
+    codes, ScalarUnitEntity, TimestampEntity, ToscaCompatibleEntity, ToscaCompatibleRelation,
+    Version,
+};
+
+use super::{DataType, Functions, Literal, Validate};
+
+/// Functions a TOSCA 2.0 validation clause may consist of.
+const BOOLEAN_FUNCTIONS: &[&str] = &[
+    "equal",
+    "greater_than",
+    "greater_or_equal",
+    "less_than",
+    "less_or_equal",
+    "in_range",
+    "valid_values",
+    "matches",
+    "has_suffix",
+    "has_prefix",
+    "contains",
+    "has_entry",
+    "has_key",
+    "has_all_entries",
+    "has_all_keys",
+    "has_any_entry",
+    "has_any_key",
+    "and",
+    "or",
+    "not",
+    "xor",
+];
+
+/// TOSCA 1.3 constraint operators.
+const CONSTRAINTS: &[&str] = &[
+    "equal",
+    "greater_than",
+    "greater_or_equal",
+    "less_than",
+    "less_or_equal",
+    "in_range",
+    "valid_values",
+    "length",
+    "min_length",
+    "max_length",
+    "pattern",
+    "schema",
+];
+
+const ORDERED_TYPES: &[&str] = &[
+    "integer",
+    "float",
+    "string",
+    "timestamp",
+    "version",
+    "scalar-unit",
+    "scalar-unit.time",
+    "scalar-unit.size",
+    "scalar-unit.frequency",
+    "scalar-unit.bitrate",
+];
+
+#[derive(Debug, Clone)]
+enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    List(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+    ScalarUnit(ScalarUnitEntity),
+    Version(Version),
+    Timestamp(TimestampEntity),
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Null, Self::Null) => true,
+            (Self::Bool(a), Self::Bool(b)) => a == b,
+            (Self::Str(a), Self::Str(b)) => a == b,
+            (Self::List(a), Self::List(b)) => a == b,
+            (Self::Map(a), Self::Map(b)) => {
+                a.len() == b.len() && a.iter().all(|entry| b.contains(entry))
+            }
+            (a, b) => a.partial_cmp(b) == Some(Ordering::Equal),
+        }
+    }
+}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Int(a), Self::Int(b)) => a.partial_cmp(b),
+            (Self::Int(a), Self::Float(b)) => (*a as f64).partial_cmp(b),
+            (Self::Float(a), Self::Int(b)) => a.partial_cmp(&(*b as f64)),
+            (Self::Float(a), Self::Float(b)) => a.partial_cmp(b),
+            (Self::Str(a), Self::Str(b)) => a.partial_cmp(b),
+            (Self::ScalarUnit(a), Self::ScalarUnit(b)) => match (a, b) {
+                (ScalarUnitEntity::Size(a, _), ScalarUnitEntity::Size(b, _))
+                | (ScalarUnitEntity::Time(a, _), ScalarUnitEntity::Time(b, _))
+                | (ScalarUnitEntity::Frequency(a, _), ScalarUnitEntity::Frequency(b, _))
+                | (ScalarUnitEntity::Bitrate(a, _), ScalarUnitEntity::Bitrate(b, _)) => {
+                    a.partial_cmp(b)
+                }
+                _ => None,
+            },
+            (Self::Version(a), Self::Version(b)) => (
+                a.major,
+                a.minor,
+                a.fix,
+                &a.qualifier,
+                a.build,
+            )
+                .partial_cmp(&(b.major, b.minor, b.fix, &b.qualifier, b.build)),
+            (Self::Timestamp(a), Self::Timestamp(b)) => {
+                (a.seconds, a.nanos).partial_cmp(&(b.seconds, b.nanos))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Value {
+    fn len(&self) -> Option<usize> {
+        match self {
+            Self::Str(s) => Some(s.chars().count()),
+            Self::List(items) => Some(items.len()),
+            Self::Map(items) => Some(items.len()),
+            _ => None,
+        }
+    }
+
+    fn entries(&self) -> Option<Vec<&Value>> {
+        match self {
+            Self::List(items) => Some(items.iter().collect()),
+            Self::Map(items) => Some(items.iter().map(|(_, v)| v).collect()),
+            _ => None,
+        }
+    }
+
+    fn keys(&self) -> Option<Vec<&Value>> {
+        match self {
+            Self::Map(items) => Some(items.iter().map(|(k, _)| k).collect()),
+            _ => None,
+        }
+    }
+}
+
+pub struct Validation;
+
+impl Validation {
+    /// Name of the operator of a validation clause or a TOSCA 1.3 constraint.
+    pub fn get_name<E, R>(clause: toto_ast::GraphHandle, ast: &toto_ast::AST<E, R>) -> String
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        match Functions::get_call(clause, ast) {
+            Some(call) => format!("${}", Functions::get_name(call, ast)),
+            None => Self::get_constraint(clause, ast)
+                .map(|(name, _)| name)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Checks that a validation clause or a TOSCA 1.3 constraint is well-formed
+    /// and applicable to values of the data type.
+    pub fn check<E, R>(
+        clause: toto_ast::GraphHandle,
+        data_type: &DataType,
+        ast: &toto_ast::AST<E, R>,
+        errors: &mut Vec<(ParseError, toto_ast::GraphHandle)>,
+    ) where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        match Functions::get_call(clause, ast) {
+            Some(call) => {
+                let name = Functions::get_name(call, ast);
+                if !BOOLEAN_FUNCTIONS.contains(&name.as_str()) {
+                    errors.push((
+                        codes::INVALID_VALIDATION_CLAUSE
+                            .with("validation clause must be a boolean function"),
+                        clause,
+                    ));
+                    return;
+                }
+                Self::check_call(call, data_type, ast, errors);
+            }
+            None => Self::check_constraint(clause, data_type, ast, errors),
+        }
+    }
+
+    /// Checks that a condition, such as a node filter, is a well-formed call
+    /// of a boolean function.
+    pub fn check_condition<E, R>(
+        condition: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+        errors: &mut Vec<(ParseError, toto_ast::GraphHandle)>,
+    ) where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        match Functions::get_call(condition, ast) {
+            Some(call) if BOOLEAN_FUNCTIONS.contains(&Functions::get_name(call, ast).as_str()) => {
+                Self::check_call(call, &DataType::default(), ast, errors)
+            }
+            _ => errors.push((
+                codes::INVALID_CONDITION.with("condition must be a boolean function"),
+                condition,
+            )),
+        }
+    }
+
+    /// Evaluates a validation clause or a TOSCA 1.3 constraint against a
+    /// value. Returns `None` if the clause cannot be evaluated statically.
+    pub fn evaluate<E, R>(
+        clause: toto_ast::GraphHandle,
+        value: toto_ast::GraphHandle,
+        builtin: Option<&str>,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<bool>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let value = Self::eval(value, &Value::Null, builtin, ast)?;
+
+        let result = match Functions::get_call(clause, ast) {
+            Some(_) => Self::eval(clause, &value, builtin, ast)?,
+            None => {
+                let (name, operand) = Self::get_constraint(clause, ast)?;
+                let operand = Self::eval(operand, &value, builtin, ast)?;
+                match name.as_str() {
+                    "length" => Self::apply("equal", &[Value::Int(value.len()? as i64), operand])?,
+                    "min_length" => Self::apply(
+                        "greater_or_equal",
+                        &[Value::Int(value.len()? as i64), operand],
+                    )?,
+                    "max_length" => {
+                        Self::apply("less_or_equal", &[Value::Int(value.len()? as i64), operand])?
+                    }
+                    "pattern" => Self::apply("matches", &[value, operand])?,
+                    "schema" => return None,
+                    name => Self::apply(name, &[value, operand])?,
+                }
+            }
+        };
+
+        match result {
+            Value::Bool(result) => Some(result),
+            _ => None,
+        }
+    }
+
+    fn get_constraint<E, R>(
+        clause: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<(String, toto_ast::GraphHandle)>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mut items = toto_yaml::as_map(clause, ast)?;
+        let (k, v) = items.next()?;
+        if items.next().is_some() {
+            return None;
+        }
+        toto_yaml::as_string(k, ast).map(|k| (k.0.clone(), v))
+    }
+
+    fn is_value_call<E, R>(n: toto_ast::GraphHandle, ast: &toto_ast::AST<E, R>) -> bool
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        Functions::get_call(n, ast).is_some_and(|call| {
+            Functions::get_name(call, ast) == "value" && Functions::get_args(call, ast).is_empty()
+        })
+    }
+
+    fn check_call<E, R>(
+        call: toto_ast::GraphHandle,
+        data_type: &DataType,
+        ast: &toto_ast::AST<E, R>,
+        errors: &mut Vec<(ParseError, toto_ast::GraphHandle)>,
+    ) where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let name = Functions::get_name(call, ast);
+        let args = Functions::get_args(call, ast);
+        let loc = Functions::get_loc(call, ast);
+
+        let arity = match name.as_str() {
+            "value" => return,
+            "not" | "length" => Some(1),
+            "and" | "or" => None,
+            "equal" | "greater_than" | "greater_or_equal" | "less_than" | "less_or_equal"
+            | "in_range" | "valid_values" | "matches" | "has_suffix" | "has_prefix"
+            | "contains" | "has_entry" | "has_key" | "has_all_entries" | "has_all_keys"
+            | "has_any_entry" | "has_any_key" | "xor" => Some(2),
+            // Evaluated by the functions pass, if at all.
+            _ => return,
+        };
+        match arity {
+            Some(arity) if args.len() != arity => {
+                errors.push((
+                    codes::ARGUMENT_COUNT.with(format!(
+                        "${} expects {} argument{}",
+                        name,
+                        arity,
+                        if arity == 1 { "" } else { "s" }
+                    )),
+                    loc,
+                ));
+                return;
+            }
+            None if args.is_empty() => {
+                errors.push((
+                    codes::ARGUMENT_COUNT.with(format!("${} expects arguments", name)),
+                    loc,
+                ));
+                return;
+            }
+            _ => {}
+        }
+
+        for arg in args.iter() {
+            if Functions::get_call(*arg, ast).is_some() {
+                Self::check_call(
+                    Functions::get_call(*arg, ast).unwrap(),
+                    data_type,
+                    ast,
+                    errors,
+                );
+            }
+        }
+
+        let operand = match (
+            args.first()
+                .is_some_and(|arg| Self::is_value_call(*arg, ast)),
+            args.get(1),
+        ) {
+            (true, Some(operand)) if Functions::get_call(*operand, ast).is_none() => *operand,
+            _ => return,
+        };
+        Self::check_operand(&name, operand, data_type, loc, ast, errors);
+    }
+
+    fn check_constraint<E, R>(
+        clause: toto_ast::GraphHandle,
+        data_type: &DataType,
+        ast: &toto_ast::AST<E, R>,
+        errors: &mut Vec<(ParseError, toto_ast::GraphHandle)>,
+    ) where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let Some((name, operand)) = Self::get_constraint(clause, ast) else {
+            errors.push((
+                ParseError::UnexpectedType("map with a single constraint"),
+                clause,
+            ));
+            return;
+        };
+        if !CONSTRAINTS.contains(&name.as_str()) {
+            errors.push((
+                codes::UNKNOWN_CONSTRAINT.with(format!("unknown constraint: {}", name)),
+                clause,
+            ));
+            return;
+        }
+
+        match name.as_str() {
+            "length" | "min_length" | "max_length" => {
+                if toto_yaml::as_int(operand, ast).is_none_or(|length| length.0 < 0) {
+                    errors.push((ParseError::UnexpectedType("non-negative integer"), operand));
+                } else if matches!(
+                    data_type.builtin,
+                    Some(builtin) if !matches!(builtin, "string" | "list" | "map")
+                ) {
+                    errors.push((Self::not_applicable(&name, data_type), clause));
+                }
+            }
+            "pattern" => Self::check_operand("matches", operand, data_type, clause, ast, errors),
+            "schema" => {}
+            _ => Self::check_operand(&name, operand, data_type, clause, ast, errors),
+        }
+    }
+
+    /// Checks the second operand of an operator applied to the value.
+    fn check_operand<E, R>(
+        name: &str,
+        operand: toto_ast::GraphHandle,
+        data_type: &DataType,
+        loc: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+        errors: &mut Vec<(ParseError, toto_ast::GraphHandle)>,
+    ) where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let applicable: &[&str] = match name {
+            "greater_than" | "greater_or_equal" | "less_than" | "less_or_equal" | "in_range" => {
+                ORDERED_TYPES
+            }
+            "matches" => &["string"],
+            "has_key" | "has_all_keys" | "has_any_key" => &["map"],
+            "has_entry" | "has_all_entries" | "has_any_entry" => &["list", "map"],
+            "has_suffix" | "has_prefix" | "contains" => &["string", "list"],
+            _ => &[],
+        };
+        if !applicable.is_empty()
+            && data_type
+                .builtin
+                .is_some_and(|builtin| !applicable.contains(&builtin))
+        {
+            errors.push((Self::not_applicable(name, data_type), loc));
+            return;
+        }
+
+        let mut check_instance = |n: toto_ast::GraphHandle| {
+            if Validate::is_instance(n, data_type, ast) == Some(false) {
+                errors.push((
+                    ParseError::UnexpectedType(data_type.builtin.unwrap_or("map")),
+                    n,
+                ));
+            } else if let Some(Err(err)) = data_type
+                .builtin
+                .and_then(|builtin| Literal::parse(builtin, n, ast))
+            {
+                errors.push((err, n));
+            }
+        };
+
+        match name {
+            "equal" | "greater_than" | "greater_or_equal" | "less_than" | "less_or_equal" => {
+                check_instance(operand)
+            }
+            "in_range" => {
+                let bounds = toto_yaml::as_list(operand, ast)
+                    .map(|items| items.map(|(_, item)| item).collect::<Vec<_>>());
+                match bounds {
+                    Some(bounds) if bounds.len() == 2 => {
+                        for bound in bounds {
+                            if toto_yaml::as_string(bound, ast)
+                                .is_some_and(|bound| bound.0 == "UNBOUNDED")
+                            {
+                                continue;
+                            }
+                            check_instance(bound);
+                        }
+                    }
+                    _ => errors.push((ParseError::UnexpectedType("list of two bounds"), operand)),
+                }
+            }
+            "valid_values" => match toto_yaml::as_list(operand, ast) {
+                Some(items) => items.for_each(|(_, item)| check_instance(item)),
+                None => errors.push((ParseError::UnexpectedType("list"), operand)),
+            },
+            "matches" => match toto_yaml::as_string(operand, ast) {
+                Some(pattern) => {
+                    if let Err(err) = regex::Regex::new(&pattern.0) {
+                        // The syntax error of `regex` spans several lines and
+                        // points at the pattern, keep only its kind.
+                        let reason = match regex_syntax::parse(&pattern.0) {
+                            Err(regex_syntax::Error::Parse(err)) => err.kind().to_string(),
+                            Err(regex_syntax::Error::Translate(err)) => err.kind().to_string(),
+                            _ => err.to_string(),
+                        };
+                        errors.push((
+                            codes::INVALID_REGEX
+                                .with(format!("invalid regular expression: {}", reason)),
+                            operand,
+                        ));
+                    }
+                }
+                None => errors.push((ParseError::UnexpectedType("string"), operand)),
+            },
+            _ => {}
+        }
+    }
+
+    fn not_applicable(name: &str, data_type: &DataType) -> ParseError {
+        codes::INAPPLICABLE_CONSTRAINT.with(format!(
+            "{} is not applicable to {}",
+            name,
+            data_type.builtin.unwrap_or("map")
+        ))
+    }
+
+    /// Evaluates an expression where `$value` refers to `value`. Literals are
+    /// parsed as values of `builtin` where it has a structured representation.
+    fn eval<E, R>(
+        n: toto_ast::GraphHandle,
+        value: &Value,
+        builtin: Option<&str>,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Option<Value>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        if let Some(call) = Functions::get_call(n, ast) {
+            let name = Functions::get_name(call, ast);
+            if name == "value" {
+                return Functions::get_args(call, ast).into_iter().try_fold(
+                    value.clone(),
+                    |curr, segment| match (curr, Self::eval(segment, value, None, ast)?) {
+                        (Value::List(items), Value::Int(idx)) => {
+                            items.get(usize::try_from(idx).ok()?).cloned()
+                        }
+                        (Value::Map(items), key) => {
+                            items.into_iter().find(|(k, _)| *k == key).map(|(_, v)| v)
+                        }
+                        _ => None,
+                    },
+                );
+            }
+            if let Some(evaluated) = Functions::get_evaluated(call, ast) {
+                return Self::eval(evaluated, value, builtin, ast);
+            }
+            let args = Functions::get_args(call, ast)
+                .into_iter()
+                .map(|arg| Self::eval(arg, value, builtin, ast))
+                .collect::<Option<Vec<_>>>()?;
+            return Self::apply(&name, &args);
+        }
+
+        match builtin.and_then(|builtin| Literal::parse(builtin, n, ast)) {
+            Some(Ok(crate::Entity::ScalarUnit(v))) => return Some(Value::ScalarUnit(v)),
+            Some(Ok(crate::Entity::Version(v))) => return Some(Value::Version(v)),
+            Some(Ok(crate::Entity::Timestamp(v))) => return Some(Value::Timestamp(v)),
+            _ => {}
+        }
+
+        match ast.node_weight(n).unwrap().as_yaml()? {
+            toto_yaml::Entity::Null(_) => Some(Value::Null),
+            toto_yaml::Entity::Bool(v) => Some(Value::Bool(v.0)),
+            toto_yaml::Entity::Int(v) => Some(Value::Int(v.0)),
+            toto_yaml::Entity::Float(v) => Some(Value::Float(v.0)),
+            toto_yaml::Entity::Str(v) => Some(Value::Str(v.0.clone())),
+            toto_yaml::Entity::List(_) => {
+                let mut items = toto_yaml::as_list(n, ast)?.collect::<Vec<_>>();
+                items.sort_by_key(|(i, _)| *i);
+                items
+                    .into_iter()
+                    .map(|(_, item)| Self::eval(item, value, builtin, ast))
+                    .collect::<Option<Vec<_>>>()
+                    .map(Value::List)
+            }
+            toto_yaml::Entity::Map(_) => toto_yaml::as_map(n, ast)?
+                .map(|(k, v)| {
+                    Some((
+                        Self::eval(k, value, builtin, ast)?,
+                        Self::eval(v, value, builtin, ast)?,
+                    ))
+                })
+                .collect::<Option<Vec<_>>>()
+                .map(Value::Map),
+        }
+    }
+
+    fn apply(name: &str, args: &[Value]) -> Option<Value> {
+        let result = match (name, args) {
+            ("not", [Value::Bool(a)]) => !a,
+            ("and", args) => args.iter().try_fold(true, |acc, arg| match arg {
+                Value::Bool(arg) => Some(acc && *arg),
+                _ => None,
+            })?,
+            ("or", args) => args.iter().try_fold(false, |acc, arg| match arg {
+                Value::Bool(arg) => Some(acc || *arg),
+                _ => None,
+            })?,
+            ("xor", [Value::Bool(a), Value::Bool(b)]) => a != b,
+            ("equal", [a, b]) => a == b,
+            ("greater_than", [a, b]) => a.partial_cmp(b)? == Ordering::Greater,
+            ("greater_or_equal", [a, b]) => a.partial_cmp(b)? != Ordering::Less,
+            ("less_than", [a, b]) => a.partial_cmp(b)? == Ordering::Less,
+            ("less_or_equal", [a, b]) => a.partial_cmp(b)? != Ordering::Greater,
+            ("in_range", [v, Value::List(bounds)]) => {
+                let [lower, upper] = bounds.as_slice() else {
+                    return None;
+                };
+                let unbounded = |bound: &Value| matches!(bound, Value::Str(s) if s == "UNBOUNDED");
+                (unbounded(lower) || v.partial_cmp(lower)? != Ordering::Less)
+                    && (unbounded(upper) || v.partial_cmp(upper)? != Ordering::Greater)
+            }
+            ("valid_values", [v, Value::List(values)]) => values.contains(v),
+            ("matches", [Value::Str(s), Value::Str(pattern)]) => {
+                regex::Regex::new(&format!("^(?:{})$", pattern))
+                    .ok()?
+                    .is_match(s)
+            }
+            ("has_prefix", [Value::Str(s), Value::Str(prefix)]) => s.starts_with(prefix.as_str()),
+            ("has_prefix", [Value::List(items), Value::List(prefix)]) => items.starts_with(prefix),
+            ("has_suffix", [Value::Str(s), Value::Str(suffix)]) => s.ends_with(suffix.as_str()),
+            ("has_suffix", [Value::List(items), Value::List(suffix)]) => items.ends_with(suffix),
+            ("contains", [Value::Str(s), Value::Str(part)]) => s.contains(part.as_str()),
+            ("contains", [Value::List(items), Value::List(part)]) => {
+                part.is_empty() || items.windows(part.len()).any(|window| window == part)
+            }
+            ("has_entry", [collection, entry]) => collection.entries()?.contains(&entry),
+            ("has_all_entries", [collection, Value::List(entries)]) => {
+                let existing = collection.entries()?;
+                entries.iter().all(|entry| existing.contains(&entry))
+            }
+            ("has_any_entry", [collection, Value::List(entries)]) => {
+                let existing = collection.entries()?;
+                entries.iter().any(|entry| existing.contains(&entry))
+            }
+            ("has_key", [map, key]) => map.keys()?.contains(&key),
+            ("has_all_keys", [map, Value::List(keys)]) => {
+                let existing = map.keys()?;
+                keys.iter().all(|key| existing.contains(&key))
+            }
+            ("has_any_key", [map, Value::List(keys)]) => {
+                let existing = map.keys()?;
+                keys.iter().any(|key| existing.contains(&key))
+            }
+            ("length", [v]) => return Some(Value::Int(v.len()? as i64)),
+            _ => return None,
+        };
+        Some(Value::Bool(result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grammar::tests::{errors, parse};
+
+    #[test]
+    fn validation() {
+        let ast = parse("../tests/tosca_2_0_validation.yaml");
+
+        let errors = errors(&ast);
+        assert_eq!(
+            errors
+                .iter()
+                .map(|(line, err)| (*line, err.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (29, "value does not satisfy $greater_than"),
+                (46, "$greater_than expects 2 arguments"),
+                (50, "expected integer"),
+                (54, "invalid regular expression: unclosed group"),
+                (58, "validation clause must be a boolean function"),
+                (62, "greater_than is not applicable to boolean"),
+                (77, "value does not satisfy $and"),
+                (78, "value does not satisfy $valid_values"),
+                (79, "value does not satisfy $has_entry"),
+                (79, "value does not satisfy $not"),
+                (80, "value does not satisfy $in_range"),
+                (80, "value does not satisfy $matches"),
+            ]
+        );
+    }
+
+    #[test]
+    fn constraints() {
+        let ast = parse("../tests/tosca_1_3_constraints.yaml");
+
+        let errors = errors(&ast);
+        assert_eq!(
+            errors
+                .iter()
+                .map(|(line, err)| (*line, err.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (23, "expected integer"),
+                (24, "unknown constraint: between"),
+                (25, "expected map with a single constraint"),
+                (38, "value does not satisfy max_length"),
+                (38, "value does not satisfy pattern"),
+                (39, "value does not satisfy valid_values"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,402 @@
+use std::collections::HashSet;
+
+use petgraph::{
+    algo::tarjan_scc,
+    data::DataMap,
+    visit::{Dfs, EdgeFiltered, EdgeRef},
+    Direction::{Incoming, Outgoing},
+};
+use toto_parser::{add_with_loc, ParseError};
+
+use crate::{codes, ToscaCompatibleEntity, ToscaCompatibleRelation};
+
+use super::{graph, Functions, Requirements};
+
+/// States of the node lifecycle, `set_state` moves a node to one of them.
+pub const NODE_STATES: &[&str] = &[
+    "initial",
+    "creating",
+    "created",
+    "configuring",
+    "configured",
+    "starting",
+    "started",
+    "stopping",
+    "deleting",
+    "deleted",
+    "error",
+];
+
+/// Imperative workflows. The steps and their `on_success` and `on_failure`
+/// transitions form a control-flow graph, which must be acyclic and
+/// reachable from the initial steps. Activities are resolved against the
+/// step's target node template.
+pub struct Workflows;
+
+impl Workflows {
+    pub fn check_all<E, R>(ast: &mut toto_ast::AST<E, R>)
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mut errors = vec![];
+
+        let workflows = ast
+            .node_indices()
+            .filter(|n| {
+                matches!(
+                    ast.node_weight(*n).unwrap().as_tosca(),
+                    Some(crate::Entity::Workflow(_))
+                )
+            })
+            .collect::<Vec<_>>();
+        let cyclic = Self::check_cycles(ast, &mut errors);
+        for workflow in workflows {
+            Self::check_reachable(workflow, &cyclic, ast, &mut errors);
+        }
+
+        let activities = ast
+            .edge_references()
+            .filter(|e| {
+                matches!(
+                    e.weight().as_tosca(),
+                    Some(crate::Relation::WorkflowActivity(_))
+                )
+            })
+            .map(|e| (e.source(), e.target()))
+            .collect::<Vec<_>>();
+        let mut operations = vec![];
+        for (step, activity) in activities {
+            match ast.node_weight(activity).unwrap().as_tosca() {
+                Some(crate::Entity::WorkflowCallOperationActivity(_)) => {
+                    if let Some(operation) =
+                        Self::resolve_operation(step, activity, ast, &mut errors)
+                    {
+                        operations.push((activity, operation));
+                    }
+                }
+                Some(crate::Entity::WorkflowSetStateActivity(_)) => {
+                    Self::check_state(activity, ast, &mut errors);
+                }
+                _ => {}
+            }
+        }
+
+        for (activity, operation) in operations {
+            ast.add_edge(
+                activity,
+                operation,
+                crate::Relation::from(crate::DefinedByRelation).into(),
+            );
+        }
+        for (err, loc) in errors {
+            add_with_loc(err, loc, ast);
+        }
+    }
+
+    /// Steps of a workflow by name.
+    pub fn get_steps<E, R>(
+        workflow: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Vec<(String, toto_ast::GraphHandle)>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let mut steps = ast
+            .edges_directed(workflow, Outgoing)
+            .filter_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::Definition(crate::DefinitionRelation(name)))
+                    if Self::is_step(e.target(), ast) =>
+                {
+                    Some((name.clone(), e.target()))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        steps.sort();
+        steps
+    }
+
+    /// Steps run after a step, on its success or on its failure.
+    pub fn get_next<E, R>(
+        step: toto_ast::GraphHandle,
+        on_failure: bool,
+        ast: &toto_ast::AST<E, R>,
+    ) -> Vec<toto_ast::GraphHandle>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        ast.edges_directed(step, Outgoing)
+            .filter(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::OnSuccess(_)) => !on_failure,
+                Some(crate::Relation::OnFailure(_)) => on_failure,
+                _ => false,
+            })
+            .map(|e| e.target())
+            .collect()
+    }
+
+    fn is_step<E, R>(n: toto_ast::GraphHandle, ast: &toto_ast::AST<E, R>) -> bool
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        matches!(
+            ast.node_weight(n).unwrap().as_tosca(),
+            Some(crate::Entity::WorkflowStep(_))
+        )
+    }
+
+    fn is_transition(rel: Option<&crate::Relation>) -> bool {
+        matches!(
+            rel,
+            Some(crate::Relation::OnSuccess(_) | crate::Relation::OnFailure(_))
+        )
+    }
+
+    /// Reports each cycle once, at its first step by name, and returns the
+    /// steps of all the cycles.
+    fn check_cycles<E, R>(
+        ast: &toto_ast::AST<E, R>,
+        errors: &mut Vec<(ParseError, toto_ast::GraphHandle)>,
+    ) -> HashSet<toto_ast::GraphHandle>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let step_graph = EdgeFiltered::from_fn(ast, |e| Self::is_transition(e.weight().as_tosca()));
+
+        let mut cyclic = HashSet::new();
+        for scc in tarjan_scc(&step_graph) {
+            let is_cycle = match scc.as_slice() {
+                [step] => ast
+                    .edges_connecting(*step, *step)
+                    .any(|e| Self::is_transition(e.weight().as_tosca())),
+                steps => steps.len() > 1,
+            };
+            if !is_cycle {
+                continue;
+            }
+
+            let mut steps = scc
+                .iter()
+                .map(|step| (Requirements::get_name(*step, ast), *step))
+                .collect::<Vec<_>>();
+            steps.sort();
+            errors.push((
+                codes::CIRCULAR_STEP_TRANSITION.with(format!(
+                    "circular transition between steps {}",
+                    steps
+                        .iter()
+                        .map(|(name, _)| name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )),
+                Functions::get_loc(steps[0].1, ast),
+            ));
+            cyclic.extend(scc);
+        }
+        cyclic
+    }
+
+    /// A workflow starts with the steps no step transitions to. Steps of a
+    /// cycle are already reported, the steps they lead to are reachable.
+    fn check_reachable<E, R>(
+        workflow: toto_ast::GraphHandle,
+        cyclic: &HashSet<toto_ast::GraphHandle>,
+        ast: &toto_ast::AST<E, R>,
+        errors: &mut Vec<(ParseError, toto_ast::GraphHandle)>,
+    ) where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let steps = Self::get_steps(workflow, ast);
+        let step_graph = EdgeFiltered::from_fn(ast, |e| Self::is_transition(e.weight().as_tosca()));
+
+        let mut reachable = HashSet::new();
+        for (_, step) in steps.iter() {
+            let is_initial = !ast
+                .edges_directed(*step, Incoming)
+                .any(|e| Self::is_transition(e.weight().as_tosca()));
+            if !(is_initial || cyclic.contains(step)) || reachable.contains(step) {
+                continue;
+            }
+            let mut dfs = Dfs::new(&step_graph, *step);
+            while let Some(n) = dfs.next(&step_graph) {
+                reachable.insert(n);
+            }
+        }
+
+        for (name, step) in steps {
+            if !reachable.contains(&step) {
+                errors.push((
+                    codes::UNREACHABLE_STEP.with(format!("step {} is unreachable", name)),
+                    Functions::get_loc(step, ast),
+                ));
+            }
+        }
+    }
+
+    /// `<interface_name>.<operation_name>`, an operation of the type of the
+    /// step's target node template. Operations of relationships, with a
+    /// `target_relationship`, aren't resolved.
+    fn resolve_operation<E, R>(
+        step: toto_ast::GraphHandle,
+        activity: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+        errors: &mut Vec<(ParseError, toto_ast::GraphHandle)>,
+    ) -> Option<toto_ast::GraphHandle>
+    where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let operation = graph::get_target(activity, ast, |rel| {
+            matches!(rel, crate::Relation::Operation(_))
+        })?;
+        let name = toto_yaml::as_string(operation, ast)?.0.clone();
+        let Some((interface_name, operation_name)) = name.split_once('.') else {
+            errors.push((
+                codes::UNKNOWN_OPERATION.with("expected <interface_name>.<operation_name>"),
+                operation,
+            ));
+            return None;
+        };
+
+        if graph::get_target(step, ast, |rel| {
+            matches!(rel, crate::Relation::TargetRelationship(_))
+        })
+        .is_some()
+        {
+            return None;
+        }
+        let template = graph::get_target(step, ast, |rel| {
+            matches!(rel, crate::Relation::TargetNode(_))
+        })?;
+        let node_type = graph::get_target(template, ast, |rel| {
+            matches!(rel, crate::Relation::HasType(_))
+        })?;
+
+        let Some(interface) = graph::find_definition(node_type, interface_name, ast, |e| {
+            matches!(e, crate::Entity::Interface(_))
+        }) else {
+            errors.push((
+                codes::UNKNOWN_OPERATION.with(format!(
+                    "{} has no interface {}",
+                    Requirements::get_type_name(node_type, ast),
+                    interface_name
+                )),
+                operation,
+            ));
+            return None;
+        };
+        let definition = graph::find_definition(interface, operation_name, ast, |e| {
+            matches!(e, crate::Entity::Operation(_))
+        });
+        if definition.is_none() {
+            errors.push((
+                codes::UNKNOWN_OPERATION.with(format!(
+                    "interface {} has no operation {}",
+                    interface_name, operation_name
+                )),
+                operation,
+            ));
+        }
+        definition
+    }
+
+    fn check_state<E, R>(
+        activity: toto_ast::GraphHandle,
+        ast: &toto_ast::AST<E, R>,
+        errors: &mut Vec<(ParseError, toto_ast::GraphHandle)>,
+    ) where
+        E: ToscaCompatibleEntity,
+        R: ToscaCompatibleRelation,
+    {
+        let Some(state) = graph::get_target(activity, ast, |rel| {
+            matches!(rel, crate::Relation::NodeState(_))
+        }) else {
+            return;
+        };
+        let Some(name) = toto_yaml::as_string(state, ast) else {
+            return;
+        };
+        if !NODE_STATES.contains(&name.0.as_str()) {
+            errors.push((
+                codes::UNKNOWN_NODE_STATE.with(format!("unknown node state: {}", name.0)),
+                state,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::visit::EdgeRef;
+
+    use crate::grammar::tests::{errors, parse};
+    use crate::{AsToscaEntity, AsToscaRelation};
+
+    use super::Workflows;
+
+    #[test]
+    fn workflows() {
+        let ast = parse("../tests/tosca_2_0_workflows.yaml");
+
+        assert_eq!(
+            errors(&ast),
+            vec![
+                (54, "interface Standard has no operation run".to_string()),
+                (55, "unknown node state: running".to_string()),
+                (59, "WebServer has no interface Lifecycle".to_string()),
+                (60, "expected <interface_name>.<operation_name>".to_string()),
+                (61, "unknown workflow step: finish".to_string()),
+                (
+                    74,
+                    "circular transition between steps check, install".to_string()
+                ),
+            ]
+        );
+
+        let deploy = ast
+            .edge_references()
+            .find_map(|e| match e.weight().as_tosca() {
+                Some(crate::Relation::Definition(crate::DefinitionRelation(name)))
+                    if name == "deploy" =>
+                {
+                    Some(e.target())
+                }
+                _ => None,
+            })
+            .unwrap();
+        let steps = Workflows::get_steps(deploy, &ast);
+        let step = |name: &str| steps.iter().find(|(n, _)| n == name).unwrap().1;
+        assert_eq!(
+            Workflows::get_next(step("create"), false, &ast),
+            vec![step("configure")]
+        );
+        assert_eq!(
+            Workflows::get_next(step("create"), true, &ast),
+            vec![step("cleanup")]
+        );
+
+        // `Standard.configure` is resolved against the interface of `web`.
+        let resolved = ast
+            .edges(step("configure"))
+            .filter(|e| {
+                matches!(
+                    e.weight().as_tosca(),
+                    Some(crate::Relation::WorkflowActivity(_))
+                )
+            })
+            .flat_map(|e| ast.edges(e.target()))
+            .filter(|e| matches!(e.weight().as_tosca(), Some(crate::Relation::DefinedBy(_))))
+            .map(|e| ast.node_weight(e.target()).unwrap().as_tosca())
+            .collect::<Vec<_>>();
+        assert!(matches!(
+            resolved.as_slice(),
+            [Some(crate::Entity::Operation(_))]
+        ));
+    }
+}
@@ -82,7 +82,7 @@ pub struct YamlMapKey;
 pub struct YamlMapValue;
 
 #[derive(Debug, Clone)]
-pub struct YamlListValue(usize);
+pub struct YamlListValue(pub usize);
 
 #[derive(Debug, Clone, From, TryInto)]
 #[try_into(owned, ref, ref_mut)]
@@ -263,6 +263,17 @@ where
         .and_then(|yaml_node| yaml_node.try_into().ok())
 }
 
+pub fn as_float<E, R>(n: toto_ast::GraphHandle, ast: &toto_ast::AST<E, R>) -> Option<&YamlFloat>
+where
+    E: AsYamlEntity,
+    R: AsYamlRelation,
+{
+    ast.node_weight(n)
+        .expect("node not found")
+        .as_yaml()
+        .and_then(|yaml_node| yaml_node.try_into().ok())
+}
+
 pub fn as_bool<E, R>(n: toto_ast::GraphHandle, ast: &toto_ast::AST<E, R>) -> Option<&YamlBool>
 where
     E: AsYamlEntity,
@@ -0,0 +1 @@
+print("configuring")